@@ -4,7 +4,10 @@
 
 use clap::Parser;
 use color_eyre::eyre::{self, Result};
-use pwsw::{cli::Args, cli::Command, commands, config::Config, daemon};
+use pwsw::{
+    cli::Args, cli::Command, cli::ConfigCommand, commands, config::Config, daemon,
+    trace::RecordOptions,
+};
 
 use std::sync::Arc;
 
@@ -40,6 +43,9 @@ async fn main() -> Result<()> {
     color_eyre::install().expect("Failed to install color_eyre");
 
     let args = Args::parse();
+    if let Some(path) = &args.config {
+        Config::set_config_path(path)?;
+    }
 
     // Handle subcommands
     match args.command {
@@ -47,11 +53,16 @@ async fn main() -> Result<()> {
         None => {
             init_logging();
             let config = Config::load()?;
-            commands::status(&config, false).await
+            commands::status(&config, false, None).await
         }
 
         // Daemon mode
-        Some(Command::Daemon { foreground }) => {
+        Some(Command::Daemon {
+            foreground,
+            record,
+            redact_titles,
+            dbus,
+        }) => {
             // Daemon handles its own logging initialization (file vs stdout)
             // But we need to load config first
             let config = Config::load()?;
@@ -61,6 +72,7 @@ async fn main() -> Result<()> {
                 eyre::bail!(
                     "No sinks configured. The daemon cannot start without sinks.\n\n\
                      Add sinks via:\n  \
+                     pwsw init       (detect audio outputs)\n  \
                      pwsw tui        (interactive setup)\n  \
                      pwsw list-sinks (discover available sinks)\n\n\
                      Or edit: {}",
@@ -68,32 +80,88 @@ async fn main() -> Result<()> {
                 );
             }
 
-            daemon::run(Arc::new(config), foreground).await
+            let record = record.map(|path| RecordOptions {
+                path,
+                redact_titles,
+            });
+            daemon::run(Arc::new(config), foreground, record, dbus).await
         }
 
         // Hybrid commands (work with or without daemon)
-        Some(Command::Status { json }) => {
+        Some(Command::Status { json, format }) => {
             init_logging();
             let config = Config::load()?;
-            commands::status(&config, json).await
+            commands::status(&config, json, format.as_deref()).await
         }
 
         // IPC-based commands (require daemon)
         Some(Command::Shutdown) => commands::shutdown().await,
 
+        Some(Command::Reload) => commands::reload().await,
+
+        Some(Command::Pause { duration }) => commands::pause(duration).await,
+
+        Some(Command::Resume) => commands::resume().await,
+
+        Some(Command::Release) => commands::release().await,
+
         Some(Command::ListWindows { json }) => commands::list_windows(json).await,
 
+        Some(Command::Explain { json }) => commands::explain(json).await,
+
         Some(Command::TestRule { pattern, json }) => commands::test_rule(&pattern, json).await,
 
         // Local commands (no daemon needed)
-        Some(Command::ListSinks { json }) => {
+        Some(Command::ListSinks { json, format }) => {
             init_logging();
 
             let config = Config::load().ok();
-            commands::list_sinks(config.as_ref(), json)
+            commands::list_sinks(config.as_ref(), json, format.as_deref())
         }
 
-        Some(Command::Validate) => {
+        Some(Command::Simulate {
+            app_id,
+            title,
+            events,
+            json,
+        }) => {
+            init_logging();
+            let config = Config::load()?;
+            if config.sinks.is_empty() {
+                eyre::bail!("No sinks configured. Add sinks via 'pwsw tui' first.");
+            }
+            commands::simulate(
+                config,
+                app_id.as_deref(),
+                title.as_deref(),
+                events.as_deref(),
+                json,
+            )
+            .await
+        }
+
+        Some(Command::Record {
+            file,
+            redact_titles,
+        }) => {
+            init_logging();
+            commands::record(&RecordOptions {
+                path: file,
+                redact_titles,
+            })
+            .await
+        }
+
+        Some(Command::Replay { file, json }) => {
+            init_logging();
+            let config = Config::load()?;
+            if config.sinks.is_empty() {
+                eyre::bail!("No sinks configured. Add sinks via 'pwsw tui' first.");
+            }
+            commands::replay(config, &file, json).await
+        }
+
+        Some(Command::Validate { live }) => {
             init_logging();
             let config = Config::load()?;
 
@@ -108,16 +176,54 @@ async fn main() -> Result<()> {
             }
 
             config.print_summary();
+            if live {
+                let code = commands::validate_live(&config);
+                if code != 0 {
+                    std::process::exit(code);
+                }
+            }
             Ok(())
         }
 
-        Some(Command::SetSink { sink }) => {
+        Some(Command::Init { yes, force }) => {
+            init_logging();
+            commands::init(yes, force)
+        }
+
+        Some(Command::Config { command }) => {
+            init_logging();
+            match command {
+                ConfigCommand::Migrate => commands::config_migrate(),
+                ConfigCommand::Schema => commands::config_schema(),
+                ConfigCommand::AddSink {
+                    name,
+                    desc,
+                    icon,
+                    default,
+                } => commands::config_add_sink(name, desc, icon, default),
+                ConfigCommand::AddRule {
+                    app_id,
+                    title,
+                    sink,
+                    desc,
+                } => commands::config_add_rule(&app_id, title.as_deref(), sink, desc),
+                ConfigCommand::RemoveRule { index } => commands::config_remove_rule(index),
+                ConfigCommand::MoveRule { from, to } => commands::config_move_rule(from, to),
+                ConfigCommand::Set { key, value } => commands::config_set(&key, &value),
+                ConfigCommand::Backups { file } => commands::config_backups(file.as_deref()),
+                ConfigCommand::Restore { id, file } => {
+                    commands::config_restore(&id, file.as_deref())
+                }
+            }
+        }
+
+        Some(Command::SetSink { sink, hold }) => {
             init_logging();
             let config = Config::load()?;
             if config.sinks.is_empty() {
                 eyre::bail!("No sinks configured. Add sinks via 'pwsw tui' first.");
             }
-            commands::set_sink_smart(&config, &sink)
+            commands::set_sink_smart(&config, &sink, hold).await
         }
 
         Some(Command::NextSink) => {
@@ -126,7 +232,7 @@ async fn main() -> Result<()> {
             if config.sinks.is_empty() {
                 eyre::bail!("No sinks configured. Add sinks via 'pwsw tui' first.");
             }
-            commands::cycle_sink(&config, commands::Direction::Next)
+            commands::cycle_sink(&config, commands::Direction::Next).await
         }
 
         Some(Command::PrevSink) => {
@@ -135,7 +241,7 @@ async fn main() -> Result<()> {
             if config.sinks.is_empty() {
                 eyre::bail!("No sinks configured. Add sinks via 'pwsw tui' first.");
             }
-            commands::cycle_sink(&config, commands::Direction::Prev)
+            commands::cycle_sink(&config, commands::Direction::Prev).await
         }
 
         // TUI - Terminal User Interface
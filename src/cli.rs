@@ -13,8 +13,8 @@ use clap::{Parser, Subcommand};
 #[command(about = "PipeWire Switcher - Automatically switch audio sinks based on active windows")]
 #[command(after_help = "\
 GETTING STARTED:
-  1. pwsw validate         Check config file syntax
-  2. pwsw list-sinks       See available audio outputs
+  1. pwsw init             Create a config from your audio outputs
+  2. pwsw validate         Check config file syntax
   3. pwsw daemon           Start the daemon
   4. pwsw                  Check status
 
@@ -22,19 +22,31 @@ DAEMON CONTROL:
   daemon               Start daemon in background
   daemon --foreground  Start with logs visible (for debugging)
   shutdown             Stop the daemon
+  reload               Reload config in the running daemon
+  pause [DURATION]     Pause automatic switching (e.g., pause 30m)
+  resume               Resume automatic switching
 
 QUERYING (requires running daemon):
   [no subcommand]     Show status, uptime, and current audio output
-  status              Same as above (supports --json)
+  status              Same as above (supports --json and --format)
   list-windows        Show all open windows (tracked vs untracked)
   test-rule PATTERN   Test regex against windows (checks app_id & title)
 
+SETUP:
+  init                Create a config from the detected audio outputs
+  config backups      List saved config backups with their changes
+  config restore ID   Restore a config backup (1 = newest; --file for includes)
+
 QUERYING (no daemon needed):
-  list-sinks          List available PipeWire audio outputs
+  list-sinks          List available PipeWire audio outputs (supports --format)
   validate            Check config file syntax
+  validate --live     Also check configured sinks against PipeWire
 
-MANUAL SINK CONTROL (no daemon needed):
+MANUAL SINK CONTROL (uses the daemon if running, otherwise switches directly):
   set-sink SINK       Switch to specific sink (by desc, name, or position 1/2/3)
+  set-sink SINK --hold [DURATION]
+                      Switch and ignore rules until released (daemon only)
+  release             Release a manual hold and return to rule-based switching
   next-sink           Cycle to next configured sink (wraps around)
   prev-sink           Cycle to previous configured sink (wraps around)
 
@@ -44,12 +56,16 @@ HOW IT WORKS:
   When all matching windows close, returns to default output.
 
 CONFIG & IPC:
-  Config: ~/.config/pwsw/config.toml
+  Config: ~/.config/pwsw/config.toml (or --config PATH)
   Socket: $XDG_RUNTIME_DIR/pwsw.sock
 
-  After editing config, restart the daemon:
-    pwsw shutdown && pwsw daemon")]
+  Config changes are picked up automatically; to force a reload:
+    pwsw reload")]
 pub struct Args {
+    /// Use this config file instead of ~/.config/pwsw/config.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -62,6 +78,18 @@ pub enum Command {
         /// Run in foreground with logs visible
         #[arg(short, long)]
         foreground: bool,
+
+        /// Also record every window event to a trace file (see `pwsw replay`)
+        #[arg(long, value_name = "FILE")]
+        record: Option<std::path::PathBuf>,
+
+        /// Replace window titles with a placeholder in the trace
+        #[arg(long, requires = "record")]
+        redact_titles: bool,
+
+        /// Also serve requests as `io.github.pwsw` on the D-Bus session bus
+        #[arg(long)]
+        dbus: bool,
     },
 
     /// Show daemon status, uptime, and current audio output
@@ -69,16 +97,53 @@ pub enum Command {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+
+        /// Output a single line from a template (e.g., `'{sink_glyph} {sink_desc}'`)
+        #[arg(
+            long,
+            value_name = "TEMPLATE",
+            conflicts_with = "json",
+            long_help = "Output a single line from a template (e.g., '{sink_glyph} {sink_desc}')\n\n\
+                         Placeholders: {sink_desc} {sink_name} {sink_icon} {sink_glyph}\n\
+                         {window_app_id} {rule_desc} {tracked_count} {daemon} {paused} {hold}\n\
+                         Use {{ and }} for literal braces."
+        )]
+        format: Option<String>,
     },
 
     /// Stop the daemon gracefully
     Shutdown,
 
+    /// Reload the config file in the running daemon
+    Reload,
+
+    /// Pause automatic switching (windows are still tracked)
+    Pause {
+        /// Resume automatically after this long (e.g., "90s", "15m", "1h30m")
+        #[arg(value_parser = crate::parse_duration)]
+        duration: Option<std::time::Duration>,
+    },
+
+    /// Resume automatic switching and re-evaluate windows
+    Resume,
+
     /// List available `PipeWire` audio outputs
     ListSinks {
         /// Output in JSON format
         #[arg(long)]
         json: bool,
+
+        /// Output one line per configured sink from a template
+        #[arg(
+            long,
+            value_name = "TEMPLATE",
+            conflicts_with = "json",
+            long_help = "Output one line per configured sink from a template\n\n\
+                         Placeholders: {index} {sink_desc} {sink_name} {sink_icon} {sink_glyph}\n\
+                         {status} {default} {current}\n\
+                         Use {{ and }} for literal braces."
+        )]
+        format: Option<String>,
     },
 
     /// Show all open windows (tracked vs untracked)
@@ -88,8 +153,75 @@ pub enum Command {
         json: bool,
     },
 
+    /// Explain which window and rule decided the current sink
+    Explain {
+        /// Output in `JSON` format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Simulate which rule and sink windows get, offline (no daemon needed)
+    Simulate {
+        /// Window `app_id` to simulate opening
+        #[arg(long, required_unless_present = "events")]
+        app_id: Option<String>,
+
+        /// Window title (defaults to empty)
+        #[arg(long, requires = "app_id")]
+        title: Option<String>,
+
+        /// Replay open/change/close events from a JSON Lines file
+        #[arg(long, value_name = "FILE", conflicts_with = "app_id")]
+        events: Option<std::path::PathBuf>,
+
+        /// Output in `JSON` format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Record compositor window events to a trace file until Ctrl-C
+    Record {
+        /// Trace file to write (JSON Lines)
+        file: std::path::PathBuf,
+
+        /// Replace window titles with a placeholder in the trace
+        #[arg(long)]
+        redact_titles: bool,
+    },
+
+    /// Replay a recorded trace through rule matching (no daemon needed)
+    Replay {
+        /// Trace file written by `pwsw record` or `pwsw daemon --record`
+        file: std::path::PathBuf,
+
+        /// Output in `JSON` format
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create a config from the audio outputs found in `PipeWire`
+    Init {
+        /// Add every detected output without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Replace an existing config file (it is backed up first)
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Check config file syntax (no daemon needed)
-    Validate,
+    Validate {
+        /// Also check the configured sinks against `PipeWire` (exit code 2 if any is missing, 3 if the query fails)
+        #[arg(long)]
+        live: bool,
+    },
+
+    /// Manage the config file (no daemon needed)
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
 
     /// Test regex pattern against all windows (`app_id` & title)
     TestRule {
@@ -105,8 +237,20 @@ pub enum Command {
     SetSink {
         /// Sink reference (description, node name, or position)
         sink: String,
+
+        /// Keep this sink until released, ignoring rules (optionally for a duration like "30m")
+        #[arg(
+            long,
+            value_name = "DURATION",
+            num_args = 0..=1,
+            value_parser = crate::parse_duration
+        )]
+        hold: Option<Option<std::time::Duration>>,
     },
 
+    /// Release a manual hold and return to rule-based switching
+    Release,
+
     /// Cycle to next configured sink
     NextSink,
 
@@ -116,3 +260,93 @@ pub enum Command {
     /// Terminal UI for configuration and monitoring
     Tui,
 }
+
+/// `pwsw config` subcommands
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Upgrade the config (and included files) to the current layout version
+    Migrate,
+
+    /// Print a JSON Schema of the config file (for taplo / Even Better TOML)
+    Schema,
+
+    /// Add a sink (the first sink becomes the default)
+    AddSink {
+        /// `PipeWire` node name (see `pwsw list-sinks`)
+        #[arg(long)]
+        name: String,
+
+        /// Description used in rules and notifications
+        #[arg(long)]
+        desc: String,
+
+        /// Icon name for notifications
+        #[arg(long)]
+        icon: Option<String>,
+
+        /// Make this the default sink
+        #[arg(long)]
+        default: bool,
+    },
+
+    /// Add a rule after the existing ones
+    AddRule {
+        /// Regex matching the window's `app_id`
+        #[arg(long)]
+        app_id: String,
+
+        /// Regex matching the window title
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Target sink (description, node name, or position)
+        #[arg(long)]
+        sink: String,
+
+        /// Label shown in notifications
+        #[arg(long)]
+        desc: Option<String>,
+    },
+
+    /// Remove a rule by its position (1 = first)
+    RemoveRule {
+        /// Rule position, as shown by `pwsw validate`
+        index: usize,
+    },
+
+    /// Move a rule to another position (1 = first)
+    MoveRule {
+        /// Current position of the rule
+        from: usize,
+
+        /// Position the rule ends up at
+        to: usize,
+    },
+
+    /// Set a [settings] or [notifications] key (e.g., `set log_level debug`)
+    Set {
+        /// Key name, optionally prefixed by its section (e.g., `notifications.urgency`)
+        key: String,
+
+        /// New value; an empty string resets the key to its default
+        #[arg(allow_hyphen_values = true)]
+        value: String,
+    },
+
+    /// List saved backups of the config, with their differences from the current file
+    Backups {
+        /// An included file to list instead (path as in `include`)
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+    },
+
+    /// Replace the config with a saved backup
+    Restore {
+        /// Backup id or position from `pwsw config backups` (1 = newest)
+        id: String,
+
+        /// An included file to restore instead (path as in `include`)
+        #[arg(long)]
+        file: Option<std::path::PathBuf>,
+    },
+}
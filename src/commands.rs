@@ -1,23 +1,61 @@
 //! CLI commands
 //!
-//! Implements both local commands (list-sinks, validate) and IPC-based commands
-//! that communicate with the daemon (status, reload, list-windows, test-rule).
+//! Implements both local commands (list-sinks, validate, config, simulate, record, replay) and
+//! IPC-based commands that communicate with the daemon (status, reload, list-windows,
+//! explain, test-rule).
 
-use color_eyre::eyre::{self, Result};
+use color_eyre::eyre::{self, Context, Result};
 use crossterm::style::Stylize;
+use regex::Regex;
 use std::collections::HashSet;
-use tracing::{info, warn};
+use std::path::{Path, PathBuf};
 
-use crate::config::Config;
+use crate::compositor::{WindowEvent, parse_events};
+use crate::config::{Config, Rule, SinkConfig};
+use crate::config_backup;
 use crate::ipc::{self, Request, Response};
-use crate::notification::{get_sink_icon, send_notification};
+use crate::notification::{SwitchDetails, get_sink_icon, switch_notification};
 use crate::pipewire::{
     ActiveSink, ActiveSinkJson, ConfiguredSinkJson, ListSinksJson, PipeWire, ProfileSink,
     ProfileSinkJson,
 };
+use crate::state::{
+    ManualRequest, ManualSwitch, PriorityMode, State, Verdict, plan_manual_switch,
+    switch_audio_blocking,
+};
 use crate::style::PwswStyle;
+use crate::template::Template;
+use crate::trace::{RecordOptions, TraceRecorder, read_trace};
+
+pub use crate::state::Direction;
 use std::fmt::Write;
 
+/// Placeholders accepted by `status --format`
+pub const STATUS_PLACEHOLDERS: &[&str] = &[
+    "sink_desc",
+    "sink_name",
+    "sink_icon",
+    "sink_glyph",
+    "window_app_id",
+    "rule_desc",
+    "tracked_count",
+    "daemon",
+    "paused",
+    "hold",
+];
+
+/// Placeholders accepted by `list-sinks --format` (rendered once per configured sink)
+pub const LIST_SINKS_PLACEHOLDERS: &[&str] = &[
+    "index",
+    "sink_desc",
+    "sink_name",
+    "sink_icon",
+    "sink_glyph",
+    "status",
+    "default",
+    "current",
+];
+
 // ============================================================================
 // Local Commands (no daemon needed)
 // ============================================================================
@@ -42,14 +80,41 @@ fn get_sink_status(
 /// # Errors
 /// Returns an error if `PipeWire` query fails or `JSON` serialization fails.
 // Sink listing with both `JSON` and human-readable formatting - cohesive output logic
-pub fn list_sinks(config: Option<&Config>, json_output: bool) -> Result<()> {
+pub fn list_sinks(config: Option<&Config>, json_output: bool, format: Option<&str>) -> Result<()> {
+    let template = format
+        .map(|f| Template::parse(f, LIST_SINKS_PLACEHOLDERS))
+        .transpose()?;
+
     let objects = PipeWire::dump()?;
     let active = PipeWire::get_active_sinks(&objects);
     let profile = PipeWire::get_profile_sinks(&objects, &active);
 
     let current_default = active.iter().find(|s| s.is_default).map(|s| s.name.clone());
 
-    if json_output {
+    if let Some(template) = template {
+        let Some(cfg) = config else {
+            eyre::bail!("--format renders configured sinks, but no valid config was loaded");
+        };
+
+        for (i, sink) in cfg.sinks.iter().enumerate() {
+            let index = (i + 1).to_string();
+            let icon = get_sink_icon(sink);
+            let is_current = current_default.as_deref() == Some(sink.name.as_str());
+            println!(
+                "{}",
+                template.render(&[
+                    ("index", &index),
+                    ("sink_desc", &sink.desc),
+                    ("sink_name", &sink.name),
+                    ("sink_icon", &icon),
+                    ("sink_glyph", sink.status_glyph().unwrap_or_default()),
+                    ("status", get_sink_status(&sink.name, &active, &profile)),
+                    ("default", if sink.default { "default" } else { "" }),
+                    ("current", if is_current { "current" } else { "" }),
+                ])
+            );
+        }
+    } else if json_output {
         let configured_names: HashSet<&str> = config
             .map(|c| c.sinks.iter().map(|s| s.name.as_str()).collect())
             .unwrap_or_default();
@@ -86,6 +151,7 @@ pub fn list_sinks(config: Option<&Config>, json_output: bool) -> Result<()> {
                                 name: s.name.clone(),
                                 desc: s.desc.clone(),
                                 icon: s.icon.clone(),
+                                glyph: s.glyph.clone(),
                                 is_default_config: s.default,
                                 status: status.to_string(),
                             }
@@ -185,131 +251,700 @@ pub fn list_sinks(config: Option<&Config>, json_output: bool) -> Result<()> {
     Ok(())
 }
 
-/// Direction for sink cycling
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Direction {
-    Next,
-    Prev,
+/// Upgrade the config file and its includes to the current layout version
+///
+/// # Errors
+/// Returns an error if a config file cannot be read, is from a newer pwsw, or
+/// cannot be written.
+pub fn config_migrate() -> Result<()> {
+    let path = Config::get_config_path()?;
+    if !path.exists() {
+        eyre::bail!("Config file not found: {}", path.display());
+    }
+
+    let migrated = Config::migrate(&path)?;
+    if migrated.is_empty() {
+        println!(
+            "Config is already at version {}",
+            crate::config::CONFIG_VERSION.to_string().technical()
+        );
+        return Ok(());
+    }
+    for (file, from) in &migrated {
+        println!(
+            "{} {} (version {} → {})",
+            "Migrated".success(),
+            file.display(),
+            from,
+            crate::config::CONFIG_VERSION
+        );
+    }
+    Ok(())
 }
 
-/// Set sink with smart toggle support
+/// Print the config file's JSON Schema
 ///
 /// # Errors
-/// Returns an error if the sink reference is invalid or sink activation fails.
-pub fn set_sink_smart(config: &Config, sink_ref: &str) -> Result<()> {
-    let target = config.resolve_sink(sink_ref).ok_or_else(|| {
-        let available: Vec<_> = config
-            .sinks
-            .iter()
-            .enumerate()
-            .map(|(i, s)| format!("{}. '{}'", i + 1, s.desc))
-            .collect();
-        eyre::eyre!(
-            "Unknown sink '{}'. Available: {}",
-            sink_ref,
-            available.join(", ")
-        )
-    })?;
+/// Returns an error if the schema cannot be serialized.
+pub fn config_schema() -> Result<()> {
+    let schema = crate::config::json_schema()?;
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
 
-    let current = PipeWire::get_default_sink_name()?;
-    let default = config
-        .get_default_sink()
-        .ok_or_else(|| eyre::eyre!("No default sink configured"))?;
+/// The config file, or `file` relative to its directory (as in `include`)
+fn backup_target(path: &Path, file: Option<&Path>) -> PathBuf {
+    match file {
+        Some(file) => path.parent().unwrap_or_else(|| Path::new(".")).join(file),
+        None => path.to_path_buf(),
+    }
+}
+
+/// List the backups of the config (or of the included `file`), newest first, with
+/// their differences from the current file
+///
+/// # Errors
+/// Returns an error if the config path cannot be determined or a backup cannot be read.
+pub fn config_backups(file: Option<&Path>) -> Result<()> {
+    let path = Config::get_config_path()?;
+    let target = backup_target(&path, file);
+    let backups = config_backup::list(&target)?;
+    if backups.is_empty() {
+        println!(
+            "No backups of {} yet (one is taken each time the file is saved)",
+            target.display()
+        );
+        return Ok(());
+    }
 
-    if config.settings.set_smart_toggle && current == target.name {
-        if target.name == default.name {
-            println!("Already on: {}", default.desc.as_str().bold());
-            return Ok(());
+    let current = std::fs::read_to_string(&target).unwrap_or_default();
+    println!(
+        "{} {}",
+        format!("BACKUPS OF {}:", target.display()).header(),
+        "(- current file, + backup)".dim()
+    );
+    for (position, backup) in backups.iter().enumerate() {
+        let text = std::fs::read_to_string(&backup.path)
+            .with_context(|| format!("Failed to read backup: {}", backup.path.display()))?;
+        println!(
+            "\n{} {}  {}",
+            format!("{}.", position + 1).bold(),
+            backup.id.as_str().technical(),
+            backup.created().dim()
+        );
+        let changes = config_backup::changed_lines(&current, &text);
+        if changes.is_empty() {
+            println!("  {}", "Same as the current file".dim());
+        }
+        for (sign, line) in changes {
+            let line = format!("  {sign} {line}");
+            if sign == '-' {
+                println!("{}", line.error());
+            } else {
+                println!("{}", line.success());
+            }
         }
-        info!("Toggle → default: {}", default.desc);
-        PipeWire::activate_sink(&default.name)?;
+    }
+    let file_arg = file.map_or_else(String::new, |f| format!(" --file {}", f.display()));
+    println!(
+        "\nRestore one with: {}",
+        format!("pwsw config restore <id or position>{file_arg}").technical()
+    );
+    if file.is_none()
+        && Config::load_from_path(&path).is_ok_and(|config| !config.includes.is_empty())
+    {
         println!(
-            "{} {}",
-            "Switched to:".success(),
-            default.desc.as_str().bold()
+            "{}",
+            "Included files have their own backups: add --file <path as in include>".dim()
         );
+    }
+    Ok(())
+}
 
-        if config.settings.notify_manual {
-            let icon = get_sink_icon(default);
-            if let Err(e) = send_notification("Audio Output", &default.desc, Some(&icon)) {
-                warn!("Notification failed: {}", e);
+/// Replace the config file (or the included `file`) with a backup listed by
+/// `pwsw config backups`
+///
+/// # Errors
+/// Returns an error if no backup matches `id`, the config is not valid with the backup
+/// in place, or the file cannot be written.
+pub fn config_restore(id: &str, file: Option<&Path>) -> Result<()> {
+    let path = Config::get_config_path()?;
+    let target = backup_target(&path, file);
+    let backup = config_backup::find(&target, id)?;
+    Config::restore_backup(&path, &target, &backup)?;
+    println!(
+        "{} {}",
+        format!("Restored backup {} from {}", backup.id, backup.created()).success(),
+        format!("({})", target.display()).dim()
+    );
+    Ok(())
+}
+
+/// Below this similarity a sink's `desc` is reported as differing from the live description
+const DESC_SIMILARITY_THRESHOLD: f64 = 0.3;
+
+/// `validate --live` exit status when a configured sink is not found
+const EXIT_SINK_MISSING: i32 = 2;
+
+/// `validate --live` exit status when `PipeWire` cannot be queried
+const EXIT_PIPEWIRE_FAILED: i32 = 3;
+
+/// Where `validate --live` found a configured sink
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LiveStatus {
+    Active,
+    ProfileSwitch,
+    Missing,
+}
+
+/// Look up `sink` among the live sinks, with the live description if it was found
+fn live_status<'a>(
+    sink: &SinkConfig,
+    active: &'a [ActiveSink],
+    profile: &'a [ProfileSink],
+) -> (LiveStatus, Option<&'a str>) {
+    if let Some(a) = active.iter().find(|a| a.name == sink.name) {
+        (LiveStatus::Active, Some(&a.description))
+    } else if let Some(p) = profile.iter().find(|p| p.predicted_name == sink.name) {
+        (LiveStatus::ProfileSwitch, Some(&p.description))
+    } else {
+        (LiveStatus::Missing, None)
+    }
+}
+
+/// Exit status of `validate --live` for the sink statuses (`None` = query failed)
+///
+/// Distinct from 1 (invalid config) so scripts can tell the cases apart.
+fn live_exit_code(statuses: Option<&[LiveStatus]>) -> i32 {
+    match statuses {
+        None => EXIT_PIPEWIRE_FAILED,
+        Some(statuses) if statuses.contains(&LiveStatus::Missing) => EXIT_SINK_MISSING,
+        Some(_) => 0,
+    }
+}
+
+/// Check the configured sinks against the live `PipeWire` graph (`validate --live`)
+///
+/// Prints whether each sink is active, reachable through a profile switch, or missing,
+/// and warns when its `desc` looks unrelated to the live description. Returns the exit
+/// status (see [`live_exit_code`]); a failed `PipeWire` query is reported, not returned.
+#[must_use]
+pub fn validate_live(config: &Config) -> i32 {
+    let objects = match PipeWire::dump() {
+        Ok(objects) => objects,
+        Err(e) => {
+            eprintln!(
+                "\n{} {}",
+                "✗".error(),
+                format!("Failed to query PipeWire: {e:#}").error()
+            );
+            return live_exit_code(None);
+        }
+    };
+    let active = PipeWire::get_active_sinks(&objects);
+    let profile = PipeWire::get_profile_sinks(&objects, &active);
+
+    println!("\n{}", "Live PipeWire state:".header());
+    let mut statuses = Vec::new();
+    for (i, sink) in config.sinks.iter().enumerate() {
+        let (status, live_desc) = live_status(sink, &active, &profile);
+        statuses.push(status);
+        let label = match status {
+            LiveStatus::Active => "active".success(),
+            LiveStatus::ProfileSwitch => "requires profile switch".warning(),
+            LiveStatus::Missing => "not found".error(),
+        };
+        println!("  {}. {} - {}", i + 1, sink.desc.as_str().bold(), label);
+
+        match live_desc {
+            Some(live) if descs_differ(&sink.desc, live) => println!(
+                "     {} desc differs from the live description '{}'",
+                "⚠".warning(),
+                live
+            ),
+            Some(_) => {}
+            None => {
+                println!("     {}", sink.name.as_str().technical());
+                let rules: Vec<String> = config
+                    .rules
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, r)| {
+                        config
+                            .resolve_sink(&r.sink_ref)
+                            .is_some_and(|s| s.name == sink.name)
+                    })
+                    .map(|(i, _)| (i + 1).to_string())
+                    .collect();
+                if !rules.is_empty() {
+                    println!("     {} {}", "used by rules".dim(), rules.join(", "));
+                }
             }
         }
+    }
+
+    let missing = statuses
+        .iter()
+        .filter(|s| **s == LiveStatus::Missing)
+        .count();
+    if missing == 0 {
+        println!(
+            "\n{} {}",
+            "✓".success(),
+            "All configured sinks exist".success()
+        );
     } else {
-        info!("Switching to: {}", target.desc);
-        PipeWire::activate_sink(&target.name)?;
         println!(
-            "{} {}",
-            "Switched to:".success(),
-            target.desc.as_str().bold()
+            "\n{} {}",
+            "✗".error(),
+            format!(
+                "{missing} of {} configured sinks not found (see 'pwsw list-sinks')",
+                config.sinks.len()
+            )
+            .error()
         );
+    }
+    live_exit_code(Some(&statuses))
+}
 
-        if config.settings.notify_manual {
-            let icon = get_sink_icon(target);
-            if let Err(e) = send_notification("Audio Output", &target.desc, Some(&icon)) {
-                warn!("Notification failed: {}", e);
-            }
+/// Whether a configured `desc` looks unrelated to a sink's live description
+///
+/// Compares character pairs of both (ignoring case and punctuation), also against the
+/// description `pwsw init` would generate, so "HDMI" matches "Digital Stereo (HDMI)".
+fn descs_differ(desc: &str, live: &str) -> bool {
+    fn normalized(s: &str) -> String {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+    fn similarity(a: &str, b: &str) -> f64 {
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        if a.contains(b) || b.contains(a) {
+            return 1.0;
         }
+        let pairs =
+            |s: &str| -> HashSet<(char, char)> { s.chars().zip(s.chars().skip(1)).collect() };
+        let (a, b) = (pairs(a), pairs(b));
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)] // pair counts are tiny
+        let score = 2.0 * a.intersection(&b).count() as f64 / (a.len() + b.len()) as f64;
+        score
     }
 
-    Ok(())
+    let desc = normalized(desc);
+    let suggested = normalized(&crate::pipewire::suggested_sink_desc(live));
+    similarity(&desc, &normalized(live)).max(similarity(&desc, &suggested))
+        < DESC_SIMILARITY_THRESHOLD
 }
 
-/// Cycle through configured sinks
+/// Create a config file from the sinks found in `PipeWire`
+///
+/// Active sinks come first, then outputs reachable by a profile switch; the current
+/// default sink is marked `default = true`. Asks which outputs to keep unless `yes`.
+/// An existing config is only replaced with `force`, or when it has no sinks and rules
+/// yet (like the commented default written on first run).
 ///
 /// # Errors
-/// Returns an error if sink query or activation fails.
-pub fn cycle_sink(config: &Config, direction: Direction) -> Result<()> {
-    // Need at least 2 sinks to cycle
-    if config.sinks.len() < 2 {
-        println!("{}", "Only one sink configured, nothing to cycle".warning());
-        return Ok(());
+/// Returns an error if the config exists, `PipeWire` can't be queried or has no sinks,
+/// the answers are invalid, or the file cannot be written.
+pub fn init(yes: bool, force: bool) -> Result<()> {
+    use std::io::IsTerminal;
+
+    let path = Config::get_config_path()?;
+    if path.exists() && !force {
+        let replaceable = Config::load_from_path(&path)
+            .is_ok_and(|config| config.sinks.is_empty() && config.rules.is_empty());
+        if !replaceable {
+            eyre::bail!(
+                "Config already exists: {}\nUse --force to replace it (the old file is backed up).",
+                path.display()
+            );
+        }
+    }
+    if !yes && !std::io::stdin().is_terminal() {
+        eyre::bail!("No terminal to ask on. Use --yes to add every detected output.");
     }
 
-    let current = PipeWire::get_default_sink_name()?;
+    let objects = PipeWire::dump()?;
+    let active = PipeWire::get_active_sinks(&objects);
+    let profile = PipeWire::get_profile_sinks(&objects, &active);
+    let mut sinks = detected_sinks(&active, &profile);
+    if sinks.is_empty() {
+        eyre::bail!("No audio outputs found. Is PipeWire running? Check with 'pwsw list-sinks'.");
+    }
+
+    if !yes {
+        println!("{}", "DETECTED OUTPUTS:".header());
+        for (i, sink) in sinks.iter().enumerate() {
+            let status = match get_sink_status(&sink.name, &active, &profile) {
+                "active" if sink.default => "current default".success().to_string(),
+                "active" => "active".success().to_string(),
+                _ => "needs profile switch".warning().to_string(),
+            };
+            println!(
+                "  {}. {} {} {}",
+                i + 1,
+                sink.desc.as_str().bold(),
+                sink.name.as_str().technical(),
+                format!("({status})").dim()
+            );
+        }
+        println!();
+
+        let chosen = parse_selection(&prompt("Outputs to add (e.g. 1,3)", "all")?, sinks.len())?;
+        sinks = sinks
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| chosen.contains(i))
+            .map(|(_, sink)| sink)
+            .collect();
+
+        let current = sinks.iter().position(|s| s.default).unwrap_or(0);
+        let answer = prompt("Default output", &(current + 1).to_string())?;
+        let [default] = parse_selection(&answer, sinks.len())?[..] else {
+            eyre::bail!("Pick a single default output");
+        };
+        for (i, sink) in sinks.iter_mut().enumerate() {
+            sink.default = i == default;
+        }
+    }
+
+    let config = Config::create_with_sinks(&path, &sinks)?;
+    println!(
+        "{} Created {} with {} sinks (default: {})",
+        "✓".success(),
+        path.display(),
+        config.sinks.len(),
+        config
+            .get_default_sink()
+            .map_or("none", |s| s.desc.as_str())
+            .bold()
+    );
+    println!(
+        "\n  {} to add rules, then {} to start switching",
+        "pwsw tui".technical(),
+        "pwsw daemon".technical()
+    );
+    Ok(())
+}
 
-    // Find current sink's index in config, or start from default
-    let current_index = config
-        .sinks
+/// Config entries for the detected sinks: active ones first, then profile outputs
+///
+/// Descriptions are shortened and made unique; the current default sink (or the first
+/// sink) is the default.
+fn detected_sinks(active: &[ActiveSink], profile: &[ProfileSink]) -> Vec<SinkConfig> {
+    let found = active
         .iter()
-        .position(|s| s.name == current)
-        .unwrap_or_else(|| {
-            // Current sink not in config, find default's index
-            config.sinks.iter().position(|s| s.default).unwrap_or(0)
-        });
+        .map(|s| (&s.name, &s.description, s.is_default))
+        .chain(
+            profile
+                .iter()
+                .map(|s| (&s.predicted_name, &s.description, false)),
+        );
 
-    // Calculate next index with wrapping
-    let next_index = match direction {
-        Direction::Next => (current_index + 1) % config.sinks.len(),
-        Direction::Prev => {
-            if current_index == 0 {
-                config.sinks.len() - 1
-            } else {
-                current_index - 1
+    let mut descs = HashSet::new();
+    let mut sinks: Vec<SinkConfig> = found
+        .map(|(name, description, default)| {
+            let base = crate::pipewire::suggested_sink_desc(description);
+            let desc = (1..)
+                .map(|n| {
+                    if n == 1 {
+                        base.clone()
+                    } else {
+                        format!("{base} {n}")
+                    }
+                })
+                .find(|desc| descs.insert(desc.clone()))
+                .expect("unbounded range");
+            SinkConfig {
+                name: name.clone(),
+                desc,
+                icon: None,
+                glyph: None,
+                default,
+                source: None,
+                host: None,
             }
-        }
-    };
+        })
+        .collect();
 
-    let target = &config.sinks[next_index];
+    if !sinks.iter().any(|s| s.default)
+        && let Some(first) = sinks.first_mut()
+    {
+        first.default = true;
+    }
+    sinks
+}
 
-    // Already on target (shouldn't happen with >= 2 sinks, but be safe)
-    if target.name == current {
-        println!("Already on: {}", target.desc.as_str().bold());
-        return Ok(());
+/// Ask a question on stdin, returning `default` for an empty answer
+fn prompt(question: &str, default: &str) -> Result<String> {
+    use std::io::Write as _;
+
+    print!("{question} [{default}]: ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+    let answer = answer.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+/// 0-based indices picked by a 1-based list like `1,3` or `2 4` (`all` picks everything)
+fn parse_selection(answer: &str, count: usize) -> Result<Vec<usize>> {
+    if answer.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+    let mut picked = Vec::new();
+    for part in answer.split([',', ' ']).filter(|p| !p.is_empty()) {
+        match part.parse::<usize>() {
+            Ok(n) if (1..=count).contains(&n) => {
+                if !picked.contains(&(n - 1)) {
+                    picked.push(n - 1);
+                }
+            }
+            _ => eyre::bail!("'{part}' is not a number from 1 to {count}"),
+        }
     }
+    if picked.is_empty() {
+        eyre::bail!("Nothing selected");
+    }
+    Ok(picked)
+}
 
-    info!("Cycling to: {}", target.desc);
-    PipeWire::activate_sink(&target.name)?;
+/// Load the config, apply `edit` and save the result if it passes validation
+///
+/// `edit` returns the message printed on success. Nothing is written when the edit
+/// or validation fails.
+fn edit_config(edit: impl FnOnce(&mut Config) -> Result<String>) -> Result<()> {
+    let mut config = Config::load()?;
+    let message = edit(&mut config)?;
+    config.validate().wrap_err("Config not saved")?;
+    config.save()?;
     println!(
         "{} {}",
-        "Switched to:".success(),
-        target.desc.as_str().bold()
+        message.success(),
+        format!("({})", Config::get_config_path()?.display()).dim()
     );
+    Ok(())
+}
+
+/// 0-based index of the rule at 1-based `position`
+fn rule_index(config: &Config, position: usize) -> Result<usize> {
+    if position == 0 || position > config.rules.len() {
+        eyre::bail!(
+            "No rule at position {position} (the config has {} rules)",
+            config.rules.len()
+        );
+    }
+    Ok(position - 1)
+}
+
+/// Add a sink to the config file
+///
+/// The first sink, and a sink added with `default`, becomes the default sink.
+///
+/// # Errors
+/// Returns an error if the config cannot be loaded or saved, or the new sink is
+/// rejected by validation (e.g., a duplicate name).
+pub fn config_add_sink(
+    name: String,
+    desc: String,
+    icon: Option<String>,
+    default: bool,
+) -> Result<()> {
+    edit_config(|config| {
+        let default = default || config.sinks.is_empty();
+        if default {
+            for sink in &mut config.sinks {
+                sink.default = false;
+            }
+        }
+        let message = format!("Added sink '{desc}'");
+        config.push_sink(SinkConfig {
+            name,
+            desc,
+            icon,
+            glyph: None,
+            default,
+            source: None,
+            host: None,
+        });
+        Ok(message)
+    })
+}
+
+/// Add a rule after the existing rules of the config file
+///
+/// # Errors
+/// Returns an error if a pattern is not a valid regex, the config cannot be loaded
+/// or saved, or the rule is rejected by validation (e.g., an unknown sink).
+pub fn config_add_rule(
+    app_id: &str,
+    title: Option<&str>,
+    sink: String,
+    desc: Option<String>,
+) -> Result<()> {
+    let app_id_regex =
+        Regex::new(app_id).with_context(|| format!("Invalid app_id regex '{app_id}'"))?;
+    let title_regex = title
+        .map(|title| Regex::new(title).with_context(|| format!("Invalid title regex '{title}'")))
+        .transpose()?;
+
+    edit_config(|config| {
+        config.push_rule(Rule {
+            app_id_regex,
+            title_regex,
+            sink_ref: sink,
+            desc,
+            notify: None,
+            notify_summary: None,
+            notify_body: None,
+            notify_urgency: None,
+            app_id_pattern: app_id.to_string(),
+            title_pattern: title.map(str::to_string),
+            source: None,
+            host: None,
+        });
+        Ok(format!("Added rule {}", config.rules.len()))
+    })
+}
+
+/// Remove the rule at 1-based `position`
+///
+/// # Errors
+/// Returns an error if there is no such rule or the config cannot be loaded or saved.
+pub fn config_remove_rule(position: usize) -> Result<()> {
+    edit_config(|config| {
+        let rule = config.rules.remove(rule_index(config, position)?);
+        Ok(format!(
+            "Removed rule {position} ({})",
+            rule.desc.as_deref().unwrap_or(&rule.app_id_pattern)
+        ))
+    })
+}
+
+/// Move the rule at 1-based `from` so it ends up at position `to`
+///
+/// # Errors
+/// Returns an error if either position is out of range or the config cannot be loaded
+/// or saved.
+pub fn config_move_rule(from: usize, to: usize) -> Result<()> {
+    edit_config(|config| {
+        let from_index = rule_index(config, from)?;
+        let to_index = rule_index(config, to)?;
+        config.move_rule(from_index, to_index)?;
+        Ok(format!("Moved rule {from} to position {to}"))
+    })
+}
+
+/// Set a `[settings]` or `[notifications]` key (see [`Config::set_value`])
+///
+/// # Errors
+/// Returns an error if the key is unknown, the value is invalid, or the config cannot
+/// be loaded or saved.
+pub fn config_set(key: &str, value: &str) -> Result<()> {
+    edit_config(|config| {
+        config.set_value(key, value)?;
+        let message = if value.is_empty() {
+            format!("Reset {key} to its default")
+        } else {
+            format!("Set {key} = {value}")
+        };
+        // Saving writes a key an include overrides to that include; say so
+        Ok(match config.setting_file(key) {
+            Some(file) => format!("{message} in {}", file.display()),
+            None => message,
+        })
+    })
+}
 
-    if config.settings.notify_manual {
-        let icon = get_sink_icon(target);
-        if let Err(e) = send_notification("Audio Output", &target.desc, Some(&icon)) {
-            warn!("Notification failed: {}", e);
+/// Set sink with smart toggle support
+///
+/// Routed through the daemon when it is running so its state stays in sync;
+/// otherwise the sink is activated directly. `hold` pins the resulting sink
+/// against rule-based switching (inner `None` = until `pwsw release`).
+///
+/// # Errors
+/// Returns an error if the sink reference is invalid, sink activation fails,
+/// or a hold is requested without a running daemon.
+pub async fn set_sink_smart(
+    config: &Config,
+    sink_ref: &str,
+    hold: Option<Option<std::time::Duration>>,
+) -> Result<()> {
+    if ipc::is_daemon_running().await {
+        // A daemon from before holds would switch without holding
+        if hold.is_some() {
+            ipc::handshake().await?;
+        }
+        return send_control_request(Request::SetSink {
+            sink: sink_ref.to_string(),
+            hold: hold.is_some(),
+            hold_secs: hold.flatten().map(|d| d.as_secs()),
+        })
+        .await;
+    }
+    if hold.is_some() {
+        eyre::bail!(
+            "--hold requires the running daemon (rules only apply while it runs)\n\
+             Start it with: pwsw daemon"
+        );
+    }
+    manual_switch_direct(config, &ManualRequest::Set(sink_ref.to_string()))
+}
+
+/// Cycle through configured sinks
+///
+/// Routed through the daemon when it is running; otherwise switches directly.
+///
+/// # Errors
+/// Returns an error if sink query or activation fails.
+pub async fn cycle_sink(config: &Config, direction: Direction) -> Result<()> {
+    if ipc::is_daemon_running().await {
+        return send_control_request(Request::CycleSink { direction }).await;
+    }
+    manual_switch_direct(config, &ManualRequest::Cycle(direction))
+}
+
+/// Manual switch without a daemon (direct `PipeWire` activation)
+fn manual_switch_direct(config: &Config, request: &ManualRequest) -> Result<()> {
+    let current = PipeWire::get_default_sink_name()?;
+
+    match plan_manual_switch(config, &current, request, config.settings.set_smart_toggle)? {
+        ManualSwitch::Switch(target) => {
+            let notification =
+                (config.settings.notify_manual && config.notifications.switch).then(|| {
+                    let details = SwitchDetails {
+                        sink_name: target.name.clone(),
+                        sink_desc: target.desc.clone(),
+                        prev_sink_desc: config
+                            .sinks
+                            .iter()
+                            .find(|s| s.name == current)
+                            .map_or_else(|| current.clone(), |s| s.desc.clone()),
+                        ..SwitchDetails::default()
+                    };
+                    let icon = get_sink_icon(target);
+                    switch_notification(&config.notifications, None, &details, Some(&icon))
+                });
+            switch_audio_blocking(&target.name, &target.desc, None, notification.as_ref())?;
+            println!(
+                "{} {}",
+                "Switched to:".success(),
+                target.desc.as_str().bold()
+            );
+        }
+        ManualSwitch::AlreadyOn(target) => {
+            println!("Already on: {}", target.desc.as_str().bold());
+        }
+        ManualSwitch::NothingToCycle => {
+            println!("{}", ManualSwitch::NothingToCycle.message().warning());
         }
     }
 
@@ -320,18 +955,55 @@ pub fn cycle_sink(config: &Config, direction: Direction) -> Result<()> {
 // IPC-based Commands (require daemon)
 // ============================================================================
 
+/// Daemon-side fields of a `Status` response
+struct DaemonStatus {
+    version: String,
+    uptime_secs: u64,
+    current_sink: String,
+    active_window: Option<String>,
+    tracked_windows: usize,
+    active_app_id: Option<String>,
+    active_rule_desc: Option<String>,
+    paused: bool,
+    pause_remaining_secs: Option<u64>,
+    hold_sink: Option<String>,
+    hold_remaining_secs: Option<u64>,
+}
+
+impl DaemonStatus {
+    /// Human-readable switching state ("active", "paused (12m left)", "manual hold (12m left)")
+    fn switching_text(&self) -> String {
+        if self.paused {
+            return self.pause_remaining_secs.map_or_else(
+                || "paused".to_string(),
+                |secs| format!("paused ({} left)", crate::format_uptime(secs)),
+            );
+        }
+        if self.hold_sink.is_some() {
+            return self.hold_remaining_secs.map_or_else(
+                || "manual hold (until released)".to_string(),
+                |secs| format!("manual hold ({} left)", crate::format_uptime(secs)),
+            );
+        }
+        "active".to_string()
+    }
+}
+
 /// Query system and daemon status (hybrid local+`IPC` command)
 ///
 /// # Errors
 /// Returns an error if `PipeWire` query fails or `IPC` communication fails.
-pub async fn status(config: &Config, json_output: bool) -> Result<()> {
+pub async fn status(config: &Config, json_output: bool, format: Option<&str>) -> Result<()> {
+    // Parse the template up front so typos fail before any queries
+    let template = format
+        .map(|f| Template::parse(f, STATUS_PLACEHOLDERS))
+        .transpose()?;
+
     // Always query `PipeWire` for current sink (works with or without daemon)
     let current_sink_name = PipeWire::get_default_sink_name()?;
-    let current_sink_desc = config
-        .sinks
-        .iter()
-        .find(|s| s.name == current_sink_name)
-        .map_or(current_sink_name.as_str(), |s| s.desc.as_str());
+    let current_sink_config = config.sinks.iter().find(|s| s.name == current_sink_name);
+    let current_sink_desc =
+        current_sink_config.map_or(current_sink_name.as_str(), |s| s.desc.as_str());
 
     // Try to query daemon status (non-fatal if fails)
     let daemon_running = ipc::is_daemon_running().await;
@@ -343,13 +1015,25 @@ pub async fn status(config: &Config, json_output: bool) -> Result<()> {
                 current_sink,
                 active_window,
                 tracked_windows,
-            }) => Some((
+                active_app_id,
+                active_rule_desc,
+                paused,
+                pause_remaining_secs,
+                hold_sink,
+                hold_remaining_secs,
+            }) => Some(DaemonStatus {
                 version,
                 uptime_secs,
                 current_sink,
                 active_window,
                 tracked_windows,
-            )),
+                active_app_id,
+                active_rule_desc,
+                paused,
+                pause_remaining_secs,
+                hold_sink,
+                hold_remaining_secs,
+            }),
             _ => None,
         }
     } else {
@@ -357,25 +1041,81 @@ pub async fn status(config: &Config, json_output: bool) -> Result<()> {
     };
 
     // Output
-    if json_output {
-        let daemon_json =
-            if let Some((version, uptime_secs, daemon_sink, active_window, tracked_windows)) =
-                daemon_info
-            {
-                serde_json::json!({
-                    "running": true,
-                    "version": version,
-                    "uptime_secs": uptime_secs,
-                    "uptime_human": crate::format_uptime(uptime_secs),
-                    "daemon_sink": daemon_sink,
-                    "active_window": active_window,
-                    "tracked_windows": tracked_windows,
-                })
-            } else {
-                serde_json::json!({
-                    "running": false,
-                })
-            };
+    if let Some(template) = template {
+        let sink_icon = current_sink_config.map(get_sink_icon).unwrap_or_default();
+        let sink_glyph = current_sink_config
+            .and_then(|s| s.status_glyph())
+            .unwrap_or_default();
+        let tracked_count = daemon_info
+            .as_ref()
+            .map_or(0, |d| d.tracked_windows)
+            .to_string();
+        let daemon = if daemon_info.is_some() {
+            "running"
+        } else {
+            "stopped"
+        };
+        let paused = if daemon_info.as_ref().is_some_and(|d| d.paused) {
+            "paused"
+        } else {
+            ""
+        };
+
+        println!(
+            "{}",
+            template.render(&[
+                ("sink_desc", current_sink_desc),
+                ("sink_name", &current_sink_name),
+                ("sink_icon", &sink_icon),
+                ("sink_glyph", sink_glyph),
+                (
+                    "window_app_id",
+                    daemon_info
+                        .as_ref()
+                        .and_then(|d| d.active_app_id.as_deref())
+                        .unwrap_or_default(),
+                ),
+                (
+                    "rule_desc",
+                    daemon_info
+                        .as_ref()
+                        .and_then(|d| d.active_rule_desc.as_deref())
+                        .unwrap_or_default(),
+                ),
+                ("tracked_count", &tracked_count),
+                ("daemon", daemon),
+                ("paused", paused),
+                (
+                    "hold",
+                    daemon_info
+                        .as_ref()
+                        .and_then(|d| d.hold_sink.as_deref())
+                        .unwrap_or_default(),
+                ),
+            ])
+        );
+    } else if json_output {
+        let daemon_json = if let Some(d) = daemon_info {
+            serde_json::json!({
+                "running": true,
+                "version": d.version,
+                "uptime_secs": d.uptime_secs,
+                "uptime_human": crate::format_uptime(d.uptime_secs),
+                "daemon_sink": d.current_sink,
+                "active_window": d.active_window,
+                "active_app_id": d.active_app_id,
+                "active_rule_desc": d.active_rule_desc,
+                "tracked_windows": d.tracked_windows,
+                "paused": d.paused,
+                "pause_remaining_secs": d.pause_remaining_secs,
+                "hold_sink": d.hold_sink,
+                "hold_remaining_secs": d.hold_remaining_secs,
+            })
+        } else {
+            serde_json::json!({
+                "running": false,
+            })
+        };
 
         println!(
             "{}",
@@ -396,22 +1136,26 @@ pub async fn status(config: &Config, json_output: bool) -> Result<()> {
         println!("{}", "Daemon".header());
         println!("{}", "-".repeat(6));
 
-        if let Some((version, uptime_secs, _daemon_sink, active_window, tracked_windows)) =
-            daemon_info
-        {
+        if let Some(d) = daemon_info {
             println!(
                 "{} {}",
                 "Status:".dim(),
-                format!("Running (uptime: {})", crate::format_uptime(uptime_secs)).success()
+                format!("Running (uptime: {})", crate::format_uptime(d.uptime_secs)).success()
             );
-            println!("{} {}", "Version:".dim(), version);
-            if let Some(rule) = active_window {
+            println!("{} {}", "Version:".dim(), d.version);
+            let switching = d.switching_text();
+            if d.paused || d.hold_sink.is_some() {
+                println!("{} {}", "Switching:".dim(), switching.warning());
+            } else {
+                println!("{} {}", "Switching:".dim(), switching.success());
+            }
+            if let Some(rule) = d.active_window {
                 println!("{} {}", "Active Rule:".dim(), rule.technical());
             }
             println!(
                 "{} {}",
                 "Tracked Windows:".dim(),
-                tracked_windows.to_string().technical()
+                d.tracked_windows.to_string().technical()
             );
         } else {
             println!("{} {}", "Status:".dim(), "Not running".error());
@@ -447,6 +1191,61 @@ pub async fn shutdown() -> Result<()> {
     }
 }
 
+/// Send a control request that answers with `Ok`/`Error` and print the result
+async fn send_control_request(request: Request) -> Result<()> {
+    if !ipc::is_daemon_running().await {
+        eyre::bail!("Daemon is not running. Start it with: pwsw daemon");
+    }
+
+    match ipc::send_request(request).await? {
+        Response::Ok { message } => {
+            println!("{}", message.success());
+            Ok(())
+        }
+        Response::Error { message } => {
+            eyre::bail!("Error: {message}");
+        }
+        _ => {
+            eyre::bail!("Unexpected response from daemon");
+        }
+    }
+}
+
+/// Ask the daemon to reload its config file
+///
+/// # Errors
+/// Returns an error if no daemon is running, IPC fails, or the new config is rejected.
+pub async fn reload() -> Result<()> {
+    send_control_request(Request::Reload).await
+}
+
+/// Pause automatic switching, optionally for a limited time
+///
+/// # Errors
+/// Returns an error if no daemon is running or IPC communication fails.
+pub async fn pause(duration: Option<std::time::Duration>) -> Result<()> {
+    send_control_request(Request::Pause {
+        duration_secs: duration.map(|d| d.as_secs()),
+    })
+    .await
+}
+
+/// Resume automatic switching
+///
+/// # Errors
+/// Returns an error if no daemon is running or IPC communication fails.
+pub async fn resume() -> Result<()> {
+    send_control_request(Request::Resume).await
+}
+
+/// Release a manual hold and return to rule-based switching
+///
+/// # Errors
+/// Returns an error if no daemon is running or IPC communication fails.
+pub async fn release() -> Result<()> {
+    send_control_request(Request::Release).await
+}
+
 /// Get list of windows currently tracked by daemon
 ///
 /// # Errors
@@ -520,6 +1319,405 @@ pub async fn list_windows(json_output: bool) -> Result<()> {
     }
 }
 
+/// Explain why the daemon chose its current target sink
+///
+/// # Errors
+/// Returns an error if no daemon is running or IPC communication fails.
+pub async fn explain(json_output: bool) -> Result<()> {
+    if !ipc::is_daemon_running().await {
+        eyre::bail!("Daemon is not running. Start it with: pwsw daemon");
+    }
+
+    let response = ipc::send_request(Request::Explain).await?;
+
+    match response {
+        Response::Explanation {
+            current_sink,
+            target_sink,
+            priority_mode,
+            paused,
+            hold_sink,
+            windows,
+        } => {
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "current_sink": current_sink,
+                        "target_sink": target_sink,
+                        "priority_mode": priority_mode,
+                        "paused": paused,
+                        "hold_sink": hold_sink,
+                        "windows": windows,
+                    }))?
+                );
+                return Ok(());
+            }
+
+            println!("{}", "Sink Selection".header());
+            println!("{}", "-".repeat(14));
+            println!("{} {}", "Current:".dim(), current_sink.as_str().bold());
+            println!("{} {}", "Target:".dim(), target_sink.as_str().bold());
+            let priority = match priority_mode {
+                PriorityMode::Time => "most recent window wins (match_by_index = false)",
+                PriorityMode::Index => {
+                    "earliest rule wins, most recent breaks ties (match_by_index = true)"
+                }
+            };
+            println!("{} {}", "Priority:".dim(), priority);
+            if let Some(ref sink) = hold_sink {
+                println!(
+                    "{} {}",
+                    "Switching:".dim(),
+                    format!("manual hold on {sink}, rules are ignored").warning()
+                );
+            } else if paused {
+                println!(
+                    "{} {}",
+                    "Switching:".dim(),
+                    "paused, target is not applied".warning()
+                );
+            }
+
+            let Some(winner) = windows.first() else {
+                println!(
+                    "\n{}",
+                    "No tracked windows, target is the default sink.".dim()
+                );
+                return Ok(());
+            };
+
+            println!(
+                "\n{} ({}):",
+                "Tracked Windows".header(),
+                windows.len().to_string().technical()
+            );
+            for window in &windows {
+                let bullet = if window.verdict == Verdict::Winner {
+                    "✓".success()
+                } else {
+                    "•".dim()
+                };
+                println!("  {} {}: {}", bullet, "app_id".dim(), window.app_id);
+                println!("    {}: {}", "title".dim(), window.title);
+                println!(
+                    "    {}: {} ({}), opened {} ago",
+                    "rule".dim(),
+                    (window.rule_index + 1).to_string().technical(),
+                    window.rule_desc,
+                    crate::format_uptime(window.age_secs)
+                );
+                println!(
+                    "    {} {}: {}",
+                    "→".dim(),
+                    "sink".dim(),
+                    window.sink_desc.as_str().bold()
+                );
+                let reason = verdict_reason(window, winner, hold_sink.as_deref());
+                if window.verdict == Verdict::Winner {
+                    println!("    {}", reason.success());
+                } else {
+                    println!("    {}", reason.dim());
+                }
+            }
+            Ok(())
+        }
+        Response::Error { message } => {
+            eyre::bail!("Error: {message}");
+        }
+        _ => {
+            eyre::bail!("Unexpected response from daemon");
+        }
+    }
+}
+
+/// One-line explanation of a window's verdict, relative to the winning window
+fn verdict_reason(
+    window: &ipc::ExplainedWindow,
+    winner: &ipc::ExplainedWindow,
+    hold_sink: Option<&str>,
+) -> String {
+    match window.verdict {
+        Verdict::Winner => "winner: decides the target sink".to_string(),
+        Verdict::Held => format!(
+            "overridden: manual hold on {}",
+            hold_sink.unwrap_or("another sink")
+        ),
+        Verdict::OlderWindow => format!(
+            "lost: {} was opened more recently ({} ago)",
+            winner.app_id,
+            crate::format_uptime(winner.age_secs)
+        ),
+        Verdict::LowerPriorityRule => format!(
+            "lost: {} matched rule {}, which comes first in the config",
+            winner.app_id,
+            winner.rule_index + 1
+        ),
+        Verdict::OlderSameRule => format!(
+            "lost: {} matched the same rule and was opened more recently",
+            winner.app_id
+        ),
+    }
+}
+
+/// One decision made while replaying window events (for `simulate` and `replay`)
+#[derive(serde::Serialize)]
+struct SimulationStep {
+    /// Milliseconds since recording started (`replay` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    t_ms: Option<u64>,
+    event: WindowEvent,
+    /// 0-based index of the matching rule (`None` for closes and unmatched windows)
+    rule_index: Option<usize>,
+    rule_desc: Option<String>,
+    /// Target sink after the event (description)
+    target_sink: String,
+    /// Whether the event caused a sink switch
+    switched: bool,
+}
+
+/// Result of feeding a sequence of window events through rule matching
+#[derive(serde::Serialize)]
+struct Simulation {
+    initial_sink: String,
+    steps: Vec<SimulationStep>,
+    final_sink: String,
+}
+
+/// Replay window events against the config offline (no daemon, compositor or `PipeWire`)
+///
+/// With `events`, reads JSON Lines window events from that file; otherwise simulates a
+/// single window opening with the given `app_id` and title.
+///
+/// # Errors
+/// Returns an error if the events file cannot be read or parsed, or rule processing fails.
+pub async fn simulate(
+    config: Config,
+    app_id: Option<&str>,
+    title: Option<&str>,
+    events: Option<&std::path::Path>,
+    json_output: bool,
+) -> Result<()> {
+    let events = if let Some(path) = events {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read events file: {}", path.display()))?;
+        parse_events(&contents)?
+    } else {
+        vec![WindowEvent::Opened {
+            id: 1,
+            app_id: app_id.unwrap_or_default().to_string(),
+            title: title.unwrap_or_default().to_string(),
+        }]
+    };
+
+    let simulation = run_simulation(config, events.into_iter().map(|e| (None, e))).await?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&simulation)?);
+    } else {
+        print_simulation(&simulation);
+    }
+    Ok(())
+}
+
+/// Replay a recorded trace through rule matching offline
+///
+/// # Errors
+/// Returns an error if the trace cannot be read or parsed, or rule processing fails.
+pub async fn replay(config: Config, path: &std::path::Path, json_output: bool) -> Result<()> {
+    let (header, entries) = read_trace(path)?;
+    let simulation = run_simulation(
+        config,
+        entries
+            .into_iter()
+            .map(|entry| (Some(entry.t_ms), entry.event)),
+    )
+    .await?;
+
+    if json_output {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "trace": header,
+                "initial_sink": simulation.initial_sink,
+                "steps": simulation.steps,
+                "final_sink": simulation.final_sink,
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} ({} protocol, recorded by {})",
+        "Trace:".dim(),
+        path.display(),
+        header.protocol.name(),
+        header.pwsw_version
+    );
+    if header.titles_redacted {
+        println!(
+            "{}",
+            "Titles were redacted; rules with a title pattern cannot match.".warning()
+        );
+    }
+    println!();
+    print_simulation(&simulation);
+    Ok(())
+}
+
+/// Feed window events through `State::process_event` with a no-op audio backend
+async fn run_simulation(
+    config: Config,
+    events: impl IntoIterator<Item = (Option<u64>, WindowEvent)>,
+) -> Result<Simulation> {
+    let config = std::sync::Arc::new(config);
+    let mut state = State::new_offline(std::sync::Arc::clone(&config));
+    let sink_desc = |name: &str| {
+        config
+            .sinks
+            .iter()
+            .find(|s| s.name == name)
+            .map_or_else(|| name.to_string(), |s| s.desc.clone())
+    };
+    let initial_sink = sink_desc(&state.current_sink_name);
+
+    let mut steps = Vec::new();
+    for (t_ms, event) in events {
+        let rule = match &event {
+            WindowEvent::Opened { app_id, title, .. }
+            | WindowEvent::Changed { app_id, title, .. } => state
+                .find_matching_rule(app_id, title)
+                .map(|(index, rule)| {
+                    let desc = rule
+                        .desc
+                        .clone()
+                        .unwrap_or_else(|| rule.app_id_pattern.clone());
+                    (index, desc)
+                }),
+            WindowEvent::Closed { .. } => None,
+        };
+        let before = state.current_sink_name.clone();
+        state.process_event(event.clone()).await?;
+
+        steps.push(SimulationStep {
+            t_ms,
+            event,
+            rule_index: rule.as_ref().map(|(index, _)| *index),
+            rule_desc: rule.map(|(_, desc)| desc),
+            target_sink: sink_desc(&state.determine_target_sink()),
+            switched: state.current_sink_name != before,
+        });
+    }
+    let final_sink = sink_desc(&state.current_sink_name);
+
+    Ok(Simulation {
+        initial_sink,
+        steps,
+        final_sink,
+    })
+}
+
+/// Print a simulation's decisions, one numbered block per event
+fn print_simulation(simulation: &Simulation) {
+    let header = format!("Simulation (starting on {})", simulation.initial_sink);
+    println!("{}", header.as_str().header());
+    println!("{}", "-".repeat(header.chars().count()));
+    let mut current = simulation.initial_sink.clone();
+    for (i, step) in simulation.steps.iter().enumerate() {
+        let event = match &step.event {
+            WindowEvent::Opened { id, app_id, title } => {
+                format!("opened #{id} {app_id}: {title}")
+            }
+            WindowEvent::Changed { id, app_id, title } => {
+                format!("changed #{id} {app_id}: {title}")
+            }
+            WindowEvent::Closed { id } => format!("closed #{id}"),
+        };
+        let time = step.t_ms.map_or_else(String::new, |ms| {
+            format!("[+{}.{:03}s] ", ms / 1000, ms % 1000)
+        });
+        println!(
+            "{}. {}{}",
+            (i + 1).to_string().technical(),
+            time.dim(),
+            event
+        );
+        match (&step.rule_index, &step.rule_desc) {
+            (Some(index), Some(desc)) => println!(
+                "   {}: {} ({})",
+                "rule".dim(),
+                (index + 1).to_string().technical(),
+                desc
+            ),
+            _ if !matches!(step.event, WindowEvent::Closed { .. }) => {
+                println!("   {}", "no matching rule".dim());
+            }
+            _ => {}
+        }
+        if step.switched {
+            println!(
+                "   {} {} → {}",
+                "switch:".dim(),
+                current,
+                step.target_sink.as_str().bold()
+            );
+            current.clone_from(&step.target_sink);
+        } else {
+            println!("   {} (stays on {})", "no switch".dim(), current);
+        }
+    }
+    println!();
+    println!(
+        "{} {}",
+        "Final sink:".dim(),
+        simulation.final_sink.as_str().bold()
+    );
+}
+
+/// Record compositor window events to a trace file until interrupted
+///
+/// # Errors
+/// Returns an error if the compositor connection fails or the trace cannot be written.
+pub async fn record(options: &RecordOptions) -> Result<()> {
+    let (protocol, mut window_events) = crate::compositor::spawn_compositor_thread_with_protocol()?;
+    let mut recorder = TraceRecorder::create(options, protocol)?;
+
+    println!(
+        "Recording window events ({} protocol) to {}",
+        protocol.name(),
+        options.path.display().to_string().technical()
+    );
+    println!("{}", "Press Ctrl-C to stop.".dim());
+
+    let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+        .context("Failed to install SIGTERM handler")?;
+    loop {
+        tokio::select! {
+            event = window_events.recv() => {
+                let Some(event) = event else {
+                    eprintln!("{}", "Compositor connection lost".error());
+                    break;
+                };
+                recorder.record(&event)?;
+                match &event {
+                    WindowEvent::Opened { id, app_id, .. } => println!("  opened #{id} {app_id}"),
+                    WindowEvent::Changed { id, app_id, .. } => println!("  changed #{id} {app_id}"),
+                    WindowEvent::Closed { id } => println!("  closed #{id}"),
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+            _ = sigterm.recv() => break,
+        }
+    }
+
+    println!(
+        "{} Recorded {} events. Reproduce with: {}",
+        "✓".success(),
+        recorder.events().to_string().technical(),
+        format!("pwsw replay {}", options.path.display()).technical()
+    );
+    Ok(())
+}
+
 /// Test a regex pattern against current windows
 ///
 /// # Errors
@@ -592,3 +1790,106 @@ pub async fn test_rule(pattern: &str, json_output: bool) -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active(name: &str, description: &str, is_default: bool) -> ActiveSink {
+        ActiveSink {
+            name: name.to_string(),
+            description: description.to_string(),
+            is_default,
+        }
+    }
+
+    #[test]
+    fn test_detected_sinks_mark_current_default_and_unique_descs() {
+        let sinks = detected_sinks(
+            &[
+                active("alsa_output.usb-a", "USB Audio Analog Stereo", false),
+                active("alsa_output.usb-b", "USB Audio Analog Stereo", true),
+            ],
+            &[ProfileSink {
+                predicted_name: "alsa_output.pci.hdmi-stereo".to_string(),
+                description: "Digital Stereo (HDMI) Output".to_string(),
+                device_id: 42,
+                device_name: "alsa_card.pci".to_string(),
+                profile_index: 3,
+                profile_name: "output:hdmi-stereo".to_string(),
+            }],
+        );
+
+        let descs: Vec<_> = sinks.iter().map(|s| s.desc.as_str()).collect();
+        assert_eq!(descs, ["USB Audio", "USB Audio 2", "HDMI"]);
+        let defaults: Vec<_> = sinks.iter().map(|s| s.default).collect();
+        assert_eq!(defaults, [false, true, false]);
+    }
+
+    #[test]
+    fn test_detected_sinks_default_to_first_without_current_default() {
+        let sinks = detected_sinks(&[active("a", "A", false), active("b", "B", false)], &[]);
+        assert!(sinks[0].default);
+        assert!(!sinks[1].default);
+    }
+
+    #[test]
+    fn test_live_statuses_map_to_exit_codes() {
+        let active_sinks = [active("alsa_output.speakers", "Speakers", true)];
+        let profile_sinks = [ProfileSink {
+            predicted_name: "alsa_output.pci.hdmi-stereo".to_string(),
+            description: "Digital Stereo (HDMI) Output".to_string(),
+            device_id: 42,
+            device_name: "alsa_card.pci".to_string(),
+            profile_index: 3,
+            profile_name: "output:hdmi-stereo".to_string(),
+        }];
+        let status = |name: &str| {
+            let sink = SinkConfig {
+                name: name.to_string(),
+                desc: "Sink".to_string(),
+                icon: None,
+                glyph: None,
+                default: false,
+                source: None,
+                host: None,
+            };
+            live_status(&sink, &active_sinks, &profile_sinks).0
+        };
+
+        let found = [
+            status("alsa_output.speakers"),
+            status("alsa_output.pci.hdmi-stereo"),
+        ];
+        assert_eq!(found, [LiveStatus::Active, LiveStatus::ProfileSwitch]);
+        assert_eq!(live_exit_code(Some(&found)), 0);
+        assert_eq!(live_exit_code(Some(&[])), 0);
+
+        let missing = [LiveStatus::Active, status("bluez_output.gone")];
+        assert_eq!(missing[1], LiveStatus::Missing);
+        assert_eq!(live_exit_code(Some(&missing)), EXIT_SINK_MISSING);
+
+        assert_eq!(live_exit_code(None), EXIT_PIPEWIRE_FAILED);
+    }
+
+    #[test]
+    fn test_descs_differ() {
+        assert!(!descs_differ("HDMI", "Digital Stereo (HDMI) Output"));
+        assert!(!descs_differ(
+            "Built-in audio",
+            "Built-in Audio Analog Stereo"
+        ));
+        assert!(!descs_differ("Desk USB DAC", "USB Audio DAC Analog Stereo"));
+        assert!(descs_differ("Headphones", "Built-in Audio Analog Stereo"));
+        assert!(descs_differ("Living Room TV", "WH-1000XM4"));
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("all", 3).unwrap(), [0, 1, 2]);
+        assert_eq!(parse_selection("3, 1 3", 3).unwrap(), [2, 0]);
+        assert!(parse_selection("4", 3).is_err());
+        assert!(parse_selection("x", 3).is_err());
+        assert!(parse_selection(",", 3).is_err());
+    }
+}
@@ -8,21 +8,38 @@ mod ext_toplevel;
 mod wlr_toplevel;
 
 use color_eyre::eyre::{self, Context, Result};
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 use tracing::{error, info};
 use wayland_client::{Connection, protocol::wl_registry};
 
 /// Supported window management protocols
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Protocol {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
     /// ext-foreign-toplevel-list-v1 (Standard)
     Ext,
     /// wlr-foreign-toplevel-management (Legacy/wlroots)
     Wlr,
 }
 
+impl Protocol {
+    /// Short protocol name as written to traces ("ext" or "wlr")
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Ext => "ext",
+            Self::Wlr => "wlr",
+        }
+    }
+}
+
 /// Window event from a compositor
-#[derive(Debug, Clone)]
+///
+/// Serialized as one JSON object per line for `simulate --events`, e.g.
+/// `{"event":"opened","id":1,"app_id":"steam","title":"Steam"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
 pub enum WindowEvent {
     /// A new window was opened
     Opened {
@@ -40,6 +57,27 @@ pub enum WindowEvent {
     Closed { id: u64 },
 }
 
+/// Parse window events from JSON Lines (one [`WindowEvent`] per line)
+///
+/// Blank lines and lines starting with `#` are skipped.
+///
+/// # Errors
+/// Returns an error naming the line number if a line is not a valid event.
+pub fn parse_events(contents: &str) -> Result<Vec<WindowEvent>> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid window event on line {}", i + 1))
+        })
+        .collect()
+}
+
 /// Maximum number of window events to buffer before dropping events
 ///
 /// This prevents unbounded memory growth if the daemon's main loop is slow.
@@ -89,6 +127,15 @@ const WINDOW_EVENT_CHANNEL_CAPACITY: usize = 100;
 ///
 /// **Note:** GNOME/Mutter and KDE Plasma 6 do not expose window management protocols and are not supported.
 pub fn spawn_compositor_thread() -> Result<mpsc::Receiver<WindowEvent>> {
+    spawn_compositor_thread_with_protocol().map(|(_, rx)| rx)
+}
+
+/// Like [`spawn_compositor_thread`], also returning the detected protocol (for traces)
+///
+/// # Errors
+/// Returns an error if no Wayland connection can be established or no supported
+/// window management protocol is available.
+pub fn spawn_compositor_thread_with_protocol() -> Result<(Protocol, mpsc::Receiver<WindowEvent>)> {
     // Connect to Wayland display
     let conn = Connection::connect_to_env()
         .context("Failed to connect to Wayland display. Is a Wayland compositor running?")?;
@@ -133,7 +180,7 @@ pub fn spawn_compositor_thread() -> Result<mpsc::Receiver<WindowEvent>> {
         }
     });
 
-    Ok(rx)
+    Ok((protocol, rx))
 }
 
 /// Detect which window management protocol is available on this compositor
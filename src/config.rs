@@ -1,30 +1,61 @@
 //! Configuration management
 //!
 //! Handles loading, parsing, and validating the TOML configuration file.
-//! Supports settings, sink definitions, and window matching rules.
+//! Supports settings, sink definitions, and window matching rules, split
+//! across the main file and the files it `include`s, with `[host.<name>]`
+//! sections that only apply on the matching machine.
 
 use color_eyre::eyre::{self, Context, ContextCompat, Result};
 use crossterm::style::Stylize;
 use regex::Regex;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use tracing::{info, warn};
 
+use crate::config_backup::{self, Backup};
+use crate::config_edit;
 use crate::style::PwswStyle;
 
 // ============================================================================
 // Public Configuration Types
 // ============================================================================
 
+/// Config file chosen with `--config` (replaces the XDG path for the whole process)
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
 /// Main configuration structure
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config {
     pub settings: Settings,
+    pub notifications: NotificationSettings,
     pub sinks: Vec<SinkConfig>,
     pub rules: Vec<Rule>,
+    pub hooks: Vec<Hook>,
+    /// `include` patterns from the main config file, as written
+    pub includes: Vec<String>,
+    /// `[settings]` and `[notifications]` keys (`section.key`) set by an included file,
+    /// with the last file setting each; saves write these keys back to that file
+    pub included_settings: BTreeMap<String, PathBuf>,
+    /// `[host.<name>]` section applied on this machine, if any
+    pub host: Option<HostOverride>,
+}
+
+/// A `[host.<name>]` section of the main config file that matched this machine's hostname
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostOverride {
+    /// Hostname the section is named after
+    pub name: String,
+    /// `[settings]` keys the section overrides
+    pub settings: Vec<String>,
+    /// The section's sinks replace the main file's instead of being appended
+    pub replace_sinks: bool,
+    /// The section's rules replace the main file's instead of being appended
+    pub replace_rules: bool,
 }
 
 /// Global settings
@@ -39,6 +70,103 @@ pub struct Settings {
     pub log_level: String,
 }
 
+/// Current config layout version, written as `version = N` at the top of the file
+///
+/// Files without `version` are version 0. Older files are upgraded in memory on load;
+/// `pwsw config migrate` writes the upgrade back.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// Placeholders available in notification templates
+pub const NOTIFICATION_PLACEHOLDERS: &[&str] = &[
+    "sink_desc",
+    "sink_name",
+    "prev_sink_desc",
+    "reason",
+    "app_id",
+];
+
+/// Placeholders available in `notifications.command` arguments
+pub const NOTIFICATION_COMMAND_PLACEHOLDERS: &[&str] =
+    &["summary", "body", "icon", "urgency", "timeout_ms"];
+
+/// Notification content and which events notify (`[notifications]`)
+///
+/// The per-event flags apply on top of `notify_manual`/`notify_rules`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationSettings {
+    /// Summary template for switch notifications
+    pub summary: String,
+    /// Body template for automatic (rule) switches
+    pub body: String,
+    /// Body template for manual switches
+    pub manual_body: String,
+    /// How long notifications stay on screen (0 = until dismissed)
+    pub timeout_ms: u32,
+    pub urgency: NotificationUrgency,
+    /// Optional sound hint (freedesktop sound theme name, e.g. `"message-new-instant"`)
+    pub sound: Option<String>,
+    /// Program and argument templates run instead of a desktop notification
+    pub command: Option<Vec<String>>,
+    /// Notify on sink switches
+    pub switch: bool,
+    /// Notify when the config is reloaded
+    pub reload: bool,
+    /// Notify when the daemon starts and stops
+    pub start_stop: bool,
+    /// Notify when a config reload fails or is rejected
+    pub errors: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            summary: "Audio Output".to_string(),
+            body: "{sink_desc} → {reason}".to_string(),
+            manual_body: "{sink_desc}".to_string(),
+            timeout_ms: 3000,
+            urgency: NotificationUrgency::Normal,
+            sound: None,
+            command: None,
+            switch: true,
+            reload: true,
+            start_stop: true,
+            errors: true,
+        }
+    }
+}
+
+/// Notification urgency level
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationUrgency {
+    Low,
+    #[default]
+    Normal,
+    Critical,
+}
+
+impl NotificationUrgency {
+    /// Name used in the config file
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::Critical => "critical",
+        }
+    }
+
+    /// The next level, wrapping around (for toggling in the TUI)
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Low => Self::Normal,
+            Self::Normal => Self::Critical,
+            Self::Critical => Self::Low,
+        }
+    }
+}
+
 /// Audio sink configuration
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SinkConfig {
@@ -50,8 +178,22 @@ pub struct SinkConfig {
     pub desc: String,
     /// Optional icon for status bars (if not set, auto-detected)
     pub icon: Option<String>,
+    /// Optional status-bar glyph for `--format` output (e.g., `"󰓃"`)
+    pub glyph: Option<String>,
     /// Whether this is the default fallback sink
     pub default: bool,
+    /// Included file this sink was read from (`None` = main config file)
+    pub source: Option<PathBuf>,
+    /// `[host.<name>]` section of the main config file this sink was read from
+    pub host: Option<String>,
+}
+
+impl SinkConfig {
+    /// Status-bar glyph: explicit `glyph`, falling back to the custom `icon`
+    #[must_use]
+    pub fn status_glyph(&self) -> Option<&str> {
+        self.glyph.as_deref().or(self.icon.as_deref())
+    }
 }
 
 /// Window matching rule
@@ -62,9 +204,17 @@ pub struct Rule {
     pub sink_ref: String,
     pub desc: Option<String>,
     pub notify: Option<bool>,
+    /// Notification overrides for switches caused by this rule
+    pub notify_summary: Option<String>,
+    pub notify_body: Option<String>,
+    pub notify_urgency: Option<NotificationUrgency>,
     // Original patterns for display
     pub app_id_pattern: String,
     pub title_pattern: Option<String>,
+    /// Included file this rule was read from (`None` = main config file)
+    pub source: Option<PathBuf>,
+    /// `[host.<name>]` section of the main config file this rule was read from
+    pub host: Option<String>,
 }
 
 impl PartialEq for Rule {
@@ -76,21 +226,137 @@ impl PartialEq for Rule {
             && self.sink_ref == other.sink_ref
             && self.desc == other.desc
             && self.notify == other.notify
+            && self.notify_summary == other.notify_summary
+            && self.notify_body == other.notify_body
+            && self.notify_urgency == other.notify_urgency
+            && self.source == other.source
+            && self.host == other.host
+    }
+}
+
+/// A valid rule that never matches, or only re-selects the default sink (see [`Config::lint_rules`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleLint {
+    /// Same `app_id` and title patterns as the earlier rule at this (0-based) index
+    Duplicate(usize),
+    /// Every window it matches is matched first by the earlier rule at this index
+    Shadowed(usize),
+    /// Switches to the default sink, and no rule it could win over switches away from it
+    TargetsDefault,
+}
+
+impl RuleLint {
+    /// Short label for the TUI rules list
+    #[must_use]
+    pub const fn badge(self) -> &'static str {
+        match self {
+            Self::Duplicate(_) => "duplicate",
+            Self::Shadowed(_) => "shadowed",
+            Self::TargetsDefault => "default only",
+        }
+    }
+
+    /// Explanation for `pwsw validate` and the rule details popup
+    #[must_use]
+    pub fn message(self) -> String {
+        match self {
+            Self::Duplicate(i) => format!("Duplicate of rule {}; it never matches", i + 1),
+            Self::Shadowed(i) => {
+                format!(
+                    "Never matches: rule {} matches all of its windows first",
+                    i + 1
+                )
+            }
+            Self::TargetsDefault => {
+                "Only re-selects the default sink: no rule it wins over switches away from it"
+                    .to_string()
+            }
+        }
+    }
+}
+
+/// Whether `pattern` matches any string, like `.*` or `^.*$`
+fn matches_everything(pattern: &str) -> bool {
+    let pattern = pattern
+        .trim_start_matches("(?i)")
+        .trim_start_matches("(?s)");
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    let pattern = pattern.strip_suffix('$').unwrap_or(pattern);
+    matches!(pattern, ".*" | ".*?" | "(.*)")
+}
+
+/// Whether every window matched by `later` is also matched by `earlier`
+fn rule_covers(earlier: &Rule, later: &Rule) -> bool {
+    let app_id = earlier.app_id_pattern == later.app_id_pattern
+        || matches_everything(&earlier.app_id_pattern);
+    let title = match &earlier.title_pattern {
+        None => true,
+        Some(title) => matches_everything(title) || later.title_pattern.as_ref() == Some(title),
+    };
+    app_id && title
+}
+
+/// Daemon event that runs a hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    /// The daemon switched the default sink (rules, manual switch, hold, resume)
+    Switch,
+    /// A window started matching a rule
+    WindowTracked,
+    /// The daemon finished starting up
+    DaemonStart,
+    /// Reloading the config file failed (the previous config stays active)
+    ReloadFailed,
+}
+
+impl HookEvent {
+    /// Name used in the config file and `PWSW_EVENT`
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Switch => "switch",
+            Self::WindowTracked => "window_tracked",
+            Self::DaemonStart => "daemon_start",
+            Self::ReloadFailed => "reload_failed",
+        }
     }
 }
 
+/// Shell command run by the daemon on an event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hook {
+    pub on: HookEvent,
+    /// Run with `sh -c`
+    pub command: String,
+    /// Killed if still running after this many seconds
+    pub timeout_secs: u64,
+    /// Included file this hook was read from (`None` = main config file)
+    pub source: Option<PathBuf>,
+}
+
 // ============================================================================
 // Config File Deserialization (TOML)
 // ============================================================================
 
 #[derive(Debug, Deserialize, Serialize)]
 struct ConfigFile {
+    /// Layout version (see [`CONFIG_VERSION`]); always current after [`migrate_table`]
+    #[serde(default)]
+    version: u32,
+    /// Extra config files (paths or globs, relative to this file's directory)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include: Vec<String>,
     #[serde(default)]
     settings: SettingsFile,
     #[serde(default)]
+    notifications: NotificationsFile,
+    #[serde(default)]
     sinks: Vec<SinkConfigFile>,
     #[serde(default)]
     rules: Vec<RuleConfigFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hooks: Vec<HookConfigFile>,
 }
 
 // TOML serialization format - mirrors Settings structure with serde defaults
@@ -110,6 +376,41 @@ struct SettingsFile {
     log_level: String,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+struct NotificationsFile {
+    summary: String,
+    body: String,
+    manual_body: String,
+    timeout_ms: u32,
+    urgency: NotificationUrgency,
+    sound: Option<String>,
+    command: Option<Vec<String>>,
+    switch: bool,
+    reload: bool,
+    start_stop: bool,
+    errors: bool,
+}
+
+impl Default for NotificationsFile {
+    fn default() -> Self {
+        let defaults = NotificationSettings::default();
+        Self {
+            summary: defaults.summary,
+            body: defaults.body,
+            manual_body: defaults.manual_body,
+            timeout_ms: defaults.timeout_ms,
+            urgency: defaults.urgency,
+            sound: defaults.sound,
+            command: defaults.command,
+            switch: defaults.switch,
+            reload: defaults.reload,
+            start_stop: defaults.start_stop,
+            errors: defaults.errors,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct SinkConfigFile {
     name: String,
@@ -117,6 +418,8 @@ struct SinkConfigFile {
     #[serde(default)]
     icon: Option<String>,
     #[serde(default)]
+    glyph: Option<String>,
+    #[serde(default)]
     default: bool,
 }
 
@@ -131,12 +434,30 @@ struct RuleConfigFile {
     desc: Option<String>,
     #[serde(default)]
     notify: Option<bool>,
+    #[serde(default)]
+    notify_summary: Option<String>,
+    #[serde(default)]
+    notify_body: Option<String>,
+    #[serde(default)]
+    notify_urgency: Option<NotificationUrgency>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct HookConfigFile {
+    on: HookEvent,
+    command: String,
+    #[serde(default = "default_hook_timeout_secs")]
+    timeout_secs: u64,
 }
 
 const fn default_true() -> bool {
     true
 }
 
+const fn default_hook_timeout_secs() -> u64 {
+    10
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -154,6 +475,396 @@ impl Default for SettingsFile {
     }
 }
 
+impl SettingsFile {
+    fn into_settings(self) -> Settings {
+        Settings {
+            default_on_startup: self.default_on_startup,
+            set_smart_toggle: self.set_smart_toggle,
+            notify_manual: self.notify_manual,
+            notify_rules: self.notify_rules,
+            match_by_index: self.match_by_index,
+            log_level: self.log_level,
+        }
+    }
+}
+
+impl NotificationsFile {
+    fn into_settings(self) -> NotificationSettings {
+        NotificationSettings {
+            summary: self.summary,
+            body: self.body,
+            manual_body: self.manual_body,
+            timeout_ms: self.timeout_ms,
+            urgency: self.urgency,
+            sound: self.sound,
+            command: self.command,
+            switch: self.switch,
+            reload: self.reload,
+            start_stop: self.start_stop,
+            errors: self.errors,
+        }
+    }
+}
+
+impl SinkConfigFile {
+    fn into_sink(self, source: Option<PathBuf>) -> SinkConfig {
+        SinkConfig {
+            name: self.name,
+            desc: self.desc,
+            icon: self.icon,
+            glyph: self.glyph,
+            default: self.default,
+            source,
+            host: None,
+        }
+    }
+}
+
+impl From<&SinkConfig> for SinkConfigFile {
+    fn from(s: &SinkConfig) -> Self {
+        Self {
+            name: s.name.clone(),
+            desc: s.desc.clone(),
+            icon: s.icon.clone(),
+            glyph: s.glyph.clone(),
+            default: s.default,
+        }
+    }
+}
+
+impl RuleConfigFile {
+    fn into_rule(self, source: Option<PathBuf>) -> Rule {
+        let app_id_pattern = self.app_id.as_str().to_string();
+        let title_pattern = self.title.as_ref().map(|t| t.as_str().to_string());
+        Rule {
+            app_id_regex: self.app_id,
+            title_regex: self.title,
+            sink_ref: self.sink,
+            desc: self.desc,
+            notify: self.notify,
+            notify_summary: self.notify_summary,
+            notify_body: self.notify_body,
+            notify_urgency: self.notify_urgency,
+            app_id_pattern,
+            title_pattern,
+            source,
+            host: None,
+        }
+    }
+}
+
+impl From<&Rule> for RuleConfigFile {
+    fn from(r: &Rule) -> Self {
+        Self {
+            app_id: r.app_id_regex.clone(),
+            title: r.title_regex.clone(),
+            sink: r.sink_ref.clone(),
+            desc: r.desc.clone(),
+            notify: r.notify,
+            notify_summary: r.notify_summary.clone(),
+            notify_body: r.notify_body.clone(),
+            notify_urgency: r.notify_urgency,
+        }
+    }
+}
+
+impl HookConfigFile {
+    fn into_hook(self, source: Option<PathBuf>) -> Hook {
+        Hook {
+            on: self.on,
+            command: self.command,
+            timeout_secs: self.timeout_secs,
+            source,
+        }
+    }
+}
+
+impl From<&Hook> for HookConfigFile {
+    fn from(h: &Hook) -> Self {
+        Self {
+            on: h.on,
+            command: h.command.clone(),
+            timeout_secs: h.timeout_secs,
+        }
+    }
+}
+
+/// Read and parse one config file, also returning its raw table for merging
+fn read_config_file(path: &Path) -> Result<(ConfigFile, toml::Table)> {
+    let (_, config_file, table) = read_config_document(path)?;
+    Ok((config_file, table))
+}
+
+/// [`read_config_file`], also returning the file's text for format-preserving saves
+///
+/// The returned tables are already upgraded to [`CONFIG_VERSION`].
+fn read_config_document(path: &Path) -> Result<(String, ConfigFile, toml::Table)> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config: {}", path.display()))?;
+    parse_config_document(path, contents)
+}
+
+/// Parse `contents` as the config file at `path` (see [`read_config_document`])
+fn parse_config_document(
+    path: &Path,
+    contents: String,
+) -> Result<(String, ConfigFile, toml::Table)> {
+    let parse_context = || format!("Failed to parse config: {}", path.display());
+    let mut table: toml::Table = toml::from_str(&contents).with_context(parse_context)?;
+    migrate_table(&mut table).with_context(parse_context)?;
+    let config_file: ConfigFile = toml::Value::Table(table.clone())
+        .try_into()
+        .with_context(parse_context)?;
+    Ok((contents, config_file, table))
+}
+
+/// One upgrade step of a raw config table
+type Migration = fn(&mut toml::Table) -> Result<()>;
+
+/// Upgrade steps, where `MIGRATIONS[n]` turns a version `n` table into version `n + 1`
+///
+/// Steps work on the raw table so they can rename or move keys the current
+/// `ConfigFile` no longer knows. Add a step whenever the layout changes, and bump
+/// [`CONFIG_VERSION`] to match.
+const MIGRATIONS: &[Migration] = &[migrate_v0_to_v1];
+
+/// Version 0 is every file written before `version` existed; the layout is unchanged
+#[allow(clippy::unnecessary_wraps)] // Signature shared by all migration steps
+const fn migrate_v0_to_v1(_table: &mut toml::Table) -> Result<()> {
+    Ok(())
+}
+
+/// Upgrade a raw config table to [`CONFIG_VERSION`] in place
+///
+/// Returns the version the table was at.
+fn migrate_table(table: &mut toml::Table) -> Result<u32> {
+    let version = match table.get("version") {
+        None => 0,
+        Some(toml::Value::Integer(v)) => {
+            u32::try_from(*v).map_err(|_| eyre::eyre!("Invalid config version {v}"))?
+        }
+        Some(_) => eyre::bail!("'version' must be a whole number"),
+    };
+    if version > CONFIG_VERSION {
+        eyre::bail!(
+            "Config version {version} is newer than this pwsw supports (up to version {CONFIG_VERSION}). \
+             Upgrade pwsw, or use a config written for this version."
+        );
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(table).with_context(|| {
+            format!(
+                "Failed to migrate config from version {from} to {}",
+                from + 1
+            )
+        })?;
+    }
+    table.insert(
+        "version".to_string(),
+        toml::Value::Integer(i64::from(CONFIG_VERSION)),
+    );
+    Ok(version)
+}
+
+/// What the main config file means, in the shape [`Config::save_to`] writes it
+///
+/// Known sections are re-serialized with their defaults filled in; `[host.<name>]`
+/// sections are kept as written, apart from their sinks and rules. Comparing this
+/// against the table to save tells which keys actually changed.
+fn normalized_table(file: &ConfigFile, raw: &toml::Table) -> Result<toml::Table> {
+    let mut table = toml::Table::try_from(file).context("Failed to serialize config")?;
+    if let Some(toml::Value::Table(hosts)) = raw.get("host") {
+        let mut normalized = toml::Table::new();
+        for (name, section) in hosts {
+            let mut section = section.as_table().cloned().unwrap_or_default();
+            normalize_list::<SinkConfigFile>(&mut section, "sinks")?;
+            normalize_list::<RuleConfigFile>(&mut section, "rules")?;
+            normalized.insert(name.clone(), toml::Value::Table(section));
+        }
+        table.insert("host".to_string(), toml::Value::Table(normalized));
+    }
+    Ok(table)
+}
+
+/// Re-serialize the `[[key]]` entries of `table` through their typed form
+fn normalize_list<T: DeserializeOwned + Serialize>(
+    table: &mut toml::Table,
+    key: &str,
+) -> Result<()> {
+    if let Some(value) = table.get_mut(key) {
+        let items: Vec<T> = value
+            .clone()
+            .try_into()
+            .with_context(|| format!("Invalid [[{key}]] entry"))?;
+        *value = toml::Value::try_from(items).context("Failed to serialize config")?;
+    }
+    Ok(())
+}
+
+/// A `[host.<name>]` section of the main config file
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+struct HostConfigFile {
+    settings: toml::Table,
+    replace_sinks: bool,
+    replace_rules: bool,
+    sinks: Vec<SinkConfigFile>,
+    rules: Vec<RuleConfigFile>,
+}
+
+/// Hostname of this machine, used to pick a `[host.<name>]` section
+fn current_hostname() -> Option<String> {
+    let uname = rustix::system::uname();
+    let name = uname.nodename().to_string_lossy().into_owned();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Parse the `[host.<name>]` sections of a config table and pick the one for `hostname`
+///
+/// Every section is checked so a typo is caught on any machine. A fully qualified
+/// hostname also matches a section named after its first label.
+fn host_section(
+    table: &toml::Table,
+    hostname: Option<&str>,
+) -> Result<Option<(String, HostConfigFile)>> {
+    let Some(hosts) = table.get("host") else {
+        return Ok(None);
+    };
+    let hosts = hosts
+        .as_table()
+        .context("'host' must be a table of [host.<name>] sections")?;
+
+    let mut sections = hosts
+        .iter()
+        .map(|(name, section)| {
+            let section: HostConfigFile = section
+                .clone()
+                .try_into()
+                .with_context(|| format!("Invalid [host.{name}] section"))?;
+            Ok((name.clone(), section))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let Some(hostname) = hostname else {
+        return Ok(None);
+    };
+    let short = hostname.split('.').next().unwrap_or(hostname);
+    let index = sections
+        .iter()
+        .position(|(name, _)| name == hostname)
+        .or_else(|| sections.iter().position(|(name, _)| name == short));
+    Ok(index.map(|i| sections.swap_remove(i)))
+}
+
+/// Override keys of `merged` with the `[section]` table of a config file
+fn merge_section(merged: &mut toml::Table, file: &toml::Table, section: &str) {
+    if let Some(toml::Value::Table(table)) = file.get(section) {
+        merged.extend(table.clone());
+    }
+}
+
+/// Expand `include` patterns of the config at `config_path` into files
+///
+/// Patterns are relative to the config's directory. Each glob's matches are sorted by
+/// path, patterns are taken in order, and a file matched twice (or the main config
+/// itself) is only read once. Globs may match nothing; plain paths must exist.
+fn resolve_includes(patterns: &[String], config_path: &Path) -> Result<Vec<PathBuf>> {
+    let base = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut seen = HashSet::from([config_path.to_path_buf()]);
+    let mut files = Vec::new();
+
+    for pattern in patterns {
+        let full = base.join(pattern);
+        let full_str = full.to_string_lossy();
+        let mut matches = glob::glob(&full_str)
+            .with_context(|| format!("Invalid include pattern '{pattern}'"))?
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Failed to expand include pattern '{pattern}'"))?;
+        matches.retain(|p| p.is_file() && !crate::config_backup::is_backup(p));
+        matches.sort();
+
+        if matches.is_empty() && glob::Pattern::escape(&full_str) == full_str {
+            eyre::bail!("Included config not found: {}", full.display());
+        }
+        for file in matches {
+            if seen.insert(file.clone()) {
+                files.push(file);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// FNV-1a hash of `path`, stable across builds so every pwsw version agrees on it
+fn instance_id_for(path: &Path) -> String {
+    let hash = path
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// Where a sink or rule is saved: its included file and `[host.<name>]` section
+type Origin<'a> = (&'a Option<PathBuf>, &'a Option<String>);
+
+fn origin_label((source, host): Origin<'_>) -> String {
+    match (source, host) {
+        (Some(file), _) => file.display().to_string(),
+        (None, Some(name)) => format!("[host.{name}]"),
+        (None, None) => "the main config file".to_string(),
+    }
+}
+
+/// Check that moving item `from` to `to` only passes items saved to the same place
+fn check_move(origins: &[Origin<'_>], from: usize, to: usize, kind: &str) -> Result<()> {
+    if from >= origins.len() || to >= origins.len() {
+        eyre::bail!("Position out of range (1-{})", origins.len());
+    }
+    let passed = if from < to {
+        &origins[from + 1..=to]
+    } else {
+        &origins[to..from]
+    };
+    if let Some(other) = passed.iter().find(|o| **o != origins[from]) {
+        eyre::bail!(
+            "Can't move past {kind} saved to {} (this one is saved to {})",
+            origin_label(*other),
+            origin_label(origins[from])
+        );
+    }
+    Ok(())
+}
+
+/// Check that saving keeps the order of `origins`, given the rank of each place
+fn ensure_ranked_order(
+    origins: &[Origin<'_>],
+    rank: impl Fn(Origin<'_>) -> Option<usize>,
+    kind: &str,
+) -> Result<()> {
+    let mut last: Option<(usize, Origin<'_>)> = None;
+    for origin in origins {
+        let Some(current) = rank(*origin) else {
+            continue;
+        };
+        if let Some((previous, previous_origin)) = last
+            && current < previous
+        {
+            eyre::bail!(
+                "Can't save {kind} in this order: {kind} from {} would be loaded before {kind} from {}",
+                origin_label(*origin),
+                origin_label(previous_origin)
+            );
+        }
+        last = Some((current, *origin));
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Config Implementation
 // ============================================================================
@@ -166,6 +877,11 @@ impl Config {
     pub fn load() -> Result<Self> {
         let config_path = Self::get_config_path()?;
 
+        // An explicit --config path is never created, so a typo doesn't go unnoticed
+        if !config_path.exists() && Self::config_path_override().is_some() {
+            eyre::bail!("Config file not found: {}", config_path.display());
+        }
+
         if !config_path.exists() {
             // When running tests (`cargo test`) avoid creating a default config
             // in the user's real XDG config directory, since tests should not
@@ -187,76 +903,159 @@ impl Config {
 
     /// Load configuration from a specific path. Useful for tests to avoid relying on XDG env.
     ///
+    /// The `[host.<name>]` section matching this machine's hostname is applied on top
+    /// of the main file: its `settings` keys override, its sinks are appended (or replace
+    /// the main file's with `replace_sinks`), and its rules are checked first (or replace
+    /// the main file's with `replace_rules`).
+    ///
+    /// Files named by `include` are merged in order after that: their `[settings]` and
+    /// `[notifications]` keys override earlier ones, and their sinks, rules and hooks
+    /// are appended.
+    ///
     /// # Errors
-    /// Returns an error if the config file cannot be read, parsed, or if validation fails.
-    pub fn load_from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
-        let path = path.as_ref();
-        let contents = fs::read_to_string(path).with_context(|| {
-            format!(
-                "Failed to read config: {path_display}",
-                path_display = path.display()
-            )
-        })?;
-        let config_file: ConfigFile = toml::from_str(&contents).with_context(|| {
-            format!(
-                "Failed to parse config: {path_display}",
-                path_display = path.display()
-            )
-        })?;
-        Self::from_config_file(config_file)
+    /// Returns an error if a config file cannot be read, parsed, or if validation fails.
+    pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::load_for_host(path.as_ref(), current_hostname().as_deref())
     }
 
-    fn from_config_file(config_file: ConfigFile) -> Result<Self> {
-        // Note: Empty sinks is allowed at config level (TUI can work with it).
-        // Daemon startup will check for sinks before running.
+    /// [`Config::load_from_path`] as if this machine were called `hostname`
+    fn load_for_host(path: &Path, hostname: Option<&str>) -> Result<Self> {
+        Self::load_with(path, hostname, None)
+    }
 
-        let settings = Settings {
-            default_on_startup: config_file.settings.default_on_startup,
-            set_smart_toggle: config_file.settings.set_smart_toggle,
-            notify_manual: config_file.settings.notify_manual,
-            notify_rules: config_file.settings.notify_rules,
-            match_by_index: config_file.settings.match_by_index,
-            log_level: config_file.settings.log_level,
+    /// [`Config::load_for_host`], reading `replaced` = `(file, text)` in place of the
+    /// contents of `file` (the main file or an included one)
+    fn load_with(
+        path: &Path,
+        hostname: Option<&str>,
+        replaced: Option<(&Path, &str)>,
+    ) -> Result<Self> {
+        let read_config_file = |file: &Path| match replaced {
+            Some((replaced, text)) if replaced == file => {
+                let (_, config_file, table) = parse_config_document(file, text.to_string())?;
+                Ok((config_file, table))
+            }
+            _ => read_config_file(file),
         };
+        let (mut main, main_table) = read_config_file(path)?;
+        let host_section = host_section(&main_table, hostname)?;
 
-        let sinks: Vec<SinkConfig> = config_file
-            .sinks
-            .into_iter()
-            .map(|s| SinkConfig {
-                name: s.name,
-                desc: s.desc,
-                icon: s.icon,
-                default: s.default,
-            })
-            .collect();
+        let mut settings = toml::Table::new();
+        let mut notifications = toml::Table::new();
+        merge_section(&mut settings, &main_table, "settings");
+        merge_section(&mut notifications, &main_table, "notifications");
 
-        let rules = config_file
-            .rules
-            .into_iter()
-            .map(|r| {
-                let app_id_pattern = r.app_id.as_str().to_string();
-                let title_pattern = r.title.as_ref().map(|t| t.as_str().to_string());
-                Rule {
-                    app_id_regex: r.app_id,
-                    title_regex: r.title,
-                    sink_ref: r.sink,
-                    desc: r.desc,
-                    notify: r.notify,
-                    app_id_pattern,
-                    title_pattern,
+        let host = host_section.as_ref().map(|(name, section)| {
+            settings.extend(section.settings.clone());
+            if section.replace_sinks {
+                main.sinks.clear();
+            }
+            if section.replace_rules {
+                main.rules.clear();
+            }
+            HostOverride {
+                name: name.clone(),
+                settings: section.settings.keys().cloned().collect(),
+                replace_sinks: section.replace_sinks,
+                replace_rules: section.replace_rules,
+            }
+        });
+        let main_sinks = main.sinks.len();
+
+        let mut sinks = Vec::new();
+        let mut rules = Vec::new();
+        let mut hooks = Vec::new();
+        let mut add_items = |file: ConfigFile, source: Option<&Path>| {
+            let source = source.map(Path::to_path_buf);
+            sinks.extend(file.sinks.into_iter().map(|s| s.into_sink(source.clone())));
+            rules.extend(file.rules.into_iter().map(|r| r.into_rule(source.clone())));
+            hooks.extend(file.hooks.into_iter().map(|h| h.into_hook(source.clone())));
+        };
+
+        let includes = main.include.clone();
+        let included = resolve_includes(&includes, path)?;
+        let mut included_settings = BTreeMap::new();
+        add_items(main, None);
+        for file_path in &included {
+            let (file, table) = read_config_file(file_path)?;
+            if !file.include.is_empty() {
+                eyre::bail!(
+                    "Included config {} has its own include, which is not supported",
+                    file_path.display()
+                );
+            }
+            if table.contains_key("host") {
+                eyre::bail!(
+                    "Included config {} has [host] sections, which are only supported in the main config",
+                    file_path.display()
+                );
+            }
+            merge_section(&mut settings, &table, "settings");
+            merge_section(&mut notifications, &table, "notifications");
+            for section in ["settings", "notifications"] {
+                if let Some(toml::Value::Table(values)) = table.get(section) {
+                    for key in values.keys() {
+                        included_settings.insert(format!("{section}.{key}"), file_path.clone());
+                    }
                 }
-            })
-            .collect();
+            }
+            add_items(file, Some(file_path));
+        }
+
+        // Host sinks follow the main file's; host rules take priority over all others
+        if let Some((name, section)) = host_section {
+            let host_sinks = section.sinks.into_iter().map(|s| SinkConfig {
+                host: Some(name.clone()),
+                ..s.into_sink(None)
+            });
+            sinks.splice(main_sinks..main_sinks, host_sinks);
+            let host_rules = section.rules.into_iter().map(|r| Rule {
+                host: Some(name.clone()),
+                ..r.into_rule(None)
+            });
+            rules.splice(0..0, host_rules);
+        }
+
+        let settings: SettingsFile = toml::Value::Table(settings)
+            .try_into()
+            .context("Failed to merge [settings] from included configs")?;
+        let notifications: NotificationsFile = toml::Value::Table(notifications)
+            .try_into()
+            .context("Failed to merge [notifications] from included configs")?;
 
         let config = Self {
-            settings,
+            settings: settings.into_settings(),
+            notifications: notifications.into_settings(),
             sinks,
             rules,
+            hooks,
+            includes,
+            included_settings,
+            host,
         };
         config.validate()?;
         Ok(config)
     }
 
+    /// Files the configuration at `path` is read from: the main file, then its includes
+    ///
+    /// Used by the daemon to know which changes need a reload.
+    ///
+    /// # Errors
+    /// Returns an error if an include pattern is invalid or a plain included path is missing.
+    pub fn source_files(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = vec![path.to_path_buf()];
+        files.extend(resolve_includes(&self.includes, path)?);
+        Ok(files)
+    }
+
+    /// Absolute `include` patterns, resolved against the directory of `path`
+    #[must_use]
+    pub fn include_patterns(&self, path: &Path) -> Vec<PathBuf> {
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        self.includes.iter().map(|p| base.join(p)).collect()
+    }
+
     /// Save configuration to the default XDG config path
     ///
     /// # Errors
@@ -264,23 +1063,355 @@ impl Config {
     /// # Panics
     /// This function panics if the config path has no parent directory (should never happen).
     pub fn save(&self) -> Result<()> {
-        let config_file = self.to_config_file();
-        let toml_str =
-            toml::to_string_pretty(&config_file).context("Failed to serialize config to TOML")?;
-
         let config_path = Self::get_config_path()?;
-        Self::save_to_path_str(&toml_str, &config_path)
+        self.save_to(&config_path)
+    }
+
+    /// Save configuration to the specified path (used by tests to avoid touching XDG paths)
+    ///
+    /// Sinks, rules and hooks read from an included file are written back to that file
+    /// (only if they changed), and so are the settings it overrides; everything else
+    /// goes to `path`.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails or if the config cannot be written to disk.
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let included = resolve_includes(&self.includes, path)?;
+        self.check_saved_order(&included)?;
+        let mut table =
+            toml::Table::try_from(self.to_config_file()).context("Failed to serialize config")?;
+        for file_path in &included {
+            self.save_included(file_path, &table)?;
+        }
+
+        let on_disk = if path.exists() {
+            Some(read_config_document(path)?)
+        } else {
+            None
+        };
+        let old = match &on_disk {
+            Some((_, file, raw)) => normalized_table(file, raw)?,
+            None => toml::Table::new(),
+        };
+
+        // Values an include overrides went back to it; the main file keeps its own
+        for (section, key) in self.included_setting_keys(None) {
+            let on_disk = old
+                .get(section)
+                .and_then(toml::Value::as_table)
+                .and_then(|t| t.get(key));
+            if let Some(toml::Value::Table(values)) = table.get_mut(section) {
+                match on_disk {
+                    Some(value) => values.insert(key.to_string(), value.clone()),
+                    None => values.remove(key),
+                };
+            }
+        }
+        self.write_host_sections(&mut table, &old)?;
+
+        // Edit the existing file in place so comments, order and unknown keys survive
+        let toml_str = match &on_disk {
+            Some((contents, ..)) => config_edit::patch_document(contents, &old, &table)
+                .with_context(|| format!("Failed to update config: {}", path.display()))?,
+            None => toml::to_string_pretty(&table).context("Failed to serialize config to TOML")?,
+        };
+        Self::save_to_path_str(&toml_str, path)
+    }
+
+    /// Upgrade the config file at `path` and the files it includes to [`CONFIG_VERSION`]
+    ///
+    /// Each file that needs it is edited in place (keeping comments) and returned with
+    /// the version it was at. Each changed file is backed up like any other save.
+    ///
+    /// # Errors
+    /// Returns an error if a file cannot be read or parsed, is from a newer version,
+    /// or cannot be written.
+    pub fn migrate(path: &Path) -> Result<Vec<(PathBuf, u32)>> {
+        let mut migrated = Vec::new();
+        let main = Self::migrate_file(path)?;
+        let includes = main.1.get("include").cloned().map_or_else(
+            || Ok(Vec::new()),
+            |include| {
+                include
+                    .try_into()
+                    .context("'include' must be a list of paths")
+            },
+        )?;
+        if let Some(from) = main.0 {
+            migrated.push((path.to_path_buf(), from));
+        }
+        for file_path in resolve_includes(&includes, path)? {
+            if let (Some(from), _) = Self::migrate_file(&file_path)? {
+                migrated.push((file_path, from));
+            }
+        }
+        Ok(migrated)
+    }
+
+    /// Upgrade one config file, returning its old version (if it changed) and new table
+    fn migrate_file(path: &Path) -> Result<(Option<u32>, toml::Table)> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {}", path.display()))?;
+        let parse_context = || format!("Failed to parse config: {}", path.display());
+        let original: toml::Table = toml::from_str(&contents).with_context(parse_context)?;
+        let mut table = original.clone();
+        let from = migrate_table(&mut table).with_context(parse_context)?;
+        if table == original {
+            return Ok((None, table));
+        }
+
+        // Make sure the result loads before touching the file
+        let _: ConfigFile = toml::Value::Table(table.clone())
+            .try_into()
+            .with_context(|| format!("Migrated config is invalid: {}", path.display()))?;
+        let toml_str = config_edit::patch_document(&contents, &original, &table)
+            .with_context(|| format!("Failed to update config: {}", path.display()))?;
+        Self::save_to_path_str(&toml_str, path)?;
+        Ok((Some(from), table))
+    }
+
+    /// Carry the `[host.<name>]` sections of the main file over into `table`
+    ///
+    /// `on_disk` is the file as it is now (see [`normalized_table`]). Sections for
+    /// other machines are kept as they are. The applied section gets its sinks, rules
+    /// and overridden settings back, while the shared values it overrides or replaces
+    /// stay as they are on disk.
+    fn write_host_sections(&self, table: &mut toml::Table, on_disk: &toml::Table) -> Result<()> {
+        let mut hosts = match on_disk.get("host") {
+            Some(toml::Value::Table(hosts)) => hosts.clone(),
+            _ => toml::Table::new(),
+        };
+
+        if let Some(host) = &self.host {
+            let mut section = match hosts.remove(&host.name) {
+                Some(toml::Value::Table(section)) => section,
+                _ => toml::Table::new(),
+            };
+
+            let mut host_settings = match section.remove("settings") {
+                Some(toml::Value::Table(settings)) => settings,
+                _ => toml::Table::new(),
+            };
+            let disk_settings = on_disk.get("settings").and_then(toml::Value::as_table);
+            if let Some(toml::Value::Table(settings)) = table.get_mut("settings") {
+                // Includes are applied after the host section, so keys they set are theirs
+                let host_keys = host.settings.iter().filter(|key| {
+                    !self
+                        .included_settings
+                        .contains_key(&format!("settings.{key}"))
+                });
+                for key in host_keys {
+                    if let Some(value) = settings.remove(key) {
+                        host_settings.insert(key.clone(), value);
+                    }
+                    if let Some(value) = disk_settings.and_then(|t| t.get(key)) {
+                        settings.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+            if !host_settings.is_empty() {
+                section.insert("settings".to_string(), toml::Value::Table(host_settings));
+            }
+
+            // With replace_*, the main file's own list isn't loaded, so every list item
+            // not from an include belongs to the host section
+            let owns = |source: &Option<PathBuf>, item_host: &Option<String>, replace: bool| {
+                source.is_none() && (replace || item_host.as_ref() == Some(&host.name))
+            };
+            let sinks = toml::Value::try_from(
+                self.sinks
+                    .iter()
+                    .filter(|s| owns(&s.source, &s.host, host.replace_sinks))
+                    .map(SinkConfigFile::from)
+                    .collect::<Vec<_>>(),
+            );
+            let rules = toml::Value::try_from(
+                self.rules
+                    .iter()
+                    .filter(|r| owns(&r.source, &r.host, host.replace_rules))
+                    .map(RuleConfigFile::from)
+                    .collect::<Vec<_>>(),
+            );
+            for (key, value, replace) in [
+                ("sinks", sinks, host.replace_sinks),
+                ("rules", rules, host.replace_rules),
+            ] {
+                let value = value.context("Failed to serialize host section")?;
+                if value.as_array().is_some_and(Vec::is_empty) {
+                    section.remove(key);
+                } else {
+                    section.insert(key.to_string(), value);
+                }
+                if replace {
+                    match on_disk.get(key) {
+                        Some(shared) => table.insert(key.to_string(), shared.clone()),
+                        None => table.remove(key),
+                    };
+                }
+            }
+
+            hosts.insert(host.name.clone(), toml::Value::Table(section));
+        }
+
+        if !hosts.is_empty() {
+            table.insert("host".to_string(), toml::Value::Table(hosts));
+        }
+        Ok(())
     }
 
-    /// Save configuration to the specified path (used by tests to avoid touching XDG paths)
-    ///
-    /// # Errors
-    /// Returns an error if serialization fails or if the config cannot be written to disk.
-    pub fn save_to<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
-        let config_file = self.to_config_file();
-        let toml_str =
-            toml::to_string_pretty(&config_file).context("Failed to serialize config to TOML")?;
-        Self::save_to_path_str(&toml_str, path.as_ref())
+    /// Make sure saving won't reorder sinks or rules
+    ///
+    /// Each is written to the file it was read from, which is loaded in a fixed order:
+    /// host rules, then the main file's, then each included file's; the main file's
+    /// sinks, then host sinks, then each included file's. With `replace_*` the host
+    /// section owns every list item not from an include.
+    fn check_saved_order(&self, included: &[PathBuf]) -> Result<()> {
+        let include_rank = |file: &PathBuf| included.iter().position(|f| f == file);
+        let replace_sinks = self.host.as_ref().is_some_and(|h| h.replace_sinks);
+        let replace_rules = self.host.as_ref().is_some_and(|h| h.replace_rules);
+        let sinks: Vec<_> = self.sinks.iter().map(|s| (&s.source, &s.host)).collect();
+        ensure_ranked_order(
+            &sinks,
+            |origin| match origin {
+                (Some(file), _) => include_rank(file).map(|i| i + 2),
+                (None, host) => Some(usize::from(host.is_some() || replace_sinks)),
+            },
+            "sinks",
+        )?;
+        let rules: Vec<_> = self.rules.iter().map(|r| (&r.source, &r.host)).collect();
+        ensure_ranked_order(
+            &rules,
+            |origin| match origin {
+                (Some(file), _) => include_rank(file).map(|i| i + 2),
+                (None, host) => Some(usize::from(host.is_none() || replace_rules)),
+            },
+            "rules",
+        )
+    }
+
+    /// Included file a `[settings]` or `[notifications]` key is saved to, if one
+    /// overrides it (see [`Config::set_value`] for the key format)
+    #[must_use]
+    pub fn setting_file(&self, key: &str) -> Option<&Path> {
+        let (section, name) = setting_key(key).ok()?;
+        self.included_settings
+            .get(&format!("{section}.{name}"))
+            .map(PathBuf::as_path)
+    }
+
+    /// `(section, key)` of the settings set by `file`, or by any included file
+    fn included_setting_keys(&self, file: Option<&Path>) -> Vec<(&str, &str)> {
+        self.included_settings
+            .iter()
+            .filter(|(_, owner)| file.is_none_or(|f| f == owner.as_path()))
+            .filter_map(|(key, _)| key.split_once('.'))
+            .collect()
+    }
+
+    /// Update the sinks, rules, hooks and overridden settings of an included file if
+    /// they changed
+    ///
+    /// `merged` is the whole config serialized. Other keys in the file and comments
+    /// are kept.
+    fn save_included(&self, file_path: &Path, merged: &toml::Table) -> Result<()> {
+        let source = Some(file_path.to_path_buf());
+        let sinks: Vec<_> = self.sinks.iter().filter(|s| s.source == source).collect();
+        let rules: Vec<_> = self.rules.iter().filter(|r| r.source == source).collect();
+        let hooks: Vec<_> = self.hooks.iter().filter(|h| h.source == source).collect();
+
+        let (contents, on_disk, mut table) = read_config_document(file_path)?;
+        let unchanged = on_disk.sinks.len() == sinks.len()
+            && on_disk.rules.len() == rules.len()
+            && on_disk.hooks.len() == hooks.len()
+            && on_disk
+                .sinks
+                .into_iter()
+                .zip(&sinks)
+                .all(|(s, current)| s.into_sink(source.clone()) == **current)
+            && on_disk
+                .rules
+                .into_iter()
+                .zip(&rules)
+                .all(|(r, current)| r.into_rule(source.clone()) == **current)
+            && on_disk
+                .hooks
+                .into_iter()
+                .zip(&hooks)
+                .all(|(h, current)| h.into_hook(source.clone()) == **current);
+
+        let mut old = table.clone();
+        normalize_list::<SinkConfigFile>(&mut old, "sinks")?;
+        normalize_list::<RuleConfigFile>(&mut old, "rules")?;
+        normalize_list::<HookConfigFile>(&mut old, "hooks")?;
+
+        let mut settings_changed = false;
+        for (section, key) in self.included_setting_keys(Some(file_path)) {
+            let value = merged
+                .get(section)
+                .and_then(toml::Value::as_table)
+                .and_then(|t| t.get(key));
+            if let Some(toml::Value::Table(values)) = table.get_mut(section)
+                && values.get(key) != value
+            {
+                // Unset optional keys (e.g. a cleared `command`) are removed
+                match value {
+                    Some(value) => values.insert(key.to_string(), value.clone()),
+                    None => values.remove(key),
+                };
+                settings_changed = true;
+            }
+        }
+        if unchanged && !settings_changed {
+            return Ok(());
+        }
+
+        let sections = [
+            (
+                "sinks",
+                toml::Value::try_from(
+                    sinks
+                        .iter()
+                        .map(|s| SinkConfigFile::from(*s))
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+            (
+                "rules",
+                toml::Value::try_from(
+                    rules
+                        .iter()
+                        .map(|r| RuleConfigFile::from(*r))
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+            (
+                "hooks",
+                toml::Value::try_from(
+                    hooks
+                        .iter()
+                        .map(|h| HookConfigFile::from(*h))
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+        ];
+        for (key, value) in sections {
+            let value = value.context("Failed to serialize included config")?;
+            if value.as_array().is_some_and(Vec::is_empty) {
+                table.remove(key);
+            } else {
+                table.insert(key.to_string(), value);
+            }
+        }
+
+        let mut toml_str = config_edit::patch_document(&contents, &old, &table)
+            .with_context(|| format!("Failed to update config: {}", file_path.display()))?;
+        // Removing the last item of an included file is fine; keep the file non-empty
+        // so the guard against accidental empty writes doesn't refuse it
+        if toml_str.trim().is_empty() {
+            toml_str = "# No sinks, rules or hooks left in this included file\n".to_string();
+        }
+        Self::save_to_path_str(&toml_str, file_path)
     }
 
     fn write_temp_file_with_contents(
@@ -353,30 +1484,20 @@ impl Config {
         Ok(())
     }
 
-    fn ensure_write_allowed_and_backup(config_path: &std::path::Path) -> Result<()> {
+    /// Keep a timestamped copy of an existing config before it is overwritten
+    ///
+    /// Atomic writes already prevent corruption - this is just for user recovery
+    /// ("undo"), so a failed backup only logs a warning.
+    fn backup_config(config_path: &Path) {
+        if let Err(e) = config_backup::create(config_path) {
+            warn!("Failed to back up config {}: {e:#}", config_path.display());
+        }
+    }
+
+    fn ensure_write_allowed(config_path: &std::path::Path) -> Result<()> {
         if let Some(home_dir) = dirs::home_dir() {
             let home_cfg = home_dir.join(".config").join("pwsw").join("config.toml");
             if config_path == home_cfg {
-                // Create a single backup of the existing file if present.
-                // Only one backup is kept (overwrites previous) since atomic writes
-                // already prevent corruption - this is just for user recovery ("undo").
-                if config_path.exists()
-                    && let Ok(metadata) = fs::metadata(config_path)
-                    && metadata.is_file()
-                {
-                    let bak_path = config_path
-                        .parent()
-                        .expect("XDG config path always has parent directory")
-                        .join("config.toml.bak");
-                    if let Err(e) = fs::copy(config_path, &bak_path) {
-                        warn!(
-                            "Failed to create config backup at {}: {}",
-                            bak_path.display(),
-                            e
-                        );
-                    }
-                }
-
                 // Debug logging: record attempted write details
                 // Only enabled in debug builds to avoid security risks in production
                 #[cfg(debug_assertions)]
@@ -458,7 +1579,9 @@ impl Config {
         // Ensure user-only permissions on Unix for the temp file
         Self::ensure_unix_permissions(tmp.path())?;
 
-        Self::ensure_write_allowed_and_backup(config_path)?;
+        Self::ensure_write_allowed(config_path)?;
+        // Every replaced file, including included ones, can be restored
+        Self::backup_config(config_path);
 
         // Persist atomically
         tmp.persist(config_path).with_context(|| {
@@ -491,6 +1614,9 @@ impl Config {
     }
 
     /// Convert runtime `Config` back to serializable `ConfigFile` format
+    ///
+    /// Only items from the main config file (outside `[host.<name>]`) are included; see
+    /// [`Config::save_to`].
     fn to_config_file(&self) -> ConfigFile {
         let settings = SettingsFile {
             default_on_startup: self.settings.default_on_startup,
@@ -501,40 +1627,58 @@ impl Config {
             log_level: self.settings.log_level.clone(),
         };
 
-        let sinks = self
-            .sinks
-            .iter()
-            .map(|s| SinkConfigFile {
-                name: s.name.clone(),
-                desc: s.desc.clone(),
-                icon: s.icon.clone(),
-                default: s.default,
-            })
-            .collect();
-
-        let rules = self
-            .rules
-            .iter()
-            .map(|r| RuleConfigFile {
-                app_id: r.app_id_regex.clone(),
-                title: r.title_regex.clone(),
-                sink: r.sink_ref.clone(),
-                desc: r.desc.clone(),
-                notify: r.notify,
-            })
-            .collect();
+        let n = &self.notifications;
+        let notifications = NotificationsFile {
+            summary: n.summary.clone(),
+            body: n.body.clone(),
+            manual_body: n.manual_body.clone(),
+            timeout_ms: n.timeout_ms,
+            urgency: n.urgency,
+            sound: n.sound.clone(),
+            command: n.command.clone(),
+            switch: n.switch,
+            reload: n.reload,
+            start_stop: n.start_stop,
+            errors: n.errors,
+        };
 
         ConfigFile {
+            version: CONFIG_VERSION,
+            include: self.includes.clone(),
             settings,
-            sinks,
-            rules,
+            notifications,
+            sinks: self
+                .sinks
+                .iter()
+                .filter(|s| s.source.is_none() && s.host.is_none())
+                .map(SinkConfigFile::from)
+                .collect(),
+            rules: self
+                .rules
+                .iter()
+                .filter(|r| r.source.is_none() && r.host.is_none())
+                .map(RuleConfigFile::from)
+                .collect(),
+            hooks: self
+                .hooks
+                .iter()
+                .filter(|h| h.source.is_none())
+                .map(HookConfigFile::from)
+                .collect(),
         }
     }
 
-    fn validate(&self) -> Result<()> {
+    /// Check the config for mistakes the types can't catch
+    ///
+    /// Run on every load, and by `pwsw config` edits before they are saved.
+    ///
+    /// # Errors
+    /// Returns an error describing the first problem found (e.g., an unknown log level,
+    /// no default sink, or a rule pointing at a missing sink).
+    pub fn validate(&self) -> Result<()> {
         // Validate log level
         match self.settings.log_level.as_str() {
-            "error" | "warn" | "info" | "debug" | "trace" => {}
+            level if LOG_LEVELS.contains(&level) => {}
             level => eyre::bail!(
                 "Invalid log_level '{level}'. Must be: error, warn, info, debug, or trace"
             ),
@@ -579,33 +1723,147 @@ impl Config {
 
         // All rule sinks must exist and patterns must be non-empty
         for (i, rule) in self.rules.iter().enumerate() {
-            // Empty app_id pattern would match everything - require explicit `.*` for that
-            if rule.app_id_pattern.is_empty() {
+            let result = self.validate_rule(i, rule);
+            match (&rule.source, &rule.host) {
+                (Some(source), _) => result.wrap_err_with(|| {
+                    format!("Rule {} is defined in {}", i + 1, source.display())
+                })?,
+                (None, Some(host)) => result
+                    .wrap_err_with(|| format!("Rule {} is defined in [host.{host}]", i + 1))?,
+                (None, None) => result?,
+            }
+        }
+
+        let n = &self.notifications;
+        for (field, template) in [
+            ("summary", &n.summary),
+            ("body", &n.body),
+            ("manual_body", &n.manual_body),
+        ] {
+            validate_notification_template(template)
+                .with_context(|| format!("Invalid notifications.{field}"))?;
+        }
+        if n.sound.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            eyre::bail!("notifications.sound is empty. Remove it to disable the sound hint.");
+        }
+        if let Some(command) = &n.command {
+            if command
+                .first()
+                .is_none_or(|program| program.trim().is_empty())
+            {
                 eyre::bail!(
-                    "Rule {} has empty app_id pattern. Use '.*' to match all windows.",
-                    i + 1
+                    "notifications.command needs a program. Remove it to use desktop notifications."
                 );
             }
+            for arg in command {
+                crate::template::Template::parse(arg, NOTIFICATION_COMMAND_PLACEHOLDERS)
+                    .with_context(|| format!("Invalid notifications.command argument '{arg}'"))?;
+            }
+        }
+
+        for (i, hook) in self.hooks.iter().enumerate() {
+            if hook.command.trim().is_empty() {
+                eyre::bail!("Hook {} has an empty command", i + 1);
+            }
+            if hook.timeout_secs == 0 {
+                eyre::bail!("Hook {} has timeout_secs = 0. Use at least 1.", i + 1);
+            }
+        }
+
+        Ok(())
+    }
 
-            if self.resolve_sink(&rule.sink_ref).is_none() {
-                let available: Vec<_> = self
-                    .sinks
+    /// Find rules that are valid but can never take effect, one entry per rule
+    ///
+    /// The first rule matching a window decides its sink, so a rule matched only by
+    /// windows an earlier rule also matches (a duplicate, or one after a catch-all like
+    /// `app_id = ".*"` without a title) never applies. A rule switching to the default
+    /// sink only matters when it can win over a rule switching elsewhere: a later rule
+    /// with `match_by_index`, any other rule otherwise.
+    #[must_use]
+    pub fn lint_rules(&self) -> Vec<Option<RuleLint>> {
+        let mut lints: Vec<Option<RuleLint>> = self
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let (j, earlier) = self.rules[..i]
                     .iter()
                     .enumerate()
-                    .map(|(idx, s)| format!("{}. '{}'", idx + 1, s.desc))
-                    .collect();
-                eyre::bail!(
-                    "Rule {} references unknown sink '{}'. Available: [{}]",
-                    i + 1,
-                    rule.sink_ref,
-                    available.join(", ")
-                );
+                    .find(|(_, earlier)| rule_covers(earlier, rule))?;
+                Some(
+                    if earlier.app_id_pattern == rule.app_id_pattern
+                        && earlier.title_pattern == rule.title_pattern
+                    {
+                        RuleLint::Duplicate(j)
+                    } else {
+                        RuleLint::Shadowed(j)
+                    },
+                )
+            })
+            .collect();
+
+        let targets_default = |rule: &Rule| {
+            self.resolve_sink(&rule.sink_ref)
+                .is_some_and(|sink| sink.default)
+        };
+        for i in 0..self.rules.len() {
+            if lints[i].is_some() || !targets_default(&self.rules[i]) {
+                continue;
             }
+            let switches_away = self.rules.iter().enumerate().any(|(j, other)| {
+                let outranked = if self.settings.match_by_index {
+                    j > i
+                } else {
+                    j != i
+                };
+                outranked && lints[j].is_none() && !targets_default(other)
+            });
+            if !switches_away {
+                lints[i] = Some(RuleLint::TargetsDefault);
+            }
+        }
+        lints
+    }
+
+    /// Validate a single rule (`i` is its 0-based position)
+    fn validate_rule(&self, i: usize, rule: &Rule) -> Result<()> {
+        // Empty app_id pattern would match everything - require explicit `.*` for that
+        if rule.app_id_pattern.is_empty() {
+            eyre::bail!(
+                "Rule {} has empty app_id pattern. Use '.*' to match all windows.",
+                i + 1
+            );
+        }
+
+        if self.resolve_sink(&rule.sink_ref).is_none() {
+            let available: Vec<_> = self
+                .sinks
+                .iter()
+                .enumerate()
+                .map(|(idx, s)| format!("{}. '{}'", idx + 1, s.desc))
+                .collect();
+            eyre::bail!(
+                "Rule {} references unknown sink '{}'. Available: [{}]",
+                i + 1,
+                rule.sink_ref,
+                available.join(", ")
+            );
+        }
+
+        // Validate regex patterns for catastrophic backtracking
+        Self::validate_regex_safe(&rule.app_id_pattern, "app_id", i + 1)?;
+        if let Some(ref title_pattern) = rule.title_pattern {
+            Self::validate_regex_safe(title_pattern, "title", i + 1)?;
+        }
 
-            // Validate regex patterns for catastrophic backtracking
-            Self::validate_regex_safe(&rule.app_id_pattern, "app_id", i + 1)?;
-            if let Some(ref title_pattern) = rule.title_pattern {
-                Self::validate_regex_safe(title_pattern, "title", i + 1)?;
+        for (field, template) in [
+            ("notify_summary", &rule.notify_summary),
+            ("notify_body", &rule.notify_body),
+        ] {
+            if let Some(template) = template {
+                validate_notification_template(template)
+                    .with_context(|| format!("Rule {}: invalid {field}", i + 1))?;
             }
         }
 
@@ -663,8 +1921,46 @@ impl Config {
         Ok(())
     }
 
+    /// Use `path` instead of the XDG config path for the rest of the process (`--config`)
+    ///
+    /// The path is made absolute so that includes, the daemon's file watcher and the
+    /// background daemon process all agree on it.
+    ///
+    /// # Errors
+    /// Returns an error if the path cannot be made absolute or an override is already set.
+    pub fn set_config_path(path: &Path) -> Result<()> {
+        let path = std::path::absolute(path)
+            .with_context(|| format!("Invalid config path: {}", path.display()))?;
+        CONFIG_PATH_OVERRIDE
+            .set(path)
+            .map_err(|_| eyre::eyre!("Config path is already set"))
+    }
+
+    /// Config path set with [`Config::set_config_path`], if any
+    #[must_use]
+    pub fn config_path_override() -> Option<&'static Path> {
+        CONFIG_PATH_OVERRIDE.get().map(PathBuf::as_path)
+    }
+
+    /// Id of the `--config` file, giving its daemon its own socket and PID file
+    ///
+    /// `None` without `--config` or when it names the default config file, so those
+    /// share the usual daemon.
+    #[must_use]
+    pub fn instance_id() -> Option<String> {
+        let path = Self::config_path_override()?;
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let default = Self::default_config_path().ok();
+        if default.is_some_and(|d| fs::canonicalize(&d).unwrap_or(d) == path) {
+            return None;
+        }
+        Some(instance_id_for(&path))
+    }
+
     /// Get the XDG config path for PWSW
     ///
+    /// Returns the `--config` path instead when one was given.
+    ///
     /// # Errors
     /// Returns an error if the config directory cannot be determined or created.
     /// Get the configured XDG config path for PWSW without creating directories.
@@ -675,6 +1971,14 @@ impl Config {
     /// test code from accidentally touching the user's real XDG config when it only
     /// needs the path.
     pub fn get_config_path() -> Result<PathBuf> {
+        if let Some(path) = Self::config_path_override() {
+            return Ok(path.to_path_buf());
+        }
+        Self::default_config_path()
+    }
+
+    /// `~/.config/pwsw/config.toml`, whether or not `--config` was given
+    fn default_config_path() -> Result<PathBuf> {
         // Compute the XDG config dir path for PWSW but do NOT create it here.
         // Creating the directory had the side-effect of touching the user's
         // real XDG config during tests when helper code only needed the path.
@@ -702,12 +2006,20 @@ impl Config {
         Ok(config_path)
     }
 
-    fn create_default_config(path: &PathBuf) -> Result<()> {
-        let default_config = r#"# PWSW (PipeWire Switcher) Configuration
+    /// Text of a new config file: commented defaults, plus `sinks` if any were detected
+    fn default_config_text(sinks: &[SinkConfig]) -> Result<String> {
+        let template = r#"# PWSW (PipeWire Switcher) Configuration
 #
-# Run `pwsw tui` for interactive setup, or configure manually below.
+# Run `pwsw init` to detect your audio outputs, `pwsw tui` for interactive
+# setup, or configure manually below.
 # Find available sinks with: pwsw list-sinks
 
+version = 1 # Config layout version; upgrade older files with `pwsw config migrate`
+
+# Merge more config files after this one (paths or globs, relative to this file).
+# Their settings override these; their sinks, rules and hooks are appended.
+# include = ["conf.d/*.toml"]
+
 [settings]
 default_on_startup = false # Switch to default sink on daemon start
 set_smart_toggle = true    # set-sink toggles back to default if already active
@@ -731,6 +2043,7 @@ log_level = "info"         # error, warn, info, debug, trace
 # name = "alsa_output.pci-0000_00_1f.3.hdmi-stereo"
 # desc = "HDMI"
 # icon = "video-display"  # Optional: override auto-detected icon
+# glyph = "󰍹"              # Optional: status bar glyph for --format output
 
 # Window rules - match windows to sinks
 # Find app_id and title with: pwsw list-windows (requires running daemon)
@@ -749,14 +2062,99 @@ log_level = "info"         # error, warn, info, debug, trace
 # title = "^Steam Big Picture"  # Optional: also match window title
 # sink = "HDMI"                 # Reference by desc, name, or position (1, 2)
 # desc = "Steam Gaming"         # Optional: custom notification text
+# notify_body = "{app_id} → {sink_desc}"  # Optional: override [notifications] body
+
+# Notification content - templates may use {sink_desc}, {sink_name},
+# {prev_sink_desc}, {reason} and {app_id}
+#
+# [notifications]
+# summary = "Audio Output"
+# body = "{sink_desc} → {reason}"  # Rule-triggered switches
+# manual_body = "{sink_desc}"      # Manual switches
+# timeout_ms = 3000                # 0 = until dismissed
+# urgency = "normal"               # low, normal, critical
+# sound = "message-new-instant"    # Optional: sound theme name
+# command = ["logger", "-t", "pwsw", "{summary}: {body}"]  # Optional: run instead
+# switch = true                    # Per-event toggles
+# reload = true
+# start_stop = true
+# errors = true
+
+# Hooks - run a shell command on daemon events (switch, window_tracked,
+# daemon_start, reload_failed). Commands get PWSW_EVENT, PWSW_SINK_NAME,
+# PWSW_SINK_DESC, PWSW_REASON and PWSW_APP_ID in their environment.
+#
+# Example:
+#
+# [[hooks]]
+# on = "switch"
+# command = "~/.local/bin/eq-preset \"$PWSW_SINK_NAME\""
+# timeout_secs = 10             # Optional: kill the command after this long
 "#;
+        if sinks.is_empty() {
+            return Ok(template.to_string());
+        }
+
+        #[derive(Serialize)]
+        struct Sinks {
+            sinks: Vec<SinkConfigFile>,
+        }
+        let sinks = toml::to_string(&Sinks {
+            sinks: sinks.iter().map(SinkConfigFile::from).collect(),
+        })
+        .context("Failed to serialize sinks")?;
+        // The sink examples stay above the detected sinks, the other sections below
+        config_edit::append_tables(template, &sinks, "# Window rules")
+    }
+
+    /// Write a new config file at `path` with `sinks` and default settings
+    ///
+    /// Used by `pwsw init`. The new config is checked before anything is written; an
+    /// existing file is then replaced atomically (and backed up like any other save).
+    ///
+    /// # Errors
+    /// Returns an error if the result does not load or the file cannot be written.
+    pub fn create_with_sinks(path: &Path, sinks: &[SinkConfig]) -> Result<Self> {
+        let text = Self::default_config_text(sinks)?;
+        Self::load_replacing(path, path, &text).context("Generated config is not valid")?;
+        Self::save_to_path_str(&text, path)?;
+        Self::load_from_path(path)
+    }
+
+    /// Replace `file` with `backup`, where `file` is the config file at `path` or one
+    /// of the files it includes
+    ///
+    /// The config is loaded with the backup in place of `file` first, and the backup is
+    /// only written if that still gives a valid config. The replacement is atomic, and
+    /// the file being replaced is backed up like any other save.
+    ///
+    /// # Errors
+    /// Returns an error if the backup cannot be read, does not load, or cannot be written.
+    pub fn restore_backup(path: &Path, file: &Path, backup: &Backup) -> Result<Self> {
+        let text = fs::read_to_string(&backup.path)
+            .with_context(|| format!("Failed to read backup: {}", backup.path.display()))?;
+        Self::load_replacing(path, file, &text)
+            .with_context(|| format!("Backup {} is not a valid config", backup.id))?;
+
+        Self::save_to_path_str(&text, file)?;
+        Self::load_from_path(path)
+    }
+
+    /// Load the config at `path` as if `file` (`path` or one of its includes) held
+    /// `text`, without writing anything
+    fn load_replacing(path: &Path, file: &Path, text: &str) -> Result<Self> {
+        Self::load_with(path, current_hostname().as_deref(), Some((file, text)))
+    }
+
+    fn create_default_config(path: &PathBuf) -> Result<()> {
+        let default_config = Self::default_config_text(&[])?;
         // Ensure parent directory exists (tests may set a temp XDG_CONFIG_HOME)
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create config dir: {}", parent.display()))?;
         }
 
-        fs::write(path, default_config)
+        fs::write(path, &default_config)
             .with_context(|| format!("Failed to write config: {}", path.display()))?;
 
         // Inform user that we created the config
@@ -767,6 +2165,10 @@ log_level = "info"         # error, warn, info, debug, trace
         );
         eprintln!();
         eprintln!("{}", "Next steps:".header());
+        eprintln!(
+            "  Run {} to add the audio outputs found on this machine",
+            "pwsw init".technical()
+        );
         eprintln!(
             "  Run {} for interactive setup (recommended)",
             "pwsw tui".technical()
@@ -789,6 +2191,34 @@ log_level = "info"         # error, warn, info, debug, trace
     pub fn print_summary(&self) {
         println!("{} {}\n", "✓".success(), "Configuration valid".success());
 
+        if let Some(host) = &self.host {
+            let mut applied = Vec::new();
+            if !host.settings.is_empty() {
+                applied.push(format!("settings: {}", host.settings.join(", ")));
+            }
+            if host.replace_sinks {
+                applied.push("replaces sinks".to_string());
+            }
+            if host.replace_rules {
+                applied.push("replaces rules".to_string());
+            }
+            let section = format!("[host.{}]", host.name);
+            if applied.is_empty() {
+                println!(
+                    "{} {}\n",
+                    "Host section:".header(),
+                    section.as_str().technical()
+                );
+            } else {
+                println!(
+                    "{} {} ({})\n",
+                    "Host section:".header(),
+                    section.as_str().technical(),
+                    applied.join("; ").dim()
+                );
+            }
+        }
+
         println!("{}", "Settings:".header());
         println!(
             "  {}: {}",
@@ -831,16 +2261,25 @@ log_level = "info"         # error, warn, info, debug, trace
             } else {
                 String::new()
             };
+            let host = sink
+                .host
+                .as_ref()
+                .map(|h| format!(" [host.{h}]").dim().to_string())
+                .unwrap_or_default();
             println!(
-                "  {}. {}{}",
+                "  {}. {}{}{}",
                 (i + 1).to_string().dim(),
                 sink.desc.as_str().bold(),
-                marker
+                marker,
+                host
             );
             println!("     {}: {}", "name".dim(), sink.name);
             if let Some(ref icon) = sink.icon {
                 println!("     {}: {}", "icon".dim(), icon.as_str().technical());
             }
+            if let Some(ref glyph) = sink.glyph {
+                println!("     {}: {}", "glyph".dim(), glyph);
+            }
         }
 
         if self.rules.is_empty() {
@@ -851,16 +2290,26 @@ log_level = "info"         # error, warn, info, debug, trace
                 "Rules".header(),
                 self.rules.len().to_string().technical()
             );
+            let lints = self.lint_rules();
             for (i, rule) in self.rules.iter().enumerate() {
+                let host = rule
+                    .host
+                    .as_ref()
+                    .map(|h| format!(" [host.{h}]").dim().to_string())
+                    .unwrap_or_default();
                 println!(
-                    "  {}. {}: {}",
+                    "  {}. {}: {}{}",
                     (i + 1).to_string().dim(),
                     "app_id".dim(),
-                    rule.app_id_pattern.as_str().technical()
+                    rule.app_id_pattern.as_str().technical(),
+                    host
                 );
                 if let Some(ref title) = rule.title_pattern {
                     println!("     {}: {}", "title".dim(), title.as_str().technical());
                 }
+                if let Some(lint) = lints[i] {
+                    println!("     {} {}", "⚠".warning(), lint.message().warning());
+                }
                 let effective_notify = rule.notify.unwrap_or(self.settings.notify_rules);
                 let source = if rule.notify.is_some() {
                     "override"
@@ -878,36 +2327,637 @@ log_level = "info"         # error, warn, info, debug, trace
             }
         }
 
-        if let Ok(path) = Self::get_config_path() {
-            println!("\n{} {}", "Config:".dim(), path.display());
+        if !self.hooks.is_empty() {
+            println!(
+                "\n{} ({}):",
+                "Hooks".header(),
+                self.hooks.len().to_string().technical()
+            );
+            for (i, hook) in self.hooks.iter().enumerate() {
+                println!(
+                    "  {}. {}: {}",
+                    (i + 1).to_string().dim(),
+                    "on".dim(),
+                    hook.on.name().technical()
+                );
+                println!("     {}: {}", "command".dim(), hook.command);
+            }
+        }
+
+        if let Ok(path) = Self::get_config_path() {
+            println!("\n{} {}", "Config:".dim(), path.display());
+        }
+    }
+
+    /// Resolve a sink reference (by position, desc, or name)
+    #[must_use]
+    pub fn resolve_sink(&self, sink_ref: &str) -> Option<&SinkConfig> {
+        // Try position first (1-indexed)
+        if let Ok(pos) = sink_ref.parse::<usize>() {
+            return if pos > 0 && pos <= self.sinks.len() {
+                Some(&self.sinks[pos - 1])
+            } else {
+                None
+            };
+        }
+        // Then try desc or name
+        self.sinks
+            .iter()
+            .find(|s| s.desc == sink_ref || s.name == sink_ref)
+    }
+
+    /// Get the configured default sink
+    ///
+    /// Returns None if no default sink is configured (which should never happen after
+    /// successful config validation, but defensive programming is good practice).
+    #[must_use]
+    pub fn get_default_sink(&self) -> Option<&SinkConfig> {
+        self.sinks.iter().find(|s| s.default)
+    }
+
+    /// Append a sink so that it is still the last one after saving
+    ///
+    /// It goes to the file of the current last sink when that is an included file or
+    /// `[host.<name>]` section (both saved after the main file's sinks), else the main file.
+    pub fn push_sink(&mut self, mut sink: SinkConfig) {
+        if let Some(last) = self.sinks.last() {
+            sink.source.clone_from(&last.source);
+            sink.host.clone_from(&last.host);
+        }
+        self.sinks.push(sink);
+    }
+
+    /// Append a rule so that it is still the last one after saving
+    ///
+    /// It goes to the included file of the current last rule, if any, else the main
+    /// file (host rules are always loaded before the main file's).
+    pub fn push_rule(&mut self, mut rule: Rule) {
+        if let Some(last) = self.rules.last() {
+            rule.source.clone_from(&last.source);
+        }
+        self.rules.push(rule);
+    }
+
+    /// Move the sink at `from` to position `to` (both 0-indexed)
+    ///
+    /// # Errors
+    /// Returns an error if the sink would pass sinks saved to another file or host
+    /// section, as the new order would not survive a save.
+    pub fn move_sink(&mut self, from: usize, to: usize) -> Result<()> {
+        let origins: Vec<_> = self.sinks.iter().map(|s| (&s.source, &s.host)).collect();
+        check_move(&origins, from, to, "sinks")?;
+        let sink = self.sinks.remove(from);
+        self.sinks.insert(to, sink);
+        Ok(())
+    }
+
+    /// Move the rule at `from` to position `to` (both 0-indexed)
+    ///
+    /// # Errors
+    /// Returns an error if the rule would pass rules saved to another file or host
+    /// section, as the new order would not survive a save.
+    pub fn move_rule(&mut self, from: usize, to: usize) -> Result<()> {
+        let origins: Vec<_> = self.rules.iter().map(|r| (&r.source, &r.host)).collect();
+        check_move(&origins, from, to, "rules")?;
+        let rule = self.rules.remove(from);
+        self.rules.insert(to, rule);
+        Ok(())
+    }
+
+    /// Set a `[settings]` or `[notifications]` key from command-line text
+    ///
+    /// `key` is `section.key`, or a bare key from either section. `value` is read as a
+    /// TOML value (`true`, `3000`, `["notify-send", "{body}"]`) when that fits the key's
+    /// type and as a plain string otherwise. An empty value resets the key to its default.
+    ///
+    /// # Errors
+    /// Returns an error if the key is unknown or the value doesn't fit its type.
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
+        let (section, name) = setting_key(key)?;
+
+        // Try the value as written first, so `true` and `3000` aren't stored as strings
+        let mut candidates = Vec::with_capacity(2);
+        if !value.is_empty() {
+            if let Ok(literal) = toml::from_str::<toml::Table>(&format!("value = {value}"))
+                && let Some(literal) = literal.get("value")
+            {
+                candidates.push(Some(literal.clone()));
+            }
+            candidates.push(Some(toml::Value::String(value.to_string())));
+        } else {
+            candidates.push(None);
+        }
+
+        let file = self.to_config_file();
+        let mut error = None;
+        for candidate in candidates {
+            let result = if section == "settings" {
+                with_key(&file.settings, name, candidate)
+                    .map(|s: SettingsFile| self.settings = s.into_settings())
+            } else {
+                with_key(&file.notifications, name, candidate)
+                    .map(|n: NotificationsFile| self.notifications = n.into_settings())
+            };
+            match result {
+                Ok(()) => return Ok(()),
+                Err(e) => error = error.or(Some(e)),
+            }
+        }
+        Err(error
+            .expect("at least one candidate value")
+            .wrap_err(format!("Invalid value '{value}' for {section}.{name}")))
+    }
+}
+
+/// `(section, key)` of a `[settings]` or `[notifications]` key, which may be given
+/// without its section
+fn setting_key(key: &str) -> Result<(&str, &str)> {
+    let (section, name) = match key.split_once('.') {
+        Some(parts) => parts,
+        None if key_description("settings", key).is_some() => ("settings", key),
+        None => ("notifications", key),
+    };
+    if !matches!(section, "settings" | "notifications") || key_description(section, name).is_none()
+    {
+        eyre::bail!(
+            "Unknown setting '{key}'. See 'pwsw config schema' for the keys of [settings] and [notifications]."
+        );
+    }
+    Ok((section, name))
+}
+
+/// `current` with `key` replaced by `value` (or reset to its default when `None`)
+fn with_key<T: DeserializeOwned + Serialize>(
+    current: &T,
+    key: &str,
+    value: Option<toml::Value>,
+) -> Result<T> {
+    let mut table = toml::Table::try_from(current).context("Failed to serialize config")?;
+    match value {
+        Some(value) => table.insert(key.to_string(), value),
+        None => table.remove(key),
+    };
+    toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| eyre::eyre!("{}", e.message()))
+}
+
+/// Long description of a config key, by section (`""` for top-level keys)
+///
+/// Shared by the TUI settings screen and `pwsw config schema`.
+#[must_use]
+pub fn key_description(section: &str, key: &str) -> Option<&'static str> {
+    let description = match (section, key) {
+        ("", "version") => {
+            "Config layout version. Files without it are version 0; upgrade them with\n\
+             'pwsw config migrate'."
+        }
+        ("", "include") => {
+            "More config files to merge after this one, as paths or globs relative to\n\
+             this file's directory (e.g., 'conf.d/*.toml')."
+        }
+        ("", "settings") => "Global daemon behavior.",
+        ("", "notifications") => "What notifications look like and which events show one.",
+        ("", "sinks") => "Audio outputs to switch between. Exactly one must be the default.",
+        ("", "rules") => {
+            "Window matching rules. The first rule matching a window decides its sink."
+        }
+        ("", "hooks") => "Shell commands run by the daemon on events.",
+        ("", "host") => {
+            "Overrides applied only on the machine whose hostname matches the section\n\
+             name (e.g., [host.laptop])."
+        }
+        ("settings", "default_on_startup") => {
+            "Automatically switches to the configured default sink when the daemon starts.\n\
+             \n\
+             When enabled: Daemon activates default sink on startup.\n\
+             When disabled: Leaves the currently active sink unchanged.\n\
+             \n\
+             Useful for ensuring a consistent audio output when the daemon starts.\n\
+             \n\
+             Default: disabled"
+        }
+        ("settings", "set_smart_toggle") => {
+            "Intelligent toggling behavior for manual sink switches via CLI.\n\
+             \n\
+             When enabled: Running 'pwsw set-sink <name>' toggles back to default\n\
+             if the sink is already active.\n\
+             When disabled: Always switches to the specified sink, even if already active.\n\
+             \n\
+             Example: If headphones are active:\n\
+             • Enabled: 'pwsw set-sink headphones' → switches to default sink\n\
+             • Disabled: 'pwsw set-sink headphones' → stays on headphones\n\
+             \n\
+             Default: disabled"
+        }
+        ("settings", "notify_manual") => {
+            "Desktop notifications for manual sink switches and daemon lifecycle events.\n\
+             \n\
+             When enabled: Shows notifications for:\n\
+             • Manual sink switches: 'pwsw set-sink <name>'\n\
+             • Cycling commands: 'pwsw prev-sink' and 'pwsw next-sink'\n\
+             • Daemon lifecycle: start and stop events\n\
+             \n\
+             When disabled: All manual operations happen silently.\n\
+             \n\
+             Requires a notification daemon (e.g., dunst, mako) to be running.\n\
+             \n\
+             Default: enabled"
+        }
+        ("settings", "notify_rules") => {
+            "Desktop notifications for automatic rule-triggered sink switches.\n\
+             \n\
+             When enabled: Shows notification when daemon switches sink due to a\n\
+             window matching a rule.\n\
+             When disabled: Rule-based switches happen silently.\n\
+             \n\
+             Useful for debugging rules or understanding why switches occur.\n\
+             \n\
+             Default: enabled"
+        }
+        ("settings", "match_by_index") => {
+            "Rule priority strategy when multiple windows match different rules.\n\
+             \n\
+             When enabled: Uses rule priority - higher priority rules always win.\n\
+             Rules at the top of the list have higher priority than those below.\n\
+             When disabled: Most recently opened matching window determines active sink.\n\
+             \n\
+             Example: Firefox (rule 1, higher priority) and Discord (rule 2, lower priority):\n\
+             • Enabled: Firefox's sink stays active regardless of which window opened last\n\
+             • Disabled: Whichever window you focused most recently determines the sink\n\
+             \n\
+             Tip: Reorder rules in the Rules tab (arrow keys + Shift+Up/Down) to adjust priority.\n\
+             \n\
+             Default: disabled (most recent window)"
+        }
+        ("settings", "log_level") => {
+            "Logging verbosity level for daemon output.\n\
+             \n\
+             Levels (from least to most verbose):\n\
+             • error: Only critical errors\n\
+             • warn: Warnings and errors\n\
+             • info: General information (recommended)\n\
+             • debug: Detailed debugging information\n\
+             • trace: Very verbose tracing (for development)\n\
+             \n\
+             View logs with: journalctl --user -u pwsw -f\n\
+             Or in TUI: Dashboard → [l] for logs view\n\
+             \n\
+             Default: info"
+        }
+        ("notifications", "switch") => {
+            "Notifications for sink switches, manual or rule-triggered.\n\
+             \n\
+             When enabled: Switches notify as configured by the manual and\n\
+             rule-based notification settings (and per-rule 'notify').\n\
+             When disabled: No switch ever shows a notification.\n\
+             \n\
+             Default: enabled"
+        }
+        ("notifications", "reload") => {
+            "Notification after the config file is reloaded successfully.\n\
+             \n\
+             When enabled: Shows 'Configuration Reloaded' after hot-reload or 'pwsw reload'.\n\
+             When disabled: Reloads happen silently.\n\
+             \n\
+             Only shown when Manual Switch Notifications are enabled.\n\
+             \n\
+             Default: enabled"
+        }
+        ("notifications", "start_stop") => {
+            "Notifications when the daemon starts and stops.\n\
+             \n\
+             When enabled: Shows 'PWSW Started' and 'PWSW Stopped'.\n\
+             When disabled: The daemon starts and stops silently.\n\
+             \n\
+             Only shown when Manual Switch Notifications are enabled.\n\
+             \n\
+             Default: enabled"
+        }
+        ("notifications", "errors") => {
+            "Notifications for errors the daemon recovers from.\n\
+             \n\
+             When enabled: Shows 'Reload Failed' or 'Reload Rejected' when a new\n\
+             config cannot be applied (the previous config stays active).\n\
+             When disabled: Errors are only logged.\n\
+             \n\
+             Only shown when Manual Switch Notifications are enabled.\n\
+             \n\
+             Default: enabled"
+        }
+        ("notifications", "summary") => {
+            "Title of switch notifications.\n\
+             \n\
+             Placeholders:\n\
+             • {sink_desc}, {sink_name}: the new sink\n\
+             • {prev_sink_desc}: the sink switched away from\n\
+             • {reason}: rule description or window change (empty for manual switches)\n\
+             • {app_id}: window that triggered a rule switch\n\
+             \n\
+             Rules can override this with 'notify_summary'.\n\
+             \n\
+             Default: Audio Output"
+        }
+        ("notifications", "body") => {
+            "Body of notifications for automatic switches.\n\
+             \n\
+             Uses the same placeholders as the title.\n\
+             Example: '{prev_sink_desc} → {sink_desc}'\n\
+             \n\
+             Rules can override this with 'notify_body'. Rapid switches are\n\
+             combined into one notification showing the chain of sinks.\n\
+             \n\
+             Default: {sink_desc} → {reason}"
+        }
+        ("notifications", "manual_body") => {
+            "Body of notifications for manual switches ('pwsw set-sink', cycling).\n\
+             \n\
+             Uses the same placeholders as the title ({reason} is empty).\n\
+             \n\
+             Default: {sink_desc}"
+        }
+        ("notifications", "urgency") => {
+            "Urgency hint sent with every notification.\n\
+             \n\
+             Levels:\n\
+             • low: may be shown less prominently\n\
+             • normal: regular notification\n\
+             • critical: many servers keep these until dismissed\n\
+             \n\
+             Rules can override this with 'notify_urgency'.\n\
+             \n\
+             Default: normal"
+        }
+        ("notifications", "timeout_ms") => {
+            "Milliseconds before a notification expires.\n\
+             \n\
+             0 keeps notifications until dismissed. Notifications with Undo /\n\
+             Keep here buttons stay at least 8 seconds.\n\
+             \n\
+             Default: 3000"
+        }
+        ("notifications", "sound") => {
+            "Sound played with notifications, as a freedesktop sound theme name\n\
+             (e.g., 'message-new-instant').\n\
+             \n\
+             Only honored by notification servers that play sounds.\n\
+             \n\
+             Default: none"
+        }
+        ("notifications", "command") => {
+            "Program and arguments run for every notification instead of showing a\n\
+             desktop notification, without a shell.\n\
+             \n\
+             Placeholders: {summary} {body} {icon} {urgency} {timeout_ms}\n\
+             \n\
+             Default: none (desktop notifications)"
+        }
+        ("sinks", "name") => "PipeWire node name, as shown by 'pwsw list-sinks'.",
+        ("sinks", "desc") => "Human-readable name, used by rules and 'pwsw set-sink'.",
+        ("sinks", "icon") => {
+            "Icon name for notifications and status bars (auto-detected if unset)."
+        }
+        ("sinks", "glyph") => "Status-bar glyph for '--format' output (falls back to icon).",
+        ("sinks", "default") => "Fallback sink when no rule matches. Exactly one sink sets this.",
+        ("rules", "app_id") => "Regex matched against the window's app_id.",
+        ("rules", "title") => "Regex matched against the window title (optional).",
+        ("rules", "sink") => "Target sink, by desc, node name or 1-based position.",
+        ("rules", "desc") => "Description shown in notifications as {reason}.",
+        ("rules", "notify") => {
+            "Show a notification for this rule (overrides settings.notify_rules)."
+        }
+        ("rules", "notify_summary") => {
+            "Notification title template for switches caused by this rule."
+        }
+        ("rules", "notify_body") => "Notification body template for switches caused by this rule.",
+        ("rules", "notify_urgency") => "Notification urgency for switches caused by this rule.",
+        ("hooks", "on") => "Event that runs the hook.",
+        ("hooks", "command") => {
+            "Shell command, run with 'sh -c'. Event details are in PWSW_* variables."
+        }
+        ("hooks", "timeout_secs") => {
+            "Kill the command if it is still running after this many seconds."
+        }
+        ("host", "settings") => "[settings] keys overridden on this machine.",
+        ("host", "replace_sinks") => "Use only this section's sinks instead of appending them.",
+        ("host", "replace_rules") => {
+            "Use only this section's rules instead of checking them first."
+        }
+        ("host", "sinks") => "Sinks added on this machine, after the shared ones.",
+        ("host", "rules") => "Rules added on this machine, checked before the shared ones.",
+        _ => return None,
+    };
+    Some(description)
+}
+
+// ============================================================================
+// JSON Schema
+// ============================================================================
+
+/// Valid `log_level` values
+const LOG_LEVELS: &[&str] = &["error", "warn", "info", "debug", "trace"];
+
+/// JSON Schema of the config file, for editor completion and validation
+///
+/// Properties are generated from sample values of the `*File` structs, so every key
+/// the loader reads is listed with its type and default, and unknown keys (typos) are
+/// flagged. Descriptions come from [`key_description`].
+///
+/// # Errors
+/// Returns an error if a sample cannot be serialized (should never happen).
+pub fn json_schema() -> Result<serde_json::Value> {
+    let settings = table_schema(
+        &SettingsFile::default(),
+        &serde_json::to_value(SettingsFile::default())?,
+        "settings",
+        &[],
+    )?;
+    let settings = with_enum(settings, "log_level", LOG_LEVELS);
+
+    let urgencies = [
+        NotificationUrgency::Low,
+        NotificationUrgency::Normal,
+        NotificationUrgency::Critical,
+    ]
+    .map(NotificationUrgency::name);
+    let notifications = table_schema(
+        &NotificationsFile {
+            sound: Some(String::new()),
+            command: Some(vec![String::new()]),
+            ..NotificationsFile::default()
+        },
+        &serde_json::to_value(NotificationsFile::default())?,
+        "notifications",
+        &[],
+    )?;
+    let notifications = with_enum(notifications, "urgency", &urgencies);
+
+    let sink = table_schema(
+        &SinkConfigFile {
+            name: String::new(),
+            desc: String::new(),
+            icon: Some(String::new()),
+            glyph: Some(String::new()),
+            default: false,
+        },
+        &serde_json::json!({ "default": false }),
+        "sinks",
+        &["name", "desc"],
+    )?;
+
+    let rule = table_schema(
+        &RuleConfigFile {
+            app_id: Regex::new("").context("Invalid sample regex")?,
+            title: Some(Regex::new("").context("Invalid sample regex")?),
+            sink: String::new(),
+            desc: Some(String::new()),
+            notify: Some(true),
+            notify_summary: Some(String::new()),
+            notify_body: Some(String::new()),
+            notify_urgency: Some(NotificationUrgency::Normal),
+        },
+        &serde_json::Value::Null,
+        "rules",
+        &["app_id", "sink"],
+    )?;
+    let rule = with_enum(rule, "notify_urgency", &urgencies);
+
+    let hook_sample = HookConfigFile {
+        on: HookEvent::Switch,
+        command: String::new(),
+        timeout_secs: default_hook_timeout_secs(),
+    };
+    let hook = table_schema(
+        &hook_sample,
+        &serde_json::json!({ "timeout_secs": default_hook_timeout_secs() }),
+        "hooks",
+        &["on", "command"],
+    )?;
+    let events = [
+        HookEvent::Switch,
+        HookEvent::WindowTracked,
+        HookEvent::DaemonStart,
+        HookEvent::ReloadFailed,
+    ]
+    .map(HookEvent::name);
+    let hook = with_enum(hook, "on", &events);
+
+    let array_of = |item: &serde_json::Value| serde_json::json!({ "type": "array", "items": item });
+
+    let mut host = table_schema(
+        &HostConfigFile::default(),
+        &serde_json::to_value(HostConfigFile::default())?,
+        "host",
+        &[],
+    )?;
+    host["properties"]["settings"] = with_description(settings.clone(), "host", "settings");
+    host["properties"]["sinks"] = with_description(array_of(&sink), "host", "sinks");
+    host["properties"]["rules"] = with_description(array_of(&rule), "host", "rules");
+
+    let mut schema = table_schema(
+        &ConfigFile {
+            version: CONFIG_VERSION,
+            include: vec![String::new()],
+            settings: SettingsFile::default(),
+            notifications: NotificationsFile::default(),
+            sinks: Vec::new(),
+            rules: Vec::new(),
+            hooks: vec![hook_sample],
+        },
+        &serde_json::Value::Null,
+        "",
+        &[],
+    )?;
+    let properties = &mut schema["properties"];
+    properties["version"]["maximum"] = CONFIG_VERSION.into();
+    for (key, value) in [
+        ("settings", settings),
+        ("notifications", notifications),
+        ("sinks", array_of(&sink)),
+        ("rules", array_of(&rule)),
+        ("hooks", array_of(&hook)),
+        (
+            "host",
+            serde_json::json!({ "type": "object", "additionalProperties": host }),
+        ),
+    ] {
+        properties[key] = with_description(value, "", key);
+    }
+
+    schema["$schema"] = "http://json-schema.org/draft-07/schema#".into();
+    schema["title"] = "pwsw config".into();
+    schema["description"] = "PWSW (PipeWire Switcher) configuration file".into();
+    Ok(schema)
+}
+
+/// Object schema with one property per serialized field of `sample`
+fn table_schema(
+    sample: &impl Serialize,
+    defaults: &serde_json::Value,
+    section: &str,
+    required: &[&str],
+) -> Result<serde_json::Value> {
+    let serde_json::Value::Object(fields) = serde_json::to_value(sample)? else {
+        eyre::bail!("Schema sample for [{section}] is not a table");
+    };
+
+    let mut properties = serde_json::Map::new();
+    for (key, value) in fields {
+        let mut property = value_schema(&value);
+        if let Some(default) = defaults.get(&key) {
+            property["default"] = default.clone();
         }
+        properties.insert(key.clone(), with_description(property, section, &key));
     }
 
-    /// Resolve a sink reference (by position, desc, or name)
-    #[must_use]
-    pub fn resolve_sink(&self, sink_ref: &str) -> Option<&SinkConfig> {
-        // Try position first (1-indexed)
-        if let Ok(pos) = sink_ref.parse::<usize>() {
-            return if pos > 0 && pos <= self.sinks.len() {
-                Some(&self.sinks[pos - 1])
-            } else {
-                None
-            };
+    let mut schema = serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "additionalProperties": false,
+    });
+    if !required.is_empty() {
+        schema["required"] = required.into();
+    }
+    Ok(schema)
+}
+
+/// Schema for the type of a sample value
+fn value_schema(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Bool(_) => serde_json::json!({ "type": "boolean" }),
+        serde_json::Value::Number(n) if n.is_u64() => {
+            serde_json::json!({ "type": "integer", "minimum": 0 })
         }
-        // Then try desc or name
-        self.sinks
-            .iter()
-            .find(|s| s.desc == sink_ref || s.name == sink_ref)
+        serde_json::Value::Number(n) if n.is_i64() => serde_json::json!({ "type": "integer" }),
+        serde_json::Value::Number(_) => serde_json::json!({ "type": "number" }),
+        serde_json::Value::String(_) => serde_json::json!({ "type": "string" }),
+        serde_json::Value::Array(items) => serde_json::json!({
+            "type": "array",
+            "items": items.first().map_or_else(|| serde_json::json!({}), value_schema),
+        }),
+        serde_json::Value::Object(_) => serde_json::json!({ "type": "object" }),
+        serde_json::Value::Null => serde_json::json!({}),
     }
+}
 
-    /// Get the configured default sink
-    ///
-    /// Returns None if no default sink is configured (which should never happen after
-    /// successful config validation, but defensive programming is good practice).
-    #[must_use]
-    pub fn get_default_sink(&self) -> Option<&SinkConfig> {
-        self.sinks.iter().find(|s| s.default)
+fn with_description(mut schema: serde_json::Value, section: &str, key: &str) -> serde_json::Value {
+    if let Some(description) = key_description(section, key) {
+        schema["description"] = description.into();
     }
+    schema
+}
+
+fn with_enum(mut schema: serde_json::Value, key: &str, values: &[&str]) -> serde_json::Value {
+    schema["properties"][key]["enum"] = values.into();
+    schema
+}
+
+/// Check a notification template for unknown placeholders and unbalanced braces
+///
+/// # Errors
+/// Returns an error describing the first problem in the template.
+pub fn validate_notification_template(template: &str) -> Result<()> {
+    crate::template::Template::parse(template, NOTIFICATION_PLACEHOLDERS).map(|_| ())
 }
 
 #[cfg(test)]
@@ -1247,4 +3297,882 @@ mod tests {
         assert!(err.contains("dangerous"));
         assert!(err.contains("title"));
     }
+
+    #[test]
+    fn test_hooks_parse_with_default_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            r#"
+[[sinks]]
+name = "sink1"
+desc = "Sink 1"
+default = true
+
+[[hooks]]
+on = "switch"
+command = "eq-preset $PWSW_SINK_NAME"
+
+[[hooks]]
+on = "reload_failed"
+command = "notify-me"
+timeout_secs = 2
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_path(&path).unwrap();
+        assert_eq!(
+            config.hooks,
+            vec![
+                Hook {
+                    on: HookEvent::Switch,
+                    command: "eq-preset $PWSW_SINK_NAME".to_string(),
+                    timeout_secs: 10,
+                    source: None,
+                },
+                Hook {
+                    on: HookEvent::ReloadFailed,
+                    command: "notify-me".to_string(),
+                    timeout_secs: 2,
+                    source: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notifications_parse_with_rule_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            r#"
+[notifications]
+body = "{prev_sink_desc} → {sink_desc}"
+timeout_ms = 0
+urgency = "low"
+start_stop = false
+
+[[sinks]]
+name = "sink1"
+desc = "Sink 1"
+default = true
+
+[[rules]]
+app_id = "steam"
+sink = "sink1"
+notify_summary = "{app_id}"
+notify_urgency = "critical"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_path(&path).unwrap();
+        let n = &config.notifications;
+        assert_eq!(n.summary, "Audio Output");
+        assert_eq!(n.body, "{prev_sink_desc} → {sink_desc}");
+        assert_eq!(n.timeout_ms, 0);
+        assert_eq!(n.urgency, NotificationUrgency::Low);
+        assert!(n.switch);
+        assert!(!n.start_stop);
+
+        let rule = &config.rules[0];
+        assert_eq!(rule.notify_summary.as_deref(), Some("{app_id}"));
+        assert_eq!(rule.notify_body, None);
+        assert_eq!(rule.notify_urgency, Some(NotificationUrgency::Critical));
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_notification_placeholder() {
+        let mut config = make_config(
+            vec![make_sink("sink1", "Sink 1", true)],
+            vec![make_rule("steam", None, "sink1")],
+        );
+        config.rules[0].notify_body = Some("{window_title}".to_string());
+        let err = format!("{:#}", config.validate().unwrap_err());
+        assert!(err.contains("Rule 1: invalid notify_body"));
+
+        config.rules[0].notify_body = None;
+        config.notifications.summary = "{sink".to_string();
+        let err = format!("{:#}", config.validate().unwrap_err());
+        assert!(err.contains("notifications.summary"));
+    }
+
+    #[test]
+    fn test_notification_command_parse_and_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            r#"
+[notifications]
+command = ["my-notify", "{summary}", "{body}"]
+
+[[sinks]]
+name = "sink1"
+desc = "Sink 1"
+default = true
+"#,
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        assert_eq!(
+            config.notifications.command,
+            Some(vec![
+                "my-notify".to_string(),
+                "{summary}".to_string(),
+                "{body}".to_string(),
+            ])
+        );
+
+        config.notifications.command = Some(vec!["my-notify".to_string(), "{sink}".to_string()]);
+        let err = format!("{:#}", config.validate().unwrap_err());
+        assert!(err.contains("notifications.command"));
+
+        config.notifications.command = Some(vec![]);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_includes_merge_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::create_dir(dir.path().join("conf.d")).unwrap();
+        fs::write(
+            &path,
+            r#"
+include = ["conf.d/*.toml", "rules-gaming.toml"]
+
+[settings]
+log_level = "warn"
+notify_rules = false
+
+[[sinks]]
+name = "speakers"
+desc = "Speakers"
+default = true
+
+[[rules]]
+app_id = "mpv"
+sink = "Speakers"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("conf.d/20-later.toml"),
+            "[settings]\nlog_level = \"debug\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("conf.d/10-sinks.toml"),
+            "[settings]\nlog_level = \"error\"\n\n[[sinks]]\nname = \"hdmi\"\ndesc = \"HDMI\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("rules-gaming.toml"),
+            "[[rules]]\napp_id = \"steam\"\nsink = \"HDMI\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load_from_path(&path).unwrap();
+        // Later files override individual keys; untouched keys keep the main file's value
+        assert_eq!(config.settings.log_level, "debug");
+        assert!(!config.settings.notify_rules);
+        let sinks: Vec<_> = config.sinks.iter().map(|s| s.desc.as_str()).collect();
+        assert_eq!(sinks, ["Speakers", "HDMI"]);
+        let rules: Vec<_> = config
+            .rules
+            .iter()
+            .map(|r| r.app_id_pattern.as_str())
+            .collect();
+        assert_eq!(rules, ["mpv", "steam"]);
+        assert_eq!(config.rules[0].source, None);
+        assert_eq!(
+            config.rules[1].source.as_deref(),
+            Some(dir.path().join("rules-gaming.toml").as_path())
+        );
+        assert_eq!(config.source_files(&path).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_include_errors_name_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "include = [\"rules.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("rules.toml"),
+            "[[rules]]\napp_id = \"steam\"\nsink = \"Nowhere\"\n",
+        )
+        .unwrap();
+
+        let err = format!("{:#}", Config::load_from_path(&path).unwrap_err());
+        assert!(err.contains("rules.toml"), "{err}");
+        assert!(err.contains("unknown sink 'Nowhere'"), "{err}");
+
+        fs::remove_file(dir.path().join("rules.toml")).unwrap();
+        let err = format!("{:#}", Config::load_from_path(&path).unwrap_err());
+        assert!(err.contains("Included config not found"), "{err}");
+    }
+
+    #[test]
+    fn test_save_writes_included_items_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let rules_path = dir.path().join("rules.toml");
+        fs::write(
+            &path,
+            "include = [\"rules.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+        )
+        .unwrap();
+        fs::write(
+            &rules_path,
+            "[settings]\nlog_level = \"debug\"\n\n[[rules]]\napp_id = \"steam\"\nsink = \"S\"\n",
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        config.rules[0].desc = Some("Gaming".to_string());
+        config.rules.insert(0, make_rule("vlc", None, "S"));
+        config.push_rule(make_rule("mpv", None, "S"));
+        config.save_to(&path).unwrap();
+
+        let main = fs::read_to_string(&path).unwrap();
+        assert!(main.contains("rules.toml"));
+        assert!(main.contains("vlc"));
+        assert!(!main.contains("steam"));
+        let included = fs::read_to_string(&rules_path).unwrap();
+        assert!(included.contains("Gaming"));
+        assert!(included.contains("log_level"));
+        assert!(included.contains("mpv"));
+
+        // The order survives a reload
+        let reloaded = Config::load_from_path(&path).unwrap();
+        let patterns: Vec<_> = reloaded.rules.iter().map(|r| &r.app_id_pattern).collect();
+        assert_eq!(patterns, ["vlc", "steam", "mpv"]);
+        assert_eq!(reloaded.rules[1].desc.as_deref(), Some("Gaming"));
+    }
+
+    #[test]
+    fn test_instance_id_is_stable_per_path() {
+        assert_eq!(instance_id_for(Path::new("")), "cbf29ce484222325");
+        let id = instance_id_for(Path::new("/home/me/scratch.toml"));
+        assert_eq!(id.len(), 16);
+        assert_eq!(id, instance_id_for(Path::new("/home/me/scratch.toml")));
+        assert_ne!(id, instance_id_for(Path::new("/home/me/other.toml")));
+    }
+
+    #[test]
+    fn test_save_keeps_settings_in_the_include_that_overrides_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let extra_path = dir.path().join("extra.toml");
+        let main = "include = [\"extra.toml\"]\n\n[settings]\nlog_level = \"info\"\n\n[[sinks]]\nname = \"a\"\ndesc = \"A\"\ndefault = true\n";
+        fs::write(&path, main).unwrap();
+        let extra = "[settings]\nlog_level = \"debug\"\n\n[notifications]\ntimeout_ms = 1234\n";
+        fs::write(&extra_path, extra).unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        assert_eq!(config.settings.log_level, "debug");
+        config.push_sink(make_sink("b", "B", false));
+        config.save_to(&path).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("log_level = \"info\""), "{saved}");
+        assert!(!saved.contains("[notifications]"), "{saved}");
+        assert_eq!(fs::read_to_string(&extra_path).unwrap(), extra);
+
+        // Editing an overridden key changes the include, so the edit takes effect
+        config.set_value("log_level", "warn").unwrap();
+        config.set_value("match_by_index", "true").unwrap();
+        config.save_to(&path).unwrap();
+        assert!(
+            fs::read_to_string(&extra_path)
+                .unwrap()
+                .contains("log_level = \"warn\"")
+        );
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("log_level = \"info\""), "{saved}");
+        assert!(saved.contains("match_by_index = true"), "{saved}");
+
+        let reloaded = Config::load_from_path(&path).unwrap();
+        assert_eq!(reloaded.settings.log_level, "warn");
+        assert!(reloaded.settings.match_by_index);
+        assert_eq!(reloaded.notifications.timeout_ms, 1234);
+        assert_eq!(reloaded.sinks.len(), 2);
+    }
+
+    #[test]
+    fn test_save_empties_included_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let rules_path = dir.path().join("rules.toml");
+        fs::write(
+            &path,
+            "include = [\"rules.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+        )
+        .unwrap();
+        fs::write(&rules_path, "[[rules]]\napp_id = \"steam\"\nsink = \"S\"\n").unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        config.rules.clear();
+        config.save_to(&path).unwrap();
+
+        assert!(!fs::read_to_string(&rules_path).unwrap().contains("steam"));
+        assert!(Config::load_from_path(&path).unwrap().rules.is_empty());
+    }
+
+    #[test]
+    fn test_save_refuses_to_reorder_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "include = [\"rules.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n\n[[rules]]\napp_id = \"vlc\"\nsink = \"S\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("rules.toml"),
+            "[[rules]]\napp_id = \"steam\"\nsink = \"S\"\n",
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_path(&path).unwrap();
+        let err = config.move_rule(0, 1).unwrap_err();
+        assert!(err.to_string().contains("rules.toml"), "{err}");
+        let patterns: Vec<_> = config.rules.iter().map(|r| &r.app_id_pattern).collect();
+        assert_eq!(patterns, ["vlc", "steam"]);
+
+        // A main-file rule after an included one would come back first
+        config.rules.push(make_rule("mpv", None, "S"));
+        let before = fs::read_to_string(&path).unwrap();
+        assert!(config.save_to(&path).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+    }
+
+    const HOSTS_CONFIG: &str = r#"
+[settings]
+log_level = "warn"
+
+[[sinks]]
+name = "speakers"
+desc = "Speakers"
+default = true
+
+[[rules]]
+app_id = "mpv"
+sink = "Speakers"
+
+[host.desktop.settings]
+log_level = "debug"
+
+[[host.desktop.sinks]]
+name = "hdmi"
+desc = "HDMI"
+
+[[host.desktop.rules]]
+app_id = "steam"
+sink = "HDMI"
+
+[host.laptop]
+replace_sinks = true
+replace_rules = true
+
+[[host.laptop.sinks]]
+name = "headphones"
+desc = "Headphones"
+default = true
+"#;
+
+    #[test]
+    fn test_host_section_applies_on_matching_host() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, HOSTS_CONFIG).unwrap();
+
+        let desktop = Config::load_for_host(&path, Some("desktop")).unwrap();
+        assert_eq!(desktop.settings.log_level, "debug");
+        let sinks: Vec<_> = desktop.sinks.iter().map(|s| s.desc.as_str()).collect();
+        assert_eq!(sinks, ["Speakers", "HDMI"]);
+        // Host rules are checked before the shared ones
+        let rules: Vec<_> = desktop
+            .rules
+            .iter()
+            .map(|r| r.app_id_pattern.as_str())
+            .collect();
+        assert_eq!(rules, ["steam", "mpv"]);
+        assert_eq!(desktop.rules[0].host.as_deref(), Some("desktop"));
+        assert_eq!(desktop.rules[1].host, None);
+        let host = desktop.host.unwrap();
+        assert_eq!(host.name, "desktop");
+        assert_eq!(host.settings, ["log_level"]);
+
+        // A fully qualified hostname matches its short name
+        let laptop = Config::load_for_host(&path, Some("laptop.example.com")).unwrap();
+        assert_eq!(laptop.settings.log_level, "warn");
+        assert_eq!(laptop.sinks.len(), 1);
+        assert_eq!(laptop.sinks[0].desc, "Headphones");
+        assert!(laptop.rules.is_empty());
+
+        let other = Config::load_for_host(&path, Some("server")).unwrap();
+        assert_eq!(other.host, None);
+        assert_eq!(other.sinks.len(), 1);
+        assert_eq!(other.rules.len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_host_section_is_rejected_on_any_host() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            format!("{HOSTS_CONFIG}\n[host.laptop.extra]\nkey = 1\n"),
+        )
+        .unwrap();
+
+        let err = format!(
+            "{:#}",
+            Config::load_for_host(&path, Some("desktop")).unwrap_err()
+        );
+        assert!(err.contains("[host.laptop]"), "{err}");
+    }
+
+    #[test]
+    fn test_save_writes_host_section_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, HOSTS_CONFIG).unwrap();
+
+        let mut config = Config::load_for_host(&path, Some("desktop")).unwrap();
+        config.settings.log_level = "trace".to_string();
+        config.settings.notify_rules = false;
+        config.rules[0].desc = Some("Gaming".to_string());
+        config.save_to(&path).unwrap();
+
+        let table: toml::Table = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        // Overridden keys go to the host section, shared ones stay shared
+        assert_eq!(table["settings"]["log_level"].as_str(), Some("warn"));
+        assert_eq!(table["settings"]["notify_rules"].as_bool(), Some(false));
+        let desktop = &table["host"]["desktop"];
+        assert_eq!(desktop["settings"]["log_level"].as_str(), Some("trace"));
+        assert_eq!(desktop["rules"][0]["desc"].as_str(), Some("Gaming"));
+        assert_eq!(table["rules"].as_array().unwrap().len(), 1);
+
+        let reloaded = Config::load_for_host(&path, Some("desktop")).unwrap();
+        assert_eq!(reloaded, config);
+        let laptop = Config::load_for_host(&path, Some("laptop")).unwrap();
+        assert_eq!(laptop.sinks[0].desc, "Headphones");
+        assert_eq!(laptop.settings.log_level, "warn");
+    }
+
+    #[test]
+    fn test_save_with_replaced_sinks_keeps_shared_sinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, HOSTS_CONFIG).unwrap();
+
+        let mut config = Config::load_for_host(&path, Some("laptop")).unwrap();
+        config.sinks.push(make_sink("usb", "USB DAC", false));
+        config.save_to(&path).unwrap();
+
+        let other = Config::load_for_host(&path, Some("server")).unwrap();
+        let sinks: Vec<_> = other.sinks.iter().map(|s| s.desc.as_str()).collect();
+        assert_eq!(sinks, ["Speakers"]);
+        let laptop = Config::load_for_host(&path, Some("laptop")).unwrap();
+        let sinks: Vec<_> = laptop.sinks.iter().map(|s| s.desc.as_str()).collect();
+        assert_eq!(sinks, ["Headphones", "USB DAC"]);
+    }
+
+    #[test]
+    fn test_save_preserves_comments_and_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let original = r#"# Synced between machines
+experimental = true
+
+[settings]
+log_level = "info" # raise when debugging
+
+[[sinks]]
+name = "speakers"
+desc = "Speakers"
+default = true
+
+# Video players
+[[rules]]
+app_id = "mpv"
+sink = "Speakers"
+"#;
+        fs::write(&path, original).unwrap();
+
+        let mut config = Config::load_for_host(&path, None).unwrap();
+        config.settings.log_level = "debug".to_string();
+        config.rules.push(make_rule("steam", None, "Speakers"));
+        config.save_to(&path).unwrap();
+
+        let saved = fs::read_to_string(&path).unwrap();
+        let expected = original.replace("\"info\"", "\"debug\"")
+            + "\n[[rules]]\napp_id = \"steam\"\nsink = \"Speakers\"\n";
+        assert_eq!(saved, expected);
+
+        // Saving again without changes leaves the file as it is
+        let reloaded = Config::load_for_host(&path, None).unwrap();
+        reloaded.save_to(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unversioned_config_is_migrated_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let rules_path = dir.path().join("rules.toml");
+        fs::write(
+            &path,
+            "# Old config\ninclude = [\"rules.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+        )
+        .unwrap();
+        fs::write(&rules_path, "[[rules]]\napp_id = \"mpv\"\nsink = \"S\"\n").unwrap();
+
+        // Loading works without touching the files
+        Config::load_for_host(&path, None).unwrap();
+        assert!(!fs::read_to_string(&path).unwrap().contains("version"));
+
+        let migrated = Config::migrate(&path).unwrap();
+        assert_eq!(migrated, [(path.clone(), 0), (rules_path.clone(), 0)]);
+        let main = fs::read_to_string(&path).unwrap();
+        assert!(main.contains("# Old config"));
+        assert!(main.contains(&format!("version = {CONFIG_VERSION}")));
+        Config::load_for_host(&path, None).unwrap();
+
+        assert!(Config::migrate(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_newer_config_version_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, format!("version = {}\n", CONFIG_VERSION + 1)).unwrap();
+
+        let err = format!("{:#}", Config::load_for_host(&path, None).unwrap_err());
+        assert!(err.contains("newer than this pwsw supports"), "{err}");
+        assert!(Config::migrate(&path).is_err());
+    }
+
+    #[test]
+    fn test_default_config_is_current_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        Config::create_default_config(&path).unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains(&format!("\nversion = {CONFIG_VERSION} ")));
+        assert!(Config::migrate(&path).unwrap().is_empty());
+    }
+
+    /// Assert every key of `value` is described by `schema`
+    fn assert_keys_in_schema(path: &str, value: &toml::Value, schema: &serde_json::Value) {
+        match value {
+            toml::Value::Table(table) => {
+                for (key, value) in table {
+                    let path = format!("{path}.{key}");
+                    let property = schema["properties"]
+                        .get(key)
+                        .or_else(|| schema.get("additionalProperties").filter(|a| a.is_object()))
+                        .unwrap_or_else(|| panic!("{path} is not in the schema"));
+                    assert_keys_in_schema(&path, value, property);
+                }
+            }
+            toml::Value::Array(items) => {
+                for item in items {
+                    assert_keys_in_schema(path, item, &schema["items"]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_json_schema_covers_config_keys() {
+        let schema = json_schema().unwrap();
+        let example: toml::Table = toml::from_str(&format!(
+            "version = 1\ninclude = [\"conf.d/*.toml\"]\n{HOSTS_CONFIG}\n\
+             [notifications]\nsound = \"bell\"\ncommand = [\"notify\", \"{{summary}}\"]\n\n\
+             [[hooks]]\non = \"switch\"\ncommand = \"true\"\ntimeout_secs = 5\n"
+        ))
+        .unwrap();
+        assert_keys_in_schema("", &toml::Value::Table(example), &schema);
+
+        let settings = &schema["properties"]["settings"];
+        assert_eq!(settings["additionalProperties"], false);
+        assert!(settings["properties"].get("match_by_idx").is_none());
+        let match_by_index = &settings["properties"]["match_by_index"];
+        assert_eq!(match_by_index["type"], "boolean");
+        assert_eq!(match_by_index["default"], false);
+        assert!(
+            match_by_index["description"]
+                .as_str()
+                .unwrap()
+                .starts_with("Rule priority strategy")
+        );
+        assert_eq!(
+            schema["properties"]["rules"]["items"]["required"],
+            serde_json::json!(["app_id", "sink"])
+        );
+        assert_eq!(
+            schema["properties"]["hooks"]["items"]["properties"]["on"]["enum"][0],
+            "switch"
+        );
+    }
+
+    #[test]
+    fn test_create_with_sinks_writes_working_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let sinks = [
+            make_sink("alsa_output.speakers", "Speakers", true),
+            make_sink("alsa_output.hdmi", "HDMI", false),
+        ];
+
+        let config = Config::create_with_sinks(&path, &sinks).unwrap();
+        assert_eq!(config.sinks, sinks);
+        assert_eq!(config.settings.log_level, "info");
+
+        // The commented examples stay around for reference
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("# Window rules"));
+        assert!(contents.find("[[sinks]]").unwrap() < contents.find("# Window rules").unwrap());
+    }
+
+    #[test]
+    fn test_create_with_sinks_checks_before_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let original = "[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n";
+        fs::write(&path, original).unwrap();
+
+        // Two defaults don't validate, so the existing file is left alone
+        let sinks = [
+            make_sink("alsa_output.speakers", "Speakers", true),
+            make_sink("alsa_output.hdmi", "HDMI", true),
+        ];
+        assert!(Config::create_with_sinks(&path, &sinks).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+        assert!(!dir.path().join("backups").exists());
+    }
+
+    #[test]
+    fn test_restore_backup_only_writes_valid_configs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let speakers = [make_sink("alsa_output.speakers", "Speakers", true)];
+        let hdmi = [make_sink("alsa_output.hdmi", "HDMI", true)];
+
+        Config::create_with_sinks(&path, &speakers).unwrap();
+        let good = config_backup::create(&path).unwrap().unwrap();
+        Config::create_with_sinks(&path, &hdmi).unwrap();
+
+        let restored = Config::restore_backup(&path, &path, &good).unwrap();
+        assert_eq!(restored.sinks, speakers);
+
+        // A backup that no longer loads leaves the current file alone
+        fs::write(&good.path, "[[rules]]\napp_id = \"(\"\nsink = 1\n").unwrap();
+        let before = fs::read_to_string(&path).unwrap();
+        let err = Config::restore_backup(&path, &path, &good).unwrap_err();
+        assert!(format!("{err:#}").contains("is not a valid config"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_saves_back_up_included_files_and_restore_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pwsw.toml");
+        let rules_path = dir.path().join("conf.d").join("rules.toml");
+        fs::create_dir(dir.path().join("conf.d")).unwrap();
+        fs::write(
+            &path,
+            "include = [\"conf.d/*.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+        )
+        .unwrap();
+        let original = "[[rules]]\napp_id = \"steam\"\nsink = \"S\"\n";
+        fs::write(&rules_path, original).unwrap();
+
+        // Any path is backed up, not just the default config or --config
+        let mut config = Config::load_from_path(&path).unwrap();
+        config.rules[0].desc = Some("Gaming".to_string());
+        config.settings.log_level = "debug".to_string();
+        config.save_to(&path).unwrap();
+        assert_eq!(config_backup::list(&path).unwrap().len(), 1);
+        let backups = config_backup::list(&rules_path).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert!(
+            backups[0]
+                .path
+                .starts_with(dir.path().join("conf.d").join("backups"))
+        );
+
+        let restored = Config::restore_backup(&path, &rules_path, &backups[0]).unwrap();
+        assert_eq!(restored.rules[0].desc, None);
+        assert_eq!(restored.settings.log_level, "debug");
+        assert_eq!(fs::read_to_string(&rules_path).unwrap(), original);
+
+        // A backup that breaks the config as a whole is not restored
+        fs::write(
+            &backups[0].path,
+            "[[rules]]\napp_id = \"steam\"\nsink = \"Missing\"\n",
+        )
+        .unwrap();
+        assert!(Config::restore_backup(&path, &rules_path, &backups[0]).is_err());
+        assert_eq!(fs::read_to_string(&rules_path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_recursive_include_skips_backups_of_included_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pwsw.toml");
+        let rules_path = dir.path().join("conf.d").join("rules.toml");
+        fs::create_dir(dir.path().join("conf.d")).unwrap();
+        fs::write(
+            &path,
+            "include = [\"conf.d/**/*.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+        )
+        .unwrap();
+        fs::write(&rules_path, "[[rules]]\napp_id = \"steam\"\nsink = \"S\"\n").unwrap();
+
+        for desc in ["Gaming", "Games"] {
+            let mut config = Config::load_from_path(&path).unwrap();
+            config.rules[0].desc = Some(desc.to_string());
+            config.save_to(&path).unwrap();
+        }
+        assert_eq!(config_backup::list(&rules_path).unwrap().len(), 2);
+
+        let config = Config::load_from_path(&path).unwrap();
+        assert_eq!(config.rules.len(), 1);
+        assert_eq!(config.rules[0].desc.as_deref(), Some("Games"));
+        assert_eq!(
+            resolve_includes(&["conf.d/**/*.toml".to_string()], &path).unwrap(),
+            vec![rules_path]
+        );
+    }
+
+    #[test]
+    fn test_lint_rules_finds_duplicates_and_shadowed_rules() {
+        let sinks = vec![
+            make_sink("speakers", "Speakers", true),
+            make_sink("headset", "Headset", false),
+        ];
+        let config = make_config(
+            sinks,
+            vec![
+                make_rule("^steam$", None, "Headset"),
+                make_rule("firefox", Some("YouTube"), "Headset"),
+                make_rule("^steam$", None, "Speakers"),
+                make_rule("firefox", None, "Headset"),
+                make_rule("^.*$", None, "Headset"),
+                make_rule("firefox", Some("Netflix"), "Headset"),
+                make_rule("mpv", Some(".*"), "Headset"),
+            ],
+        );
+
+        assert_eq!(
+            config.lint_rules(),
+            [
+                None,
+                None,
+                Some(RuleLint::Duplicate(0)),
+                None,
+                None,
+                Some(RuleLint::Shadowed(3)),
+                Some(RuleLint::Shadowed(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_rules_default_target_depends_on_priority_mode() {
+        let sinks = vec![
+            make_sink("speakers", "Speakers", true),
+            make_sink("headset", "Headset", false),
+        ];
+        let mut config = make_config(
+            sinks,
+            vec![
+                make_rule("discord", None, "Headset"),
+                make_rule("firefox", None, "Speakers"),
+                make_rule("mpv", None, "1"),
+            ],
+        );
+
+        // Recent window wins: the Speakers rules can still switch away from Headset
+        config.settings.match_by_index = false;
+        assert_eq!(config.lint_rules(), [None, None, None]);
+
+        // Rule order wins: nothing after them switches away from the default
+        config.settings.match_by_index = true;
+        assert_eq!(
+            config.lint_rules(),
+            [
+                None,
+                Some(RuleLint::TargetsDefault),
+                Some(RuleLint::TargetsDefault)
+            ]
+        );
+
+        config.rules.remove(0);
+        config.settings.match_by_index = false;
+        assert_eq!(
+            config.lint_rules(),
+            [
+                Some(RuleLint::TargetsDefault),
+                Some(RuleLint::TargetsDefault)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_value_parses_by_key_type() {
+        let mut config = make_config(vec![], vec![]);
+
+        config.set_value("match_by_index", "true").unwrap();
+        config.set_value("notifications.timeout_ms", "0").unwrap();
+        config.set_value("urgency", "critical").unwrap();
+        config.set_value("summary", "true").unwrap();
+        config
+            .set_value("command", r#"["my-notify", "{body}"]"#)
+            .unwrap();
+        assert!(config.settings.match_by_index);
+        assert_eq!(config.notifications.timeout_ms, 0);
+        assert_eq!(config.notifications.urgency, NotificationUrgency::Critical);
+        assert_eq!(config.notifications.summary, "true");
+        assert_eq!(
+            config.notifications.command.as_deref(),
+            Some(&["my-notify".to_string(), "{body}".to_string()][..])
+        );
+
+        config.set_value("notifications.command", "").unwrap();
+        assert_eq!(config.notifications.command, None);
+
+        assert!(config.set_value("timeout_ms", "soon").is_err());
+        assert!(config.set_value("settings.urgency", "low").is_err());
+        assert!(config.set_value("no_such_key", "1").is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_empty_hook_command() {
+        let mut config = make_config(vec![make_sink("sink1", "Sink 1", true)], vec![]);
+        config.hooks.push(Hook {
+            on: HookEvent::DaemonStart,
+            command: "  ".to_string(),
+            timeout_secs: 10,
+            source: None,
+        });
+
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("Hook 1"));
+    }
 }
@@ -0,0 +1,298 @@
+//! Timestamped backups of the config file and its includes
+//!
+//! Every save copies the file it replaces into a `backups` directory next to it, named
+//! after the file and the local time of the save (`config-20261018-142530.toml`). Only
+//! the newest [`KEEP_BACKUPS`] are kept. `pwsw config backups` lists them and
+//! `pwsw config restore` brings one back (see [`crate::config::Config::restore_backup`]).
+
+use color_eyre::eyre::{self, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of backups kept per config file
+pub const KEEP_BACKUPS: usize = 10;
+
+/// Timestamp format of backup ids
+const ID_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// A saved copy of the config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    /// Local time of the save that replaced this version (`20261018-142530`), with a
+    /// `-2`, `-3`, ... suffix for saves within the same second
+    pub id: String,
+    pub path: PathBuf,
+}
+
+impl Backup {
+    /// When the backup was taken, for display (`2026-10-18 14:25:30`)
+    #[must_use]
+    pub fn created(&self) -> String {
+        let timestamp = self.id.get(..15).unwrap_or(&self.id);
+        chrono::NaiveDateTime::parse_from_str(timestamp, ID_FORMAT).map_or_else(
+            |_| self.id.clone(),
+            |t| t.format("%Y-%m-%d %H:%M:%S").to_string(),
+        )
+    }
+}
+
+/// Directory holding the backups of `config_path`
+#[must_use]
+pub fn backup_dir(config_path: &Path) -> PathBuf {
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("backups")
+}
+
+/// Whether `path` is a backup written by [`create`]
+///
+/// Include globs such as `conf.d/**/*.toml` also match the backups of included files,
+/// which must not be loaded as config.
+#[must_use]
+pub fn is_backup(path: &Path) -> bool {
+    let in_backup_dir = path
+        .parent()
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "backups");
+    let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let ends_with_id = |s: &str| {
+        s.len() > 16
+            && s.get(s.len() - 16..)
+                .and_then(|t| t.strip_prefix('-'))
+                .is_some_and(|t| chrono::NaiveDateTime::parse_from_str(t, ID_FORMAT).is_ok())
+    };
+    // `<stem>-<timestamp>` or `<stem>-<timestamp>-<n>`
+    in_backup_dir
+        && (ends_with_id(stem)
+            || stem
+                .rsplit_once('-')
+                .is_some_and(|(rest, n)| n.parse::<u32>().is_ok() && ends_with_id(rest)))
+}
+
+/// File name parts of the backups of `config_path`: `(stem-, .extension)`
+fn name_parts(config_path: &Path) -> (String, String) {
+    let stem = config_path
+        .file_stem()
+        .map_or_else(|| "config".into(), |s| s.to_string_lossy());
+    let extension = config_path
+        .extension()
+        .map_or_else(String::new, |e| format!(".{}", e.to_string_lossy()));
+    (format!("{stem}-"), extension)
+}
+
+/// Copy `config_path` into its backup directory and prune old backups
+///
+/// Nothing is copied if the file doesn't exist or matches the newest backup.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or the backup cannot be written.
+pub fn create(config_path: &Path) -> Result<Option<Backup>> {
+    if !config_path.is_file() {
+        return Ok(None);
+    }
+    let contents = fs::read(config_path)
+        .with_context(|| format!("Failed to read config: {}", config_path.display()))?;
+
+    let existing = list(config_path)?;
+    if let Some(newest) = existing.first()
+        && fs::read(&newest.path).is_ok_and(|c| c == contents)
+    {
+        return Ok(None);
+    }
+
+    let dir = backup_dir(config_path);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create backup dir: {}", dir.display()))?;
+
+    let (prefix, extension) = name_parts(config_path);
+    let timestamp = chrono::Local::now().format(ID_FORMAT).to_string();
+    // Number saves within the same second after the newest one, even if older ones
+    // were pruned, so ids keep sorting by time
+    let next = existing
+        .iter()
+        .filter(|b| b.id.starts_with(&timestamp))
+        .map(|b| sequence(&b.id))
+        .max()
+        .map_or(1, |n| n + 1);
+    let id = if next == 1 {
+        timestamp
+    } else {
+        format!("{timestamp}-{next}")
+    };
+    let backup = Backup {
+        path: dir.join(format!("{prefix}{id}{extension}")),
+        id,
+    };
+
+    fs::write(&backup.path, &contents)
+        .with_context(|| format!("Failed to write backup: {}", backup.path.display()))?;
+    // Backups may hold the same details as the config itself
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&backup.path, fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to set permissions: {}", backup.path.display()))?;
+    }
+
+    for old in list(config_path)?.iter().skip(KEEP_BACKUPS) {
+        fs::remove_file(&old.path)
+            .with_context(|| format!("Failed to remove old backup: {}", old.path.display()))?;
+    }
+    Ok(Some(backup))
+}
+
+/// Position of a backup among the saves within its second (1 without a suffix)
+fn sequence(id: &str) -> u32 {
+    id.get(16..).and_then(|n| n.parse().ok()).unwrap_or(1)
+}
+
+/// Backups of `config_path`, newest first
+///
+/// # Errors
+/// Returns an error if the backup directory exists but cannot be read.
+pub fn list(config_path: &Path) -> Result<Vec<Backup>> {
+    let dir = backup_dir(config_path);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let (prefix, extension) = name_parts(config_path);
+    let mut backups: Vec<Backup> = fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read backup dir: {}", dir.display()))?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let id = name.strip_prefix(&prefix)?.strip_suffix(&extension)?;
+            // Skip other files, including backups of a config whose name extends this one
+            let timestamp = id.get(..15)?;
+            chrono::NaiveDateTime::parse_from_str(timestamp, ID_FORMAT).ok()?;
+            if id.len() > 15 && id[15..].strip_prefix('-')?.parse::<u32>().is_err() {
+                return None;
+            }
+            Some(Backup {
+                id: id.to_string(),
+                path,
+            })
+        })
+        .collect();
+
+    // Ids sort by time, then by their same-second number
+    backups.sort_by(|a, b| (&b.id[..15], sequence(&b.id)).cmp(&(&a.id[..15], sequence(&a.id))));
+    Ok(backups)
+}
+
+/// Find a backup by id or by its position in [`list`] (1 = newest)
+///
+/// # Errors
+/// Returns an error if no backup matches.
+pub fn find(config_path: &Path, id: &str) -> Result<Backup> {
+    let backups = list(config_path)?;
+    let found = match id.parse::<usize>() {
+        Ok(position) => position
+            .checked_sub(1)
+            .and_then(|i| backups.get(i))
+            .cloned(),
+        _ => backups.iter().find(|b| b.id == id).cloned(),
+    };
+    found.ok_or_else(|| {
+        eyre::eyre!(
+            "No backup '{id}' of {}. List them with 'pwsw config backups'.",
+            config_path.display()
+        )
+    })
+}
+
+/// Lines that differ between `current` and `backup`: `('-', line)` for lines only in
+/// `current`, `('+', line)` for lines only in `backup`
+#[must_use]
+pub fn changed_lines<'a>(current: &'a str, backup: &'a str) -> Vec<(char, &'a str)> {
+    diff::lines(current, backup)
+        .into_iter()
+        .filter_map(|line| match line {
+            diff::Result::Left(l) => Some(('-', l)),
+            diff::Result::Right(r) => Some(('+', r)),
+            diff::Result::Both(..) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backups_are_listed_newest_first_and_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        for i in 0..KEEP_BACKUPS + 2 {
+            fs::write(&path, format!("# version {i}\n")).unwrap();
+            assert!(create(&path).unwrap().is_some());
+        }
+        // Unchanged files are not backed up twice
+        assert!(create(&path).unwrap().is_none());
+
+        let backups = list(&path).unwrap();
+        assert_eq!(backups.len(), KEEP_BACKUPS);
+        assert_eq!(
+            fs::read_to_string(&backups[0].path).unwrap(),
+            format!("# version {}\n", KEEP_BACKUPS + 1)
+        );
+        assert_eq!(
+            fs::read_to_string(&backups[KEEP_BACKUPS - 1].path).unwrap(),
+            "# version 2\n"
+        );
+
+        assert_eq!(find(&path, "1").unwrap(), backups[0]);
+        assert_eq!(find(&path, &backups[3].id).unwrap(), backups[3]);
+        assert!(find(&path, "11").is_err());
+        assert!(find(&path, "20000101-000000").is_err());
+    }
+
+    #[test]
+    fn test_is_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.toml");
+        fs::write(&path, "# rules\n").unwrap();
+        let backup = create(&path).unwrap().unwrap();
+
+        assert!(is_backup(&backup.path));
+        assert!(is_backup(Path::new(
+            "conf.d/backups/rules-20261018-142530-2.toml"
+        )));
+        assert!(!is_backup(&path));
+        assert!(!is_backup(Path::new("conf.d/rules-20261018-142530.toml")));
+        assert!(!is_backup(Path::new("conf.d/backups/rules.toml")));
+    }
+
+    #[test]
+    fn test_other_files_are_not_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::create_dir(backup_dir(&path)).unwrap();
+        for name in [
+            "config-20261018-142530.toml",
+            "config-20261018-142530-2.toml",
+            "config-local-20261018-142530.toml",
+            "config-notes.toml",
+            "config-20261018-142530.toml.swp",
+        ] {
+            fs::write(backup_dir(&path).join(name), "").unwrap();
+        }
+
+        let ids: Vec<_> = list(&path).unwrap().into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, ["20261018-142530-2", "20261018-142530"]);
+        assert_eq!(list(&path).unwrap()[1].created(), "2026-10-18 14:25:30");
+    }
+
+    #[test]
+    fn test_changed_lines() {
+        assert_eq!(
+            changed_lines("a\nb\nc\n", "a\nB\nc\nd\n"),
+            [('-', "b"), ('+', "B"), ('+', "d")]
+        );
+    }
+}
@@ -0,0 +1,311 @@
+//! Format-preserving edits of config files
+//!
+//! Saves from the TUI are applied as targeted changes to the existing TOML document
+//! instead of rewriting it, so comments, blank lines, key order and keys pwsw doesn't
+//! know about survive. The caller describes the change as two plain tables: what the
+//! file meant before (`old`) and what it should mean now (`new`). Only keys whose value
+//! differs between the two are touched in the document.
+
+use color_eyre::eyre::{Context, Result};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, Value};
+
+/// Apply the difference between `old` and `new` to the TOML document `original`
+///
+/// Keys missing from both tables are left alone. Array-of-tables entries (sinks, rules,
+/// hooks) that are unchanged keep their formatting even when they move.
+///
+/// # Errors
+/// Returns an error if `original` is not valid TOML.
+pub fn patch_document(original: &str, old: &toml::Table, new: &toml::Table) -> Result<String> {
+    let mut doc: DocumentMut = original
+        .parse()
+        .context("Failed to parse config for editing")?;
+    patch_table(doc.as_table_mut(), old, new);
+    Ok(doc.to_string())
+}
+
+/// Add the tables of the TOML document `tables` (e.g. `[[sinks]]`) to the end of `original`
+///
+/// Comments at the end of `original` stay above the new tables, except from the first
+/// line starting with `comments_below` on; without such a line all of them do.
+///
+/// # Errors
+/// Returns an error if either document is not valid TOML.
+pub fn append_tables(original: &str, tables: &str, comments_below: &str) -> Result<String> {
+    let mut doc: DocumentMut = original
+        .parse()
+        .context("Failed to parse config for editing")?;
+    let tables: DocumentMut = tables.parse().context("Failed to parse new tables")?;
+    if tables.is_empty() {
+        return Ok(original.to_string());
+    }
+
+    let trailing = doc.trailing().as_str().unwrap_or_default().to_string();
+    let split = trailing
+        .match_indices(comments_below)
+        .map(|(at, _)| at)
+        .find(|&at| at == 0 || trailing[..at].ends_with('\n'))
+        .unwrap_or(trailing.len());
+    let (above, below) = trailing.split_at(split);
+    doc.set_trailing(if below.is_empty() {
+        String::new()
+    } else {
+        format!("\n{below}")
+    });
+
+    let mut above = Some(above);
+    for (key, mut item) in tables.as_table().clone() {
+        let first = match &mut item {
+            Item::Table(table) => Some(table.decor_mut()),
+            Item::ArrayOfTables(tables) => tables.get_mut(0).map(Table::decor_mut),
+            _ => None,
+        };
+        if let (Some(decor), Some(prefix)) = (first, above.take()) {
+            decor.set_prefix(prefix);
+        }
+        doc.insert(&key, item);
+    }
+    Ok(doc.to_string())
+}
+
+fn patch_table(doc: &mut Table, old: &toml::Table, new: &toml::Table) {
+    for key in old.keys() {
+        if !new.contains_key(key) {
+            doc.remove(key);
+        }
+    }
+
+    for (key, new_value) in new {
+        let old_value = old.get(key);
+        if old_value == Some(new_value) {
+            continue;
+        }
+
+        match (doc.get_mut(key), old_value, new_value) {
+            (Some(Item::Table(table)), Some(toml::Value::Table(old)), toml::Value::Table(new)) => {
+                patch_table(table, old, new);
+            }
+            (Some(Item::ArrayOfTables(_)), _, toml::Value::Array(new)) if new.is_empty() => {
+                doc.remove(key);
+            }
+            (
+                Some(Item::ArrayOfTables(tables)),
+                Some(toml::Value::Array(old)),
+                toml::Value::Array(new),
+            ) if tables.len() == old.len() && new.iter().all(toml::Value::is_table) => {
+                patch_array_of_tables(tables, old, new);
+            }
+            (Some(Item::Value(value)), _, new_value) if !is_array_of_tables(new_value) => {
+                let decor = value.decor().clone();
+                *value = to_value(new_value);
+                *value.decor_mut() = decor;
+            }
+            _ => {
+                doc.insert(key, to_item(new_value));
+            }
+        }
+    }
+}
+
+/// Update `[[...]]` entries in place, reusing the document table of each entry
+///
+/// An entry equal to an old one is moved as-is (with its comments); any other entry
+/// patches the old entry at the same index, or is added as a new table.
+fn patch_array_of_tables(doc: &mut ArrayOfTables, old: &[toml::Value], new: &[toml::Value]) {
+    let mut previous: Vec<Option<Table>> = doc.iter().cloned().map(Some).collect();
+    let positions: Vec<isize> = {
+        let mut positions: Vec<_> = doc.iter().filter_map(Table::position).collect();
+        positions.sort_unstable();
+        positions
+    };
+
+    let mut tables: Vec<Option<Table>> = new
+        .iter()
+        .map(|value| {
+            let index = old
+                .iter()
+                .enumerate()
+                .position(|(i, o)| o == value && previous[i].is_some())?;
+            previous[index].take()
+        })
+        .collect();
+
+    for (i, value) in new.iter().enumerate() {
+        if tables[i].is_some() {
+            continue;
+        }
+        let table = match (previous.get_mut(i).and_then(Option::take), &old.get(i)) {
+            (Some(mut table), Some(toml::Value::Table(old))) => {
+                if let toml::Value::Table(new) = value {
+                    patch_table(&mut table, old, new);
+                }
+                table
+            }
+            _ => to_table(value),
+        };
+        tables[i] = Some(table);
+    }
+
+    // The document is printed in table position order, so kept tables take the old
+    // positions in their new order; new tables follow whatever precedes them
+    let mut positions = positions.into_iter();
+    doc.clear();
+    for mut table in tables.into_iter().flatten() {
+        if table.position().is_some() {
+            table.set_position(positions.next());
+        }
+        doc.push(table);
+    }
+}
+
+fn is_array_of_tables(value: &toml::Value) -> bool {
+    value
+        .as_array()
+        .is_some_and(|a| !a.is_empty() && a.iter().all(toml::Value::is_table))
+}
+
+/// A new document item for `value`, laid out like `toml::to_string_pretty` would
+fn to_item(value: &toml::Value) -> Item {
+    match value {
+        toml::Value::Table(_) => Item::Table(to_table(value)),
+        toml::Value::Array(values) if is_array_of_tables(value) => {
+            let mut tables = ArrayOfTables::new();
+            for value in values {
+                tables.push(to_table(value));
+            }
+            Item::ArrayOfTables(tables)
+        }
+        _ => Item::Value(to_value(value)),
+    }
+}
+
+fn to_table(value: &toml::Value) -> Table {
+    let mut table = Table::new();
+    if let toml::Value::Table(entries) = value {
+        for (key, value) in entries {
+            table.insert(key, to_item(value));
+        }
+        // Tables holding only sub-tables (e.g. `[host]`) don't need a header
+        table.set_implicit(
+            !entries.is_empty()
+                && entries
+                    .values()
+                    .all(|v| v.is_table() || is_array_of_tables(v)),
+        );
+    }
+    table.decor_mut().set_prefix("\n");
+    table
+}
+
+fn to_value(value: &toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::from(s.as_str()),
+        toml::Value::Integer(i) => Value::from(*i),
+        toml::Value::Float(f) => Value::from(*f),
+        toml::Value::Boolean(b) => Value::from(*b),
+        toml::Value::Array(values) => Value::Array(values.iter().map(to_value).collect()),
+        toml::Value::Table(entries) => Value::InlineTable(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), to_value(value)))
+                .collect(),
+        ),
+        toml::Value::Datetime(datetime) => datetime
+            .to_string()
+            .parse()
+            .unwrap_or_else(|_| Value::from(datetime.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(s: &str) -> toml::Table {
+        toml::from_str(s).unwrap()
+    }
+
+    const ORIGINAL: &str = r#"# My audio setup
+[settings]
+log_level = "info"  # bump to debug when testing
+custom_key = 1
+
+# Speakers first
+[[rules]]
+app_id = "mpv"
+sink = "Speakers"
+
+# Games go to the headphones
+[[rules]]
+app_id = "steam"
+sink = "Headphones"
+"#;
+
+    #[test]
+    fn test_patch_keeps_comments_and_unknown_keys() {
+        let old = table(
+            "[settings]\nlog_level = \"info\"\n\n[[rules]]\napp_id = \"mpv\"\nsink = \"Speakers\"\n\n[[rules]]\napp_id = \"steam\"\nsink = \"Headphones\"\n",
+        );
+        let new = table(
+            "[settings]\nlog_level = \"debug\"\n\n[[rules]]\napp_id = \"mpv\"\nsink = \"Speakers\"\n\n[[rules]]\napp_id = \"steam\"\nsink = \"Headphones\"\ndesc = \"Games\"\n",
+        );
+
+        let patched = patch_document(ORIGINAL, &old, &new).unwrap();
+        assert_eq!(
+            patched,
+            ORIGINAL.replace("\"info\"", "\"debug\"").replace(
+                "sink = \"Headphones\"\n",
+                "sink = \"Headphones\"\ndesc = \"Games\"\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_reordered_entries_keep_their_comments() {
+        let old = table(
+            "[[rules]]\napp_id = \"mpv\"\nsink = \"Speakers\"\n\n[[rules]]\napp_id = \"steam\"\nsink = \"Headphones\"\n",
+        );
+        let new = table(
+            "[[rules]]\napp_id = \"steam\"\nsink = \"Headphones\"\n\n[[rules]]\napp_id = \"mpv\"\nsink = \"Speakers\"\n",
+        );
+
+        let patched = patch_document(ORIGINAL, &old, &new).unwrap();
+        let steam = patched.find("# Games go to the headphones").unwrap();
+        let mpv = patched.find("# Speakers first").unwrap();
+        assert!(steam < mpv, "{patched}");
+        assert!(patched.contains("custom_key = 1"));
+    }
+
+    #[test]
+    fn test_added_and_removed_entries() {
+        let old = table(
+            "[[rules]]\napp_id = \"mpv\"\nsink = \"Speakers\"\n\n[[rules]]\napp_id = \"steam\"\nsink = \"Headphones\"\n",
+        );
+        let new = table(
+            "[[rules]]\napp_id = \"steam\"\nsink = \"Headphones\"\n\n[[rules]]\napp_id = \"firefox\"\nsink = \"Speakers\"\n",
+        );
+
+        let patched = patch_document(ORIGINAL, &old, &new).unwrap();
+        assert!(!patched.contains("mpv"));
+        assert!(patched.contains("# Games go to the headphones"));
+        assert!(patched.ends_with("\n[[rules]]\napp_id = \"firefox\"\nsink = \"Speakers\"\n"));
+        assert_eq!(table(&patched)["rules"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_append_tables_between_trailing_comments() {
+        let original = "[settings]\nlog_level = \"info\"\n\n# Sinks\n\n# Rules\n# [[rules]]\n";
+        let sinks = "[[sinks]]\nname = \"s\"\ndesc = \"S\"\n";
+
+        let appended = append_tables(original, sinks, "# Rules").unwrap();
+        assert_eq!(
+            appended,
+            "[settings]\nlog_level = \"info\"\n\n# Sinks\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\n\n# Rules\n# [[rules]]\n"
+        );
+
+        // Without the marker, every comment stays above the new tables
+        let appended = append_tables(original, sinks, "# Hooks").unwrap();
+        assert!(appended.ends_with("# [[rules]]\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\n"));
+        assert_eq!(table(&appended)["sinks"].as_array().unwrap().len(), 1);
+    }
+}
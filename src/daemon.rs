@@ -5,22 +5,26 @@
 
 use color_eyre::eyre::{self, Context, ContextCompat, Result};
 use notify::{Event, RecursiveMode, Watcher};
+use std::collections::HashSet;
 use std::io::Write;
-use std::path::PathBuf;
-use std::sync::Arc;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
 use tempfile::NamedTempFile;
 use tokio::signal;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing::{error, info, warn};
 
 use crate::compositor;
-use crate::config::Config;
+use crate::config::{Config, HookEvent};
+use crate::dbus::{DbusRequest, DbusService};
+use crate::hooks::HookEnv;
 use crate::ipc::{self, IpcServer, Request, Response, WindowInfo};
-use crate::notification::send_notification;
+use crate::notification::{SwitchActionEvent, send_notification};
 use crate::pipewire::PipeWire;
-use crate::state::State;
+use crate::state::{ManualRequest, State};
 use crate::style::PwswStyle;
+use crate::trace::{RecordOptions, TraceRecorder};
 
 // ============================================================================
 // Public API
@@ -42,19 +46,19 @@ pub fn get_log_file_path() -> Result<PathBuf> {
 /// Get the daemon PID file path
 ///
 /// Returns `$XDG_RUNTIME_DIR/pwsw.pid` or `/tmp/pwsw-{uid}.pid` as fallback
-///
-/// The fallback uses the numeric UID rather than the `USER` environment variable
-/// to prevent potential symlink attacks from manipulated environment variables.
+/// (per `--config` file, see [`ipc::runtime_file_path`])
 #[must_use]
 pub fn get_pid_file_path() -> PathBuf {
-    if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
-        PathBuf::from(runtime_dir).join("pwsw.pid")
-    } else {
-        // Fallback to /tmp with UID for consistent, secure location
-        // Using UID instead of USER env var prevents potential symlink attacks
-        use rustix::process::getuid;
-        let uid = getuid().as_raw();
-        PathBuf::from(format!("/tmp/pwsw-{uid}.pid"))
+    ipc::runtime_file_path("pid")
+}
+
+/// Command that stops this config's daemon, for error hints
+fn shutdown_command() -> String {
+    match Config::config_path_override() {
+        Some(path) if Config::instance_id().is_some() => {
+            format!("pwsw --config {} shutdown", path.display())
+        }
+        _ => "pwsw shutdown".to_string(),
     }
 }
 
@@ -203,17 +207,56 @@ struct IpcContext {
     uptime_secs: u64,
     current_sink_name: String,
     active_window: Option<String>,
+    active_app_id: Option<String>,
+    active_rule_desc: Option<String>,
     daemon_manager: crate::daemon_manager::DaemonManager,
     // tracked: (id, app_id, title, sink_name, sink_desc)
     tracked_with_sinks: Vec<(u64, String, String, String, String)>,
     // all windows: (id, app_id, title)
     all_windows: Vec<(u64, String, String)>,
+    target_sink_name: String,
+    priority_mode: crate::state::PriorityMode,
+    // tracked windows ranked by priority (winner first)
+    explained_windows: Vec<ipc::ExplainedWindow>,
+    paused: bool,
+    pause_remaining_secs: Option<u64>,
+    hold_sink_name: Option<String>,
+    hold_remaining_secs: Option<u64>,
     config: Arc<Config>,
     shutdown_tx: broadcast::Sender<()>,
+    command_tx: mpsc::Sender<DaemonCommand>,
+}
+
+/// Commands from IPC handlers that must run on the main loop (they mutate `State`)
+enum DaemonCommand {
+    Reload {
+        reply: oneshot::Sender<Response>,
+    },
+    Pause {
+        duration: Option<Duration>,
+        reply: oneshot::Sender<Response>,
+    },
+    Resume {
+        reply: oneshot::Sender<Response>,
+    },
+    ManualSwitch {
+        request: ManualRequest,
+        /// Hold the resulting sink against rule-based switching
+        hold: bool,
+        /// Hold duration (`None` = until released)
+        hold_duration: Option<Duration>,
+        reply: oneshot::Sender<Response>,
+    },
+    Release {
+        reply: oneshot::Sender<Response>,
+    },
 }
 
 /// Run the daemon with the given configuration
 ///
+/// With `record`, every window event from the compositor is also written to a trace file.
+/// With `dbus`, the daemon also serves requests as `io.github.pwsw` on the session bus.
+///
 /// # Errors
 /// Returns an error if another daemon is running, initialization fails, compositor
 /// connection fails, `SIGTERM` signal handler cannot be installed, or any critical
@@ -222,11 +265,15 @@ struct IpcContext {
 /// # Returns
 /// Returns `Ok(())` on successful daemon shutdown.
 // Main daemon event loop - cohesive logic hard to split; constants scoped in spawn blocks
-pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
+pub async fn run(
+    config: Arc<Config>,
+    foreground: bool,
+    record: Option<RecordOptions>,
+    dbus: bool,
+) -> Result<()> {
     const CONFIG_DEBOUNCE_MS: u64 = 250;
 
     use std::process::Command;
-    use std::time::Duration;
 
     // Check for running daemon BEFORE any initialization
     // Order matters: Check PID file first (fast, local), then IPC socket (slow, network timeout)
@@ -238,8 +285,9 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
             "Another PWSW daemon is already running (PID file check).\n\
              Socket: {}\n\n\
              To stop the existing daemon, run:\n  \
-             pwsw shutdown",
-            socket_path.display()
+             {}",
+            socket_path.display(),
+            shutdown_command()
         );
     }
 
@@ -250,8 +298,9 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
             "Another PWSW daemon is already running (IPC check).\n\
              Socket: {}\n\n\
              To stop the existing daemon, run:\n  \
-             pwsw shutdown",
-            socket_path.display()
+             {}",
+            socket_path.display(),
+            shutdown_command()
         );
     }
 
@@ -267,8 +316,21 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
 
         // Spawn detached daemon process WITHOUT --foreground so it logs to file
         // Pass environment variable to prevent child from spawning another process
-        let mut child = Command::new(&exe)
-            .arg("daemon")
+        let mut command = Command::new(&exe);
+        if let Some(path) = Config::config_path_override() {
+            command.arg("--config").arg(path);
+        }
+        command.arg("daemon");
+        if let Some(ref record) = record {
+            command.arg("--record").arg(&record.path);
+            if record.redact_titles {
+                command.arg("--redact-titles");
+            }
+        }
+        if dbus {
+            command.arg("--dbus");
+        }
+        let mut child = command
             .env("PWSW_DAEMON_CHILD", "1")
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
@@ -411,6 +473,13 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
     // Create shutdown channel with larger buffer to handle concurrent subscribers
     let (shutdown_tx, mut shutdown_rx) = broadcast::channel::<()>(8);
 
+    // Commands from IPC handlers that need mutable access to state
+    let (command_tx, mut command_rx) = mpsc::channel::<DaemonCommand>(16);
+
+    // Undo/Keep here clicks on rule switch notifications
+    let (action_tx, mut action_rx) = mpsc::unbounded_channel::<SwitchActionEvent>();
+    state.enable_notification_actions(action_tx);
+
     // Switch to default on startup if configured
     if state.config.settings.default_on_startup {
         let default = state
@@ -424,57 +493,75 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
             let name_clone = default.name.clone();
             let desc_clone = default.desc.clone();
             let join = tokio::task::spawn_blocking(move || {
-                crate::state::switch_audio_blocking(&name_clone, &desc_clone, None, None, false)
+                crate::state::switch_audio_blocking(&name_clone, &desc_clone, None, None)
             });
 
             let inner = join.await.map_err(|e| eyre::eyre!("Join error: {e:#}"))?;
             inner?;
 
             state.current_sink_name.clone_from(&default.name);
+            state.run_hooks(
+                HookEvent::Switch,
+                &HookEnv {
+                    sink_name: Some(default.name.clone()),
+                    sink_desc: Some(default.desc.clone()),
+                    reason: Some("Default on startup".to_string()),
+                    app_id: None,
+                },
+            );
         }
     }
 
     // Spawn compositor event thread
-    let mut window_events = compositor::spawn_compositor_thread()?;
+    let (protocol, mut window_events) = compositor::spawn_compositor_thread_with_protocol()?;
     info!("Compositor event thread started");
 
+    let mut recorder = match record {
+        Some(ref options) => {
+            let recorder = TraceRecorder::create(options, protocol)?;
+            info!("Recording window events to {}", options.path.display());
+            Some(recorder)
+        }
+        None => None,
+    };
+
     // Start IPC server
     let ipc_server = IpcServer::bind().await?;
+
+    // D-Bus method calls are answered on the main loop, like IPC connections
+    let (dbus_tx, mut dbus_rx) = mpsc::channel::<DbusRequest>(16);
+    let dbus_service = if dbus {
+        match DbusService::start(None, dbus_tx.clone()).await {
+            Ok(service) => {
+                info!("D-Bus service started ({})", crate::dbus::BUS_NAME);
+                Some(Arc::new(service))
+            }
+            Err(e) => {
+                warn!("D-Bus service unavailable: {e:#}");
+                None
+            }
+        }
+    } else {
+        None
+    };
     info!("IPC server listening on {:?}", ipc_server.socket_path());
 
     // Setup config file watcher (hot-reload)
-    let config_path = Config::get_config_path()?;
-    let config_dir = config_path.parent().unwrap_or(&config_path);
-
     // Use unbounded channel to ensure no config reload events are lost
     // Debouncing (250ms) in the main loop handles rapid successive saves
     let (config_tx, mut config_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
-    let config_tx_clone = config_tx.clone();
-
-    // Only notify reloads for changes to the exact config file
-    // Use unbounded send() instead of try_send() to never drop events
-    let config_path_clone = config_path.clone();
-    let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
-        match res {
-            Ok(event) => {
-                if (event.kind.is_modify() || event.kind.is_create())
-                    && event.paths.iter().any(|p| p == &config_path_clone)
-                {
-                    // Never drop config reload events - unbounded send always succeeds
-                    let _ = config_tx_clone.send(());
-                }
-            }
-            Err(e) => error!("Config watch error: {:?}", e),
-        }
-    })?;
-
-    if let Err(e) = watcher.watch(config_dir, RecursiveMode::NonRecursive) {
-        warn!("Failed to watch config directory for hot-reload: {}", e);
-    }
+    let mut config_watch = ConfigWatch::new(Config::get_config_path()?, config_tx)?;
+    config_watch.update(&state.config);
+    let mut watched_config = Arc::clone(&state.config);
 
     if state.config.settings.notify_manual
-        && let Err(e) =
-            send_notification(NOTIFICATION_STARTED_TITLE, NOTIFICATION_STARTED_MSG, None)
+        && state.config.notifications.start_stop
+        && let Err(e) = send_notification(
+            &state.config.notifications,
+            NOTIFICATION_STARTED_TITLE,
+            NOTIFICATION_STARTED_MSG,
+            None,
+        )
     {
         warn!("Could not send startup notification: {}", e);
     }
@@ -496,8 +583,17 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
     }
 
     info!("Daemon initialization complete, entering event loop");
+    state.run_hooks(
+        HookEvent::DaemonStart,
+        &HookEnv {
+            sink_name: Some(state.current_sink_name.clone()),
+            sink_desc: Some(sink_desc(&state.config, &state.current_sink_name)),
+            ..HookEnv::default()
+        },
+    );
 
     let mut last_config_reload = Instant::now();
+    let mut last_sink_name = state.current_sink_name.clone();
 
     // Main event loop
     loop {
@@ -508,30 +604,21 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
                     break;
                 };
 
+                if let Some(rec) = recorder.as_mut()
+                    && let Err(e) = rec.record(&event)
+                {
+                    error!("Stopped recording window events: {e:#}");
+                    recorder = None;
+                }
+
                 if let Err(e) = state.process_event(event).await {
                     error!("Event processing error: {e:#}", e = e);
                 }
             }
 
             Some(mut stream) = ipc_server.accept() => {
-                // Handle IPC request - clone what we need for the task
-                // Tracked windows: (id, app_id, title, sink_name, sink_desc)
-                let tracked_with_sinks = state.get_tracked_windows_with_sinks();
-                // All windows: (id, app_id, title)
-                let all_windows = state.get_all_windows();
-
-                let ctx = IpcContext {
-                    version: crate::version_string(),
-                    uptime_secs: start_time.elapsed().as_secs(),
-                    current_sink_name: state.current_sink_name.clone(),
-                    active_window: state.get_most_recent_window()
-                        .map(|w| format!("{}: {}", w.trigger_desc, w.sink_name)),
-                    daemon_manager: state.daemon_manager,
-                    tracked_with_sinks,
-                    all_windows,
-                    config: state.config.clone(),
-                    shutdown_tx: shutdown_tx.clone(),
-                };
+                // Handle IPC request - snapshot what we need for the task
+                let ctx = ipc_context(&state, start_time, &shutdown_tx, &command_tx);
 
                 tokio::spawn(async move {
                     if let Err(e) = handle_ipc_request(&mut stream, ctx).await {
@@ -555,68 +642,89 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
                 if now.duration_since(last_config_reload) >= Duration::from_millis(CONFIG_DEBOUNCE_MS) {
                     last_config_reload = now;
                     info!("Config file changed, attempting reload...");
-                    match Config::load() {
-                        Ok(new_config) => {
-                            // Daemon requires sinks to operate - reject config with no sinks
-                            if new_config.sinks.is_empty() {
-                                warn!("Config reload rejected: no sinks configured. Keeping previous config.");
-                                if state.config.settings.notify_manual {
-                                    let _ = send_notification("Reload Rejected", "Config has no sinks - keeping previous", None);
-                                }
-                                // Drain pending events
-                                while config_rx.try_recv().is_ok() {}
-                                continue;
-                            }
-
-                            let notify_enabled = state.config.settings.notify_manual;
-                            state.reload_config(Arc::new(new_config));
-
-                            // Re-evaluate all active windows against new rules
-                            if let Err(e) = state.reevaluate_all_windows().await {
-                                error!("Failed to re-evaluate windows after config reload: {e:#}");
-                            }
-
-                            if notify_enabled {
-                                let _ = send_notification("Configuration Reloaded", "New settings applied successfully", None);
-                            }
-
-                            // Drain any pending events that occurred during processing (e.g. from atomic save steps)
-                            while config_rx.try_recv().is_ok() {}
-                        },
-                        Err(e) => {
-                            error!("Failed to reload config: {e:#}");
-                            if state.config.settings.notify_manual {
-                                let _ = send_notification("Reload Failed", &format!("Config error: {e:#}"), None);
-                            }
-                        }
-                    }
+                    // Errors are logged and notified inside; keep the previous config
+                    let _ = reload_config_from_disk(&mut state).await;
+
+                    // Drain any pending events that occurred during processing (e.g. from atomic save steps)
+                    while config_rx.try_recv().is_ok() {}
+                }
+            }
+
+            Some(command) = command_rx.recv() => {
+                handle_daemon_command(&mut state, command).await;
+            }
+
+            Some(event) = action_rx.recv() => {
+                match state.handle_notification_action(event).await {
+                    Ok(message) => info!("Notification action: {message}"),
+                    Err(e) => error!("Notification action failed: {e:#}"),
+                }
+            }
+
+            Some((request, reply)) = dbus_rx.recv() => {
+                let ctx = ipc_context(&state, start_time, &shutdown_tx, &command_tx);
+                tokio::spawn(async move {
+                    let _ = reply.send(dispatch_request(request, ctx).await);
+                });
+            }
+
+            () = tokio::time::sleep_until(
+                tokio::time::Instant::from_std(state.pause_deadline().unwrap_or_else(Instant::now))
+            ), if state.pause_deadline().is_some() => {
+                info!("Pause expired");
+                if let Err(e) = state.resume().await {
+                    error!("Failed to switch after pause expired: {e:#}");
+                }
+            }
+
+            () = tokio::time::sleep_until(
+                tokio::time::Instant::from_std(state.hold_deadline().unwrap_or_else(Instant::now))
+            ), if state.hold_deadline().is_some() => {
+                info!("Manual hold expired");
+                if let Err(e) = state.release_hold().await {
+                    error!("Failed to switch after manual hold expired: {e:#}");
                 }
             }
 
             _ = signal::ctrl_c() => {
                 info!("Shutting down (Ctrl-C)");
-                if state.config.settings.notify_manual {
-                    let _ = send_notification(NOTIFICATION_STOPPED_TITLE, NOTIFICATION_STOPPED_MSG, None);
-                }
+                notify_stopped(&state.config);
                 break;
             }
 
             _ = sigterm.recv() => {
                 info!("Shutting down (SIGTERM from systemd)");
-                if state.config.settings.notify_manual {
-                    let _ = send_notification(NOTIFICATION_STOPPED_TITLE, NOTIFICATION_STOPPED_MSG, None);
-                }
+                notify_stopped(&state.config);
                 break;
             }
 
             _ = shutdown_rx.recv() => {
                 info!("Shutting down (IPC request)");
-                if state.config.settings.notify_manual {
-                    let _ = send_notification(NOTIFICATION_STOPPED_TITLE, NOTIFICATION_STOPPED_MSG, None);
-                }
+                notify_stopped(&state.config);
                 break;
             }
         }
+
+        // Follow include changes after a reload
+        if !Arc::ptr_eq(&watched_config, &state.config) {
+            watched_config = Arc::clone(&state.config);
+            config_watch.update(&state.config);
+        }
+
+        // Announce sink switches on D-Bus, whatever caused them
+        if state.current_sink_name != last_sink_name {
+            last_sink_name.clone_from(&state.current_sink_name);
+            if let Some(service) = &dbus_service {
+                let service = Arc::clone(service);
+                let name = last_sink_name.clone();
+                let desc = sink_desc(&state.config, &name);
+                tokio::spawn(async move {
+                    if let Err(e) = service.sink_changed(&name, &desc).await {
+                        warn!("{e:#}");
+                    }
+                });
+            }
+        }
     }
 
     // Cleanup PID file on shutdown
@@ -625,20 +733,410 @@ pub async fn run(config: Arc<Config>, foreground: bool) -> Result<()> {
     Ok(())
 }
 
+/// Watches the config file and its includes for hot-reload
+struct ConfigWatch {
+    watcher: notify::RecommendedWatcher,
+    config_path: PathBuf,
+    /// Paths (the config file and `include` patterns) whose changes trigger a reload
+    patterns: Arc<Mutex<Vec<glob::Pattern>>>,
+    watched_dirs: HashSet<PathBuf>,
+}
+
+impl ConfigWatch {
+    /// Start watching; `tx` receives a message for every relevant change
+    fn new(config_path: PathBuf, tx: mpsc::UnboundedSender<()>) -> Result<Self> {
+        let patterns: Arc<Mutex<Vec<glob::Pattern>>> = Arc::default();
+        let watched = Arc::clone(&patterns);
+
+        // Only notify reloads for changes to the config file or an included file
+        // Use unbounded send() instead of try_send() to never drop events
+        let watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+            match res {
+                Ok(event) => {
+                    let patterns = watched.lock().unwrap_or_else(PoisonError::into_inner);
+                    if (event.kind.is_modify() || event.kind.is_create() || event.kind.is_remove())
+                        && event
+                            .paths
+                            .iter()
+                            .filter(|p| !crate::config_backup::is_backup(p))
+                            .any(|p| patterns.iter().any(|pattern| pattern.matches_path(p)))
+                    {
+                        // Never drop config reload events - unbounded send always succeeds
+                        let _ = tx.send(());
+                    }
+                }
+                Err(e) => error!("Config watch error: {:?}", e),
+            }
+        })?;
+
+        Ok(Self {
+            watcher,
+            config_path,
+            patterns,
+            watched_dirs: HashSet::new(),
+        })
+    }
+
+    /// Watch the config file and the files `config` includes
+    ///
+    /// Directories are watched rather than files so that editors saving atomically and
+    /// newly created files matching an include glob are noticed.
+    fn update(&mut self, config: &Config) {
+        let escaped = PathBuf::from(glob::Pattern::escape(&self.config_path.to_string_lossy()));
+        let paths: Vec<PathBuf> = std::iter::once(escaped)
+            .chain(config.include_patterns(&self.config_path))
+            .collect();
+
+        let mut patterns = Vec::with_capacity(paths.len());
+        for path in &paths {
+            match glob::Pattern::new(&path.to_string_lossy()) {
+                Ok(pattern) => patterns.push(pattern),
+                Err(e) => warn!("Not watching include '{}': {e}", path.display()),
+            }
+
+            let (dir, mode) = watch_root(path);
+            if self.watched_dirs.contains(&dir) {
+                continue;
+            }
+            match self.watcher.watch(&dir, mode) {
+                Ok(()) => {
+                    self.watched_dirs.insert(dir);
+                }
+                Err(e) => warn!(
+                    "Failed to watch {} for config hot-reload: {}",
+                    dir.display(),
+                    e
+                ),
+            }
+        }
+
+        *self.patterns.lock().unwrap_or_else(PoisonError::into_inner) = patterns;
+    }
+}
+
+/// Directory to watch for a config path pattern
+///
+/// That is the pattern's parent, or the nearest ancestor without glob characters
+/// (watched recursively) when the directory part itself is a glob.
+fn watch_root(pattern: &Path) -> (PathBuf, RecursiveMode) {
+    let is_literal = |p: &Path| {
+        let s = p.to_string_lossy();
+        glob::Pattern::escape(&s) == s
+    };
+    let parent = pattern.parent().unwrap_or_else(|| Path::new("/"));
+    if is_literal(parent) {
+        return (parent.to_path_buf(), RecursiveMode::NonRecursive);
+    }
+    let root = parent
+        .ancestors()
+        .find(|dir| is_literal(dir))
+        .unwrap_or_else(|| Path::new("/"));
+    (root.to_path_buf(), RecursiveMode::Recursive)
+}
+
+/// Send the "daemon stopped" notification if enabled
+fn notify_stopped(config: &Config) {
+    if config.settings.notify_manual && config.notifications.start_stop {
+        let _ = send_notification(
+            &config.notifications,
+            NOTIFICATION_STOPPED_TITLE,
+            NOTIFICATION_STOPPED_MSG,
+            None,
+        );
+    }
+}
+
+/// Reload the config file from disk and re-evaluate tracked windows
+///
+/// Shared by the config file watcher and `pwsw reload`. Failures are logged and
+/// notified here; the previous config stays active.
+async fn reload_config_from_disk(state: &mut State) -> Result<()> {
+    let new_config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to reload config: {e:#}");
+            if state.config.settings.notify_manual && state.config.notifications.errors {
+                let _ = send_notification(
+                    &state.config.notifications,
+                    "Reload Failed",
+                    &format!("Config error: {e:#}"),
+                    None,
+                );
+            }
+            state.run_hooks(HookEvent::ReloadFailed, &reload_failed_env(&e));
+            return Err(e);
+        }
+    };
+
+    // Daemon requires sinks to operate - reject config with no sinks
+    if new_config.sinks.is_empty() {
+        warn!("Config reload rejected: no sinks configured. Keeping previous config.");
+        if state.config.settings.notify_manual && state.config.notifications.errors {
+            let _ = send_notification(
+                &state.config.notifications,
+                "Reload Rejected",
+                "Config has no sinks - keeping previous",
+                None,
+            );
+        }
+        let e = eyre::eyre!("Config has no sinks - keeping previous config");
+        state.run_hooks(HookEvent::ReloadFailed, &reload_failed_env(&e));
+        return Err(e);
+    }
+
+    state.reload_config(Arc::new(new_config));
+
+    // Re-evaluate all active windows against new rules
+    if let Err(e) = state.reevaluate_all_windows().await {
+        error!("Failed to re-evaluate windows after config reload: {e:#}");
+    }
+
+    // The new config decides whether to announce itself
+    if state.config.settings.notify_manual && state.config.notifications.reload {
+        let _ = send_notification(
+            &state.config.notifications,
+            "Configuration Reloaded",
+            "New settings applied successfully",
+            None,
+        );
+    }
+
+    Ok(())
+}
+
+/// Execute a command forwarded from an IPC handler on the main loop
+async fn handle_daemon_command(state: &mut State, command: DaemonCommand) {
+    match command {
+        DaemonCommand::Reload { reply } => {
+            info!("Config reload requested via IPC");
+            let response = match reload_config_from_disk(state).await {
+                Ok(()) => Response::Ok {
+                    message: "Configuration reloaded".to_string(),
+                },
+                Err(e) => Response::Error {
+                    message: format!("Reload failed: {e:#}"),
+                },
+            };
+            let _ = reply.send(response);
+        }
+        DaemonCommand::Pause { duration, reply } => {
+            state.pause(duration);
+            let message = duration.map_or_else(
+                || "Automatic switching paused until resumed".to_string(),
+                |d| {
+                    format!(
+                        "Automatic switching paused for {}",
+                        crate::format_uptime(d.as_secs())
+                    )
+                },
+            );
+            if state.config.settings.notify_manual {
+                let _ = send_notification(
+                    &state.config.notifications,
+                    "Switching Paused",
+                    &message,
+                    None,
+                );
+            }
+            let _ = reply.send(Response::Ok { message });
+        }
+        DaemonCommand::ManualSwitch {
+            request,
+            hold,
+            hold_duration,
+            reply,
+        } => {
+            let hold = hold.then_some(hold_duration);
+            let response = match state.manual_switch(&request, hold).await {
+                Ok(message) => Response::Ok { message },
+                Err(e) => Response::Error {
+                    message: format!("{e:#}"),
+                },
+            };
+            let _ = reply.send(response);
+        }
+        DaemonCommand::Release { reply } => {
+            let response = match state.release_hold().await {
+                Ok(true) => Response::Ok {
+                    message: "Manual hold released".to_string(),
+                },
+                Ok(false) => Response::Ok {
+                    message: "No manual hold is active".to_string(),
+                },
+                Err(e) => Response::Error {
+                    message: format!("Hold released, but switching failed: {e:#}"),
+                },
+            };
+            let _ = reply.send(response);
+        }
+        DaemonCommand::Resume { reply } => {
+            let was_paused = state.is_paused();
+            let response = match state.resume().await {
+                Ok(()) if was_paused => {
+                    if state.config.settings.notify_manual {
+                        let _ = send_notification(
+                            &state.config.notifications,
+                            "Switching Resumed",
+                            "Automatic switching resumed",
+                            None,
+                        );
+                    }
+                    Response::Ok {
+                        message: "Automatic switching resumed".to_string(),
+                    }
+                }
+                Ok(()) => Response::Ok {
+                    message: "Automatic switching was not paused".to_string(),
+                },
+                Err(e) => Response::Error {
+                    message: format!("Resumed, but switching failed: {e:#}"),
+                },
+            };
+            let _ = reply.send(response);
+        }
+    }
+}
+
+/// Forward a command to the main loop and wait for its response
+async fn send_daemon_command(
+    command_tx: &mpsc::Sender<DaemonCommand>,
+    make_command: impl FnOnce(oneshot::Sender<Response>) -> DaemonCommand,
+) -> Response {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    if command_tx.send(make_command(reply_tx)).await.is_err() {
+        return Response::Error {
+            message: "Daemon is shutting down".to_string(),
+        };
+    }
+    reply_rx.await.unwrap_or_else(|_| Response::Error {
+        message: "Daemon dropped the request".to_string(),
+    })
+}
+
+/// Hook environment for a failed reload (the error becomes `PWSW_REASON`)
+fn reload_failed_env(error: &eyre::Report) -> HookEnv {
+    HookEnv {
+        reason: Some(format!("{error:#}")),
+        ..HookEnv::default()
+    }
+}
+
+/// Snapshot the state an `IPC` request handler needs (handlers run on their own task)
+fn ipc_context(
+    state: &State,
+    start_time: Instant,
+    shutdown_tx: &broadcast::Sender<()>,
+    command_tx: &mpsc::Sender<DaemonCommand>,
+) -> IpcContext {
+    let explained_windows = state
+        .explain_windows()
+        .into_iter()
+        .map(|(id, w, verdict)| ipc::ExplainedWindow {
+            id,
+            app_id: w.app_id.clone(),
+            title: w.title.clone(),
+            rule_index: w.rule_index,
+            rule_desc: w.trigger_desc.clone(),
+            sink_name: w.sink_name.clone(),
+            sink_desc: sink_desc(&state.config, &w.sink_name),
+            age_secs: w.opened_at.elapsed().as_secs(),
+            verdict,
+        })
+        .collect();
+    let most_recent = state.get_most_recent_window();
+
+    IpcContext {
+        version: crate::version_string(),
+        uptime_secs: start_time.elapsed().as_secs(),
+        current_sink_name: state.current_sink_name.clone(),
+        active_window: most_recent.map(|w| format!("{}: {}", w.trigger_desc, w.sink_name)),
+        active_app_id: most_recent.map(|w| w.app_id.clone()),
+        active_rule_desc: most_recent.map(|w| w.trigger_desc.clone()),
+        daemon_manager: state.daemon_manager,
+        tracked_with_sinks: state.get_tracked_windows_with_sinks(),
+        all_windows: state.get_all_windows(),
+        target_sink_name: state.determine_target_sink(),
+        priority_mode: state.priority_mode(),
+        explained_windows,
+        paused: state.is_paused(),
+        pause_remaining_secs: state.pause_remaining().map(|d| d.as_secs()),
+        hold_sink_name: state.active_hold().map(|h| h.sink_name.clone()),
+        hold_remaining_secs: state.hold_remaining().map(|d| d.as_secs()),
+        config: state.config.clone(),
+        shutdown_tx: shutdown_tx.clone(),
+        command_tx: command_tx.clone(),
+    }
+}
+
+/// Description of a configured sink, falling back to its node name
+fn sink_desc(config: &Config, sink_name: &str) -> String {
+    config
+        .sinks
+        .iter()
+        .find(|s| s.name == sink_name)
+        .map_or_else(|| sink_name.to_string(), |s| s.desc.clone())
+}
+
 /// Handle a single IPC request from a client
-// IPC request handler - cohesive dispatch logic for all request types
 async fn handle_ipc_request(stream: &mut tokio::net::UnixStream, ctx: IpcContext) -> Result<()> {
-    let request = ipc::read_request(stream).await?;
+    let request = match ipc::read_request(stream).await? {
+        Ok(request) => request,
+        Err(message) => {
+            warn!("Rejected IPC request: {message}");
+            ipc::write_response(stream, &Response::Error { message }).await?;
+            return Ok(());
+        }
+    };
+
+    if let Request::Shutdown = request {
+        info!("Shutdown requested via IPC");
+        // Send response before shutting down
+        ipc::write_response(
+            stream,
+            &Response::Ok {
+                message: "Daemon shutting down...".to_string(),
+            },
+        )
+        .await?;
+
+        // Signal shutdown to main loop
+        let _ = ctx.shutdown_tx.send(());
+        return Ok(());
+    }
+
+    let response = dispatch_request(request, ctx).await;
+    ipc::write_response(stream, &response).await?;
+    Ok(())
+}
+
+/// Answer a request from any transport (`IPC` socket or D-Bus)
+// Cohesive dispatch logic for all request types
+async fn dispatch_request(request: Request, ctx: IpcContext) -> Response {
+    match request {
+        Request::Hello { protocol_version } => {
+            if protocol_version != ipc::PROTOCOL_VERSION {
+                warn!(
+                    "Client speaks IPC protocol v{protocol_version}, daemon speaks v{}",
+                    ipc::PROTOCOL_VERSION
+                );
+            }
+            Response::Hello {
+                protocol_version: ipc::PROTOCOL_VERSION,
+                daemon_version: ctx.version,
+                capabilities: ipc::SUPPORTED_REQUESTS
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            }
+        }
 
-    let response = match request {
         Request::Status => {
             // Get current sink description
-            let current_sink = ctx
-                .config
-                .sinks
-                .iter()
-                .find(|s| s.name == ctx.current_sink_name)
-                .map_or_else(|| ctx.current_sink_name.clone(), |s| s.desc.clone());
+            let current_sink = sink_desc(&ctx.config, &ctx.current_sink_name);
+            let hold_sink = ctx
+                .hold_sink_name
+                .as_ref()
+                .map(|name| sink_desc(&ctx.config, name));
 
             Response::Status {
                 version: ctx.version,
@@ -646,6 +1144,12 @@ async fn handle_ipc_request(stream: &mut tokio::net::UnixStream, ctx: IpcContext
                 current_sink,
                 active_window: ctx.active_window,
                 tracked_windows: ctx.tracked_with_sinks.len(),
+                active_app_id: ctx.active_app_id,
+                active_rule_desc: ctx.active_rule_desc,
+                paused: ctx.paused,
+                pause_remaining_secs: ctx.pause_remaining_secs,
+                hold_sink,
+                hold_remaining_secs: ctx.hold_remaining_secs,
             }
         }
 
@@ -688,6 +1192,18 @@ async fn handle_ipc_request(stream: &mut tokio::net::UnixStream, ctx: IpcContext
             Response::Windows { windows }
         }
 
+        Request::Explain => Response::Explanation {
+            current_sink: sink_desc(&ctx.config, &ctx.current_sink_name),
+            target_sink: sink_desc(&ctx.config, &ctx.target_sink_name),
+            priority_mode: ctx.priority_mode,
+            paused: ctx.paused,
+            hold_sink: ctx
+                .hold_sink_name
+                .as_ref()
+                .map(|name| sink_desc(&ctx.config, name)),
+            windows: ctx.explained_windows,
+        },
+
         Request::TestRule { pattern } => match regex::Regex::new(&pattern) {
             Ok(regex) => {
                 let matches = ctx
@@ -729,68 +1245,57 @@ async fn handle_ipc_request(stream: &mut tokio::net::UnixStream, ctx: IpcContext
             daemon_manager: ctx.daemon_manager,
         },
 
-        Request::SetSink { sink } => {
-            // Resolve sink reference (name, description, or 1-indexed position)
-            if let Some(target) = ctx.config.resolve_sink(&sink) {
-                // Attempt to activate the sink via PipeWire
-                // Run blocking activation in spawn_blocking to avoid blocking the IPC task/runtime
-                let target_name = target.name.clone();
-                let join =
-                    tokio::task::spawn_blocking(move || PipeWire::activate_sink(&target_name));
-
-                match join.await {
-                    Ok(Ok(())) => Response::Ok {
-                        message: format!("Switched to sink: {}", target.desc),
-                    },
-                    Ok(Err(e)) => Response::Error {
-                        message: format!(
-                            "Failed to activate sink '{target_desc}': {e:#}",
-                            target_desc = target.desc,
-                            e = e
-                        ),
-                    },
-                    Err(e) => Response::Error {
-                        message: format!("Internal error during sink activation: {e:#}"),
-                    },
-                }
-            } else {
-                // Build helpful error message with available sinks
-                let available: Vec<_> = ctx
-                    .config
-                    .sinks
-                    .iter()
-                    .enumerate()
-                    .map(|(i, s)| format!("{}. '{}'", i + 1, s.desc))
-                    .collect();
-                Response::Error {
-                    message: format!(
-                        "Unknown sink '{}'. Available: [{}]",
-                        sink,
-                        available.join(", ")
-                    ),
-                }
-            }
+        Request::SetSink {
+            sink,
+            hold,
+            hold_secs,
+        } => {
+            send_daemon_command(&ctx.command_tx, |reply| DaemonCommand::ManualSwitch {
+                request: ManualRequest::Set(sink),
+                hold,
+                hold_duration: hold_secs.map(Duration::from_secs),
+                reply,
+            })
+            .await
         }
 
-        Request::Shutdown => {
-            info!("Shutdown requested via IPC");
-            // Send response before shutting down
-            ipc::write_response(
-                stream,
-                &Response::Ok {
-                    message: "Daemon shutting down...".to_string(),
-                },
-            )
-            .await?;
+        Request::CycleSink { direction } => {
+            send_daemon_command(&ctx.command_tx, |reply| DaemonCommand::ManualSwitch {
+                request: ManualRequest::Cycle(direction),
+                hold: false,
+                hold_duration: None,
+                reply,
+            })
+            .await
+        }
 
-            // Signal shutdown to main loop
-            let _ = ctx.shutdown_tx.send(());
+        Request::Reload => {
+            send_daemon_command(&ctx.command_tx, |reply| DaemonCommand::Reload { reply }).await
+        }
 
-            // Return without sending another response
-            return Ok(());
+        Request::Pause { duration_secs } => {
+            let duration = duration_secs.map(Duration::from_secs);
+            send_daemon_command(&ctx.command_tx, |reply| DaemonCommand::Pause {
+                duration,
+                reply,
+            })
+            .await
         }
-    };
 
-    ipc::write_response(stream, &response).await?;
-    Ok(())
+        Request::Release => {
+            send_daemon_command(&ctx.command_tx, |reply| DaemonCommand::Release { reply }).await
+        }
+
+        Request::Resume => {
+            send_daemon_command(&ctx.command_tx, |reply| DaemonCommand::Resume { reply }).await
+        }
+
+        Request::Shutdown => {
+            info!("Shutdown requested");
+            let _ = ctx.shutdown_tx.send(());
+            Response::Ok {
+                message: "Daemon shutting down...".to_string(),
+            }
+        }
+    }
 }
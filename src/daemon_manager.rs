@@ -30,12 +30,19 @@ impl DaemonManager {
     /// 2. If exists AND `INVOCATION_ID` is set, return Systemd (supervised)
     /// 3. Otherwise return Direct (manual start or no service file)
     ///
+    /// A `--config` file other than the default always gets Direct: `pwsw.service`
+    /// runs the daemon for the default config, not this one.
+    ///
     /// Note: We use `systemctl cat` instead of checking `LoadState` to avoid false
     /// positives from systemd's cached state after service file deletion.
     /// `INVOCATION_ID` alone is unreliable - it's set for all processes in
     /// a systemd user session, not just supervised services.
     #[must_use]
     pub fn detect() -> Self {
+        if crate::config::Config::instance_id().is_some() {
+            return Self::Direct;
+        }
+
         // First check if the service unit is installed
         let service_loaded = Self::check_systemd_available();
 
@@ -0,0 +1,209 @@
+//! D-Bus service interface
+//!
+//! Exposes the daemon on the session bus as `io.github.pwsw` for desktop tools that
+//! prefer D-Bus over the `IPC` socket. Method calls are translated into
+//! [`ipc::Request`](crate::ipc::Request)s and answered by the daemon main loop, so both
+//! transports share one request handler.
+
+use color_eyre::eyre::{Context, Result};
+use std::collections::HashMap;
+use tokio::sync::{mpsc, oneshot};
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::Value;
+use zbus::{Connection, fdo, interface};
+
+use crate::ipc::{Request, Response, WindowInfo};
+use crate::state::Direction;
+
+/// Well-known bus name owned by the daemon
+pub const BUS_NAME: &str = "io.github.pwsw";
+
+/// Object path of the service
+pub const OBJECT_PATH: &str = "/io/github/pwsw";
+
+/// A request forwarded to the daemon main loop, with the channel for its response
+pub type DbusRequest = (Request, oneshot::Sender<Response>);
+
+/// D-Bus string dictionary (`a{sv}`)
+type Dict = HashMap<String, Value<'static>>;
+
+/// The `io.github.pwsw` interface object
+struct PwswInterface {
+    requests: mpsc::Sender<DbusRequest>,
+}
+
+impl PwswInterface {
+    /// Forward a request to the daemon and wait for its response
+    async fn send(&self, request: Request) -> fdo::Result<Response> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.requests
+            .send((request, reply_tx))
+            .await
+            .map_err(|_| fdo::Error::Failed("Daemon is shutting down".to_string()))?;
+        reply_rx
+            .await
+            .map_err(|_| fdo::Error::Failed("Daemon dropped the request".to_string()))
+    }
+
+    /// Send a request answered with `Ok`/`Error`, returning the message
+    async fn send_control(&self, request: Request) -> fdo::Result<String> {
+        match self.send(request).await? {
+            Response::Ok { message } => Ok(message),
+            Response::Error { message } => Err(fdo::Error::Failed(message)),
+            _ => Err(unexpected_response()),
+        }
+    }
+}
+
+#[interface(name = "io.github.pwsw")]
+impl PwswInterface {
+    /// Switch to a sink by description, node name or position (smart toggle applies)
+    async fn set_sink(&self, sink: String) -> fdo::Result<String> {
+        self.send_control(Request::SetSink {
+            sink,
+            hold: false,
+            hold_secs: None,
+        })
+        .await
+    }
+
+    /// Cycle to the next configured sink
+    async fn next_sink(&self) -> fdo::Result<String> {
+        self.send_control(Request::CycleSink {
+            direction: Direction::Next,
+        })
+        .await
+    }
+
+    /// Cycle to the previous configured sink
+    async fn prev_sink(&self) -> fdo::Result<String> {
+        self.send_control(Request::CycleSink {
+            direction: Direction::Prev,
+        })
+        .await
+    }
+
+    /// Daemon status (same fields as `pwsw status --json`, absent values omitted)
+    async fn status(&self) -> fdo::Result<Dict> {
+        match self.send(Request::Status).await? {
+            Response::Status {
+                version,
+                uptime_secs,
+                current_sink,
+                active_window,
+                tracked_windows,
+                active_app_id,
+                active_rule_desc,
+                paused,
+                pause_remaining_secs,
+                hold_sink,
+                hold_remaining_secs,
+            } => {
+                let mut status = Dict::new();
+                status.insert("version".to_string(), version.into());
+                status.insert("uptime_secs".to_string(), uptime_secs.into());
+                status.insert("current_sink".to_string(), current_sink.into());
+                status.insert(
+                    "tracked_windows".to_string(),
+                    (tracked_windows as u64).into(),
+                );
+                status.insert("paused".to_string(), paused.into());
+                let optional = [
+                    ("active_window", active_window.map(Value::from)),
+                    ("active_app_id", active_app_id.map(Value::from)),
+                    ("active_rule_desc", active_rule_desc.map(Value::from)),
+                    (
+                        "pause_remaining_secs",
+                        pause_remaining_secs.map(Value::from),
+                    ),
+                    ("hold_sink", hold_sink.map(Value::from)),
+                    ("hold_remaining_secs", hold_remaining_secs.map(Value::from)),
+                ];
+                for (key, value) in optional {
+                    if let Some(value) = value {
+                        status.insert(key.to_string(), value);
+                    }
+                }
+                Ok(status)
+            }
+            Response::Error { message } => Err(fdo::Error::Failed(message)),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    /// All open windows; tracked windows also carry `sink_name` and `sink_desc`
+    async fn list_windows(&self) -> fdo::Result<Vec<Dict>> {
+        match self.send(Request::ListWindows).await? {
+            Response::Windows { windows } => Ok(windows.into_iter().map(window_dict).collect()),
+            Response::Error { message } => Err(fdo::Error::Failed(message)),
+            _ => Err(unexpected_response()),
+        }
+    }
+
+    /// Emitted whenever the daemon switches the default sink
+    #[zbus(signal)]
+    async fn sink_changed(
+        emitter: &SignalEmitter<'_>,
+        sink_name: &str,
+        sink_desc: &str,
+    ) -> zbus::Result<()>;
+}
+
+/// Convert a window to a D-Bus dictionary
+fn window_dict(window: WindowInfo) -> Dict {
+    let mut dict = Dict::new();
+    if let Some(id) = window.id {
+        dict.insert("id".to_string(), id.into());
+    }
+    dict.insert("app_id".to_string(), window.app_id.into());
+    dict.insert("title".to_string(), window.title.into());
+    dict.insert("tracked".to_string(), window.tracked.is_some().into());
+    if let Some(tracked) = window.tracked {
+        dict.insert("sink_name".to_string(), tracked.sink_name.into());
+        dict.insert("sink_desc".to_string(), tracked.sink_desc.into());
+    }
+    dict
+}
+
+fn unexpected_response() -> fdo::Error {
+    fdo::Error::Failed("Unexpected response from daemon".to_string())
+}
+
+/// Running D-Bus service (the bus name is released when dropped)
+pub struct DbusService {
+    connection: Connection,
+}
+
+impl DbusService {
+    /// Connect to the bus, serve the interface and own [`BUS_NAME`]
+    ///
+    /// Connects to the session bus, or to `address` if given (e.g. a private bus in tests).
+    /// Method calls are forwarded to `requests`.
+    ///
+    /// # Errors
+    /// Returns an error if the bus is unreachable or the name is already owned.
+    pub async fn start(address: Option<&str>, requests: mpsc::Sender<DbusRequest>) -> Result<Self> {
+        let builder = match address {
+            Some(address) => zbus::connection::Builder::address(address)?,
+            None => zbus::connection::Builder::session()?,
+        };
+        let connection = builder
+            .serve_at(OBJECT_PATH, PwswInterface { requests })?
+            .name(BUS_NAME)?
+            .build()
+            .await
+            .with_context(|| format!("Failed to own D-Bus name {BUS_NAME}"))?;
+        Ok(Self { connection })
+    }
+
+    /// Emit the `SinkChanged` signal
+    ///
+    /// # Errors
+    /// Returns an error if the signal cannot be sent.
+    pub async fn sink_changed(&self, sink_name: &str, sink_desc: &str) -> Result<()> {
+        let emitter = SignalEmitter::new(&self.connection, OBJECT_PATH)?;
+        PwswInterface::sink_changed(&emitter, sink_name, sink_desc)
+            .await
+            .context("Failed to emit SinkChanged signal")
+    }
+}
@@ -0,0 +1,158 @@
+//! User hook scripts
+//!
+//! Runs the `[[hooks]]` commands from the config when daemon events happen. Each hook
+//! runs with `sh -c` on its own task and is killed after its timeout, so slow scripts
+//! never delay sink switching or the event loop.
+
+use std::process::Stdio;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::config::{Hook, HookEvent};
+
+/// Event details passed to hooks as `PWSW_*` environment variables (unset when `None`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookEnv {
+    /// `PWSW_SINK_NAME`: node name of the sink switched to (or matched by the rule)
+    pub sink_name: Option<String>,
+    /// `PWSW_SINK_DESC`: description of that sink
+    pub sink_desc: Option<String>,
+    /// `PWSW_REASON`: why the event happened (rule description, "Manual switch", error)
+    pub reason: Option<String>,
+    /// `PWSW_APP_ID`: `app_id` of the window involved
+    pub app_id: Option<String>,
+}
+
+impl HookEnv {
+    /// Environment variables for an event, `PWSW_EVENT` first
+    #[must_use]
+    pub fn vars(&self, event: HookEvent) -> Vec<(&'static str, &str)> {
+        let optional = [
+            ("PWSW_SINK_NAME", &self.sink_name),
+            ("PWSW_SINK_DESC", &self.sink_desc),
+            ("PWSW_REASON", &self.reason),
+            ("PWSW_APP_ID", &self.app_id),
+        ];
+        std::iter::once(("PWSW_EVENT", event.name()))
+            .chain(
+                optional
+                    .into_iter()
+                    .filter_map(|(key, value)| value.as_deref().map(|v| (key, v))),
+            )
+            .collect()
+    }
+}
+
+/// Start every hook registered for `event` without waiting for them
+///
+/// Must be called from within the tokio runtime. Failures and timeouts are logged.
+pub fn run_hooks(hooks: &[Hook], event: HookEvent, env: &HookEnv) {
+    for hook in hooks.iter().filter(|h| h.on == event) {
+        let mut command = tokio::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg(&hook.command)
+            .envs(env.vars(event))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        let child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                warn!(
+                    "Failed to start {} hook '{}': {e}",
+                    event.name(),
+                    hook.command
+                );
+                continue;
+            }
+        };
+
+        debug!("Running {} hook: {}", event.name(), hook.command);
+        let label = hook.command.clone();
+        let timeout = Duration::from_secs(hook.timeout_secs);
+        tokio::spawn(async move {
+            // Dropping the output future on timeout kills the child (`kill_on_drop`)
+            match tokio::time::timeout(timeout, child.wait_with_output()).await {
+                Ok(Ok(output)) if output.status.success() => {
+                    debug!("{} hook finished: {label}", event.name());
+                }
+                Ok(Ok(output)) => warn!(
+                    "{} hook '{label}' failed ({}): {}",
+                    event.name(),
+                    output.status,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Ok(Err(e)) => warn!("{} hook '{label}' failed: {e}", event.name()),
+                Err(_) => warn!(
+                    "{} hook '{label}' timed out after {}s and was killed",
+                    event.name(),
+                    timeout.as_secs()
+                ),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_env_vars_skip_unset_values() {
+        let env = HookEnv {
+            sink_name: Some("hdmi".to_string()),
+            sink_desc: Some("HDMI".to_string()),
+            reason: None,
+            app_id: Some("steam".to_string()),
+        };
+        assert_eq!(
+            env.vars(HookEvent::Switch),
+            vec![
+                ("PWSW_EVENT", "switch"),
+                ("PWSW_SINK_NAME", "hdmi"),
+                ("PWSW_SINK_DESC", "HDMI"),
+                ("PWSW_APP_ID", "steam"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_hooks_passes_env_and_filters_by_event() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let hooks = vec![
+            Hook {
+                on: HookEvent::Switch,
+                command: format!(
+                    "printf '%s %s' \"$PWSW_EVENT\" \"$PWSW_SINK_NAME\" > '{}'",
+                    out.display()
+                ),
+                timeout_secs: 5,
+                source: None,
+            },
+            Hook {
+                on: HookEvent::DaemonStart,
+                command: format!("echo wrong > '{}'", out.display()),
+                timeout_secs: 5,
+                source: None,
+            },
+        ];
+        let env = HookEnv {
+            sink_name: Some("hdmi".to_string()),
+            ..HookEnv::default()
+        };
+
+        run_hooks(&hooks, HookEvent::Switch, &env);
+
+        for _ in 0..100 {
+            if std::fs::read_to_string(&out).is_ok_and(|s| !s.is_empty()) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "switch hdmi");
+    }
+}
@@ -30,6 +30,31 @@ const STALE_SOCKET_CHECK_TIMEOUT_MS: u64 = 500;
 /// Timeout for client connections (longer to allow daemon to process request)
 const CLIENT_CONNECT_TIMEOUT_SECS: u64 = 5;
 
+/// `IPC` protocol version, bumped on incompatible changes to `Request`/`Response`
+///
+/// Adding a request type is compatible and does not require a bump; clients discover new
+/// request types via `capabilities`. Older daemons silently ignore fields they don't
+/// know, so a new field that changes what a request does needs a bump, and clients
+/// check the version before relying on it (v2: `SetSink.hold`).
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// Request types understood by this build (reported in `Response::Hello`)
+pub const SUPPORTED_REQUESTS: &[&str] = &[
+    "Hello",
+    "Status",
+    "ListWindows",
+    "Explain",
+    "TestRule",
+    "SetSink",
+    "CycleSink",
+    "Release",
+    "GetManagerInfo",
+    "Shutdown",
+    "Reload",
+    "Pause",
+    "Resume",
+];
+
 // ============================================================================
 // Message Types
 // ============================================================================
@@ -38,24 +63,78 @@ const CLIENT_CONNECT_TIMEOUT_SECS: u64 = 5;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Request {
+    /// Protocol handshake: exchange protocol versions and capabilities
+    Hello { protocol_version: u32 },
     /// Query daemon status
     Status,
     /// Get list of currently tracked windows
     ListWindows,
+    /// Explain which tracked window decided the current target sink
+    Explain,
     /// Test a rule pattern against current windows
     TestRule { pattern: String },
-    /// Manually switch to a specific sink
-    SetSink { sink: String },
+    /// Manually switch to a specific sink (smart toggle applies)
+    SetSink {
+        sink: String,
+        /// Hold the resulting sink against rule-based switching
+        #[serde(default)]
+        hold: bool,
+        /// Hold duration in seconds (`None` = until released)
+        #[serde(default)]
+        hold_secs: Option<u64>,
+    },
+    /// Release a manual hold and return to rule-based switching
+    Release,
+    /// Manually cycle to the next/previous configured sink
+    CycleSink { direction: crate::state::Direction },
     /// Get daemon manager information (systemd vs direct)
     GetManagerInfo,
     /// Gracefully shutdown the daemon
     Shutdown,
+    /// Reload the config file (same as the file watcher hot-reload)
+    Reload,
+    /// Pause automatic switching (windows are still tracked)
+    Pause {
+        /// Resume automatically after this many seconds (`None` = until resumed)
+        #[serde(default)]
+        duration_secs: Option<u64>,
+    },
+    /// Resume automatic switching and re-evaluate immediately
+    Resume,
+}
+
+impl Request {
+    /// Wire name of this request type (the `type` tag)
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::Hello { .. } => "Hello",
+            Self::Status => "Status",
+            Self::ListWindows => "ListWindows",
+            Self::Explain => "Explain",
+            Self::TestRule { .. } => "TestRule",
+            Self::SetSink { .. } => "SetSink",
+            Self::CycleSink { .. } => "CycleSink",
+            Self::Release => "Release",
+            Self::GetManagerInfo => "GetManagerInfo",
+            Self::Shutdown => "Shutdown",
+            Self::Reload => "Reload",
+            Self::Pause { .. } => "Pause",
+            Self::Resume => "Resume",
+        }
+    }
 }
 
 /// Responses sent from daemon to CLI
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Response {
+    /// Handshake reply with the daemon's protocol version and supported request types
+    Hello {
+        protocol_version: u32,
+        daemon_version: String,
+        capabilities: Vec<String>,
+    },
     /// Status information
     Status {
         version: String,
@@ -63,6 +142,24 @@ pub enum Response {
         current_sink: String,
         active_window: Option<String>,
         tracked_windows: usize,
+        /// `app_id` of the most recent tracked window (for `--format`)
+        #[serde(default)]
+        active_app_id: Option<String>,
+        /// Description of the rule that matched the most recent tracked window
+        #[serde(default)]
+        active_rule_desc: Option<String>,
+        /// Whether automatic switching is paused
+        #[serde(default)]
+        paused: bool,
+        /// Seconds left on a timed pause (`None` if not paused or paused indefinitely)
+        #[serde(default)]
+        pause_remaining_secs: Option<u64>,
+        /// Description of the manually held sink (`None` if no hold is active)
+        #[serde(default)]
+        hold_sink: Option<String>,
+        /// Seconds left on a timed hold (`None` if not held or held until released)
+        #[serde(default)]
+        hold_remaining_secs: Option<u64>,
     },
     /// Generic success response
     Ok { message: String },
@@ -70,6 +167,18 @@ pub enum Response {
     Error { message: String },
     /// List of tracked windows
     Windows { windows: Vec<WindowInfo> },
+    /// Sink selection explanation, tracked windows ranked by priority
+    Explanation {
+        current_sink: String,
+        /// Sink `determine_target_sink` picks right now (description)
+        target_sink: String,
+        priority_mode: crate::state::PriorityMode,
+        /// Whether automatic switching is paused (target not applied)
+        paused: bool,
+        /// Description of the manually held sink (`None` if no hold is active)
+        hold_sink: Option<String>,
+        windows: Vec<ExplainedWindow>,
+    },
     /// Rule test results
     RuleMatches {
         pattern: String,
@@ -102,25 +211,56 @@ pub struct TrackedInfo {
     pub sink_desc: String,
 }
 
+/// Tracked window with its ranking outcome (for `explain`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainedWindow {
+    pub id: u64,
+    pub app_id: String,
+    pub title: String,
+    /// 0-based position of the matched rule in the config
+    pub rule_index: usize,
+    pub rule_desc: String,
+    pub sink_name: String,
+    pub sink_desc: String,
+    /// Seconds since the window matched its rule (`opened_at`)
+    pub age_secs: u64,
+    pub verdict: crate::state::Verdict,
+}
+
 // ============================================================================
 // Socket Path Management
 // ============================================================================
 
 /// Get the `IPC` socket path
 /// Prefers `$XDG_RUNTIME_DIR/pwsw.sock`, falls back to `/tmp/pwsw-{uid}.sock`
+#[must_use]
+pub fn get_socket_path() -> PathBuf {
+    runtime_file_path("sock")
+}
+
+/// Path of a per-daemon runtime file (`pwsw.{extension}`)
+///
+/// With `--config`, the name gets the config's [`Config::instance_id`] (e.g.
+/// `pwsw-1a2b3c4d5e6f7a8b.sock`) so a daemon for another config runs next to the
+/// usual one, and commands given the same `--config` talk to it.
 ///
 /// The fallback uses the numeric UID rather than the `USER` environment variable
 /// to prevent potential symlink attacks from manipulated environment variables.
+///
+/// [`Config::instance_id`]: crate::config::Config::instance_id
 #[must_use]
-pub fn get_socket_path() -> PathBuf {
+pub fn runtime_file_path(extension: &str) -> PathBuf {
+    let instance = crate::config::Config::instance_id()
+        .map(|id| format!("-{id}"))
+        .unwrap_or_default();
     if let Ok(runtime_dir) = std::env::var("XDG_RUNTIME_DIR") {
-        PathBuf::from(runtime_dir).join("pwsw.sock")
+        PathBuf::from(runtime_dir).join(format!("pwsw{instance}.{extension}"))
     } else {
         // Fallback to /tmp with UID for consistent, secure location
         // Using UID instead of USER env var prevents potential symlink attacks
         use rustix::process::getuid;
         let uid = getuid().as_raw();
-        PathBuf::from(format!("/tmp/pwsw-{uid}.sock"))
+        PathBuf::from(format!("/tmp/pwsw-{uid}{instance}.{extension}"))
     }
 }
 
@@ -321,13 +461,60 @@ async fn write_message<T: Serialize>(stream: &mut UnixStream, message: &T) -> Re
 
 /// Send a request to the daemon and wait for response
 ///
+/// If the exchange fails after connecting (dropped connection or an unreadable
+/// response), the daemon is probed for its version so a mismatch after an
+/// upgrade is reported clearly instead of as a deserialization error.
+///
 /// # Errors
 /// Returns an error if connection fails or IPC communication fails.
 pub async fn send_request(request: Request) -> Result<Response> {
+    let mut stream = connect().await?;
+
+    match exchange(&mut stream, &request).await {
+        Ok(response) => Ok(response),
+        Err(e) => match diagnose_version_mismatch(request.kind()).await {
+            Some(message) => Err(e.wrap_err(message)),
+            None => Err(e),
+        },
+    }
+}
+
+/// Perform the protocol handshake and verify the daemon speaks our protocol
+///
+/// Returns the daemon's capabilities (supported request types).
+///
+/// # Errors
+/// Returns an error if the daemon cannot be reached or runs an incompatible version.
+pub async fn handshake() -> Result<Vec<String>> {
+    match send_request(Request::Hello {
+        protocol_version: PROTOCOL_VERSION,
+    })
+    .await?
+    {
+        Response::Hello {
+            protocol_version,
+            daemon_version,
+            capabilities,
+        } => {
+            if protocol_version != PROTOCOL_VERSION {
+                eyre::bail!(
+                    "{}",
+                    version_mismatch_message(&daemon_version, Some(protocol_version))
+                );
+            }
+            Ok(capabilities)
+        }
+        Response::Error { message } => eyre::bail!("Handshake failed: {message}"),
+        _ => eyre::bail!("Unexpected response to handshake"),
+    }
+}
+
+/// Connect to the daemon socket
+async fn connect() -> Result<UnixStream> {
     let socket_path = get_socket_path();
 
     // Connect to daemon (longer timeout for actual client requests)
-    let mut stream = tokio::time::timeout(
+    let stream = tokio::time::timeout(
         Duration::from_secs(CLIENT_CONNECT_TIMEOUT_SECS),
         UnixStream::connect(&socket_path),
     )
@@ -341,14 +528,69 @@ pub async fn send_request(request: Request) -> Result<Response> {
     })?;
 
     debug!("Connected to daemon at {:?}", socket_path);
+    Ok(stream)
+}
 
-    // Send request
-    write_message(&mut stream, &request).await?;
+/// Write a request and read the response on an open connection
+async fn exchange(stream: &mut UnixStream, request: &Request) -> Result<Response> {
+    write_message(stream, request).await?;
+    read_message(stream).await
+}
 
-    // Read response
-    let response: Response = read_message(&mut stream).await?;
+/// Send a single request without version diagnosis (used while diagnosing)
+async fn send_request_plain(request: &Request) -> Result<Response> {
+    let mut stream = connect().await?;
+    exchange(&mut stream, request).await
+}
+
+/// Explain a failed exchange if the running daemon is a different version
+///
+/// Tries `Hello` first, then falls back to `Status`, which every daemon version
+/// understands. Returns `None` if the daemon matches this build or cannot be queried.
+async fn diagnose_version_mismatch(request_kind: &str) -> Option<String> {
+    let hello = Request::Hello {
+        protocol_version: PROTOCOL_VERSION,
+    };
+    let (daemon_version, protocol_version) = match send_request_plain(&hello).await {
+        Ok(Response::Hello {
+            protocol_version,
+            daemon_version,
+            capabilities,
+        }) => {
+            if protocol_version == PROTOCOL_VERSION
+                && capabilities.iter().any(|c| c == request_kind)
+            {
+                return None;
+            }
+            (daemon_version, Some(protocol_version))
+        }
+        _ => match send_request_plain(&Request::Status).await {
+            Ok(Response::Status { version, .. }) => (version, None),
+            _ => return None,
+        },
+    };
 
-    Ok(response)
+    if daemon_version == crate::version_string() && protocol_version == Some(PROTOCOL_VERSION) {
+        return None;
+    }
+
+    Some(format!(
+        "{}\nThe '{request_kind}' request is not supported by the running daemon.",
+        version_mismatch_message(&daemon_version, protocol_version)
+    ))
+}
+
+/// Build the user-facing "restart the daemon" message
+fn version_mismatch_message(daemon_version: &str, protocol_version: Option<u32>) -> String {
+    let protocol = protocol_version.map_or_else(
+        || "unversioned protocol".to_string(),
+        |v| format!("protocol v{v}"),
+    );
+    format!(
+        "Daemon is version {daemon_version} ({protocol}), but this client is {} (protocol v{PROTOCOL_VERSION}).\n\
+         Restart it: pwsw shutdown && pwsw daemon",
+        crate::version_string()
+    )
 }
 
 // ============================================================================
@@ -428,10 +670,34 @@ impl Drop for IpcServer {
 
 /// Read a request from a client connection
 ///
+/// The outer `Result` reports transport failures. A well-framed message that is not a
+/// valid request (e.g., a request type from a newer client) yields an inner `Err`
+/// with a message suitable for a `Response::Error`, so the client gets a reply
+/// instead of a dropped connection.
+///
 /// # Errors
-/// Returns an error if reading fails or the message cannot be deserialized.
-pub async fn read_request(stream: &mut UnixStream) -> Result<Request> {
-    read_message(stream).await
+/// Returns an error if reading fails or the payload is not `JSON`.
+pub async fn read_request(stream: &mut UnixStream) -> Result<std::result::Result<Request, String>> {
+    let value: serde_json::Value = read_message(stream).await?;
+    Ok(parse_request(value))
+}
+
+/// Parse a raw `JSON` request, describing unknown or malformed requests
+fn parse_request(value: serde_json::Value) -> std::result::Result<Request, String> {
+    let kind = value
+        .get("type")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string);
+
+    serde_json::from_value(value).map_err(|e| match kind {
+        Some(kind) if !SUPPORTED_REQUESTS.contains(&kind.as_str()) => format!(
+            "Unsupported request type '{kind}': daemon is version {} (protocol v{PROTOCOL_VERSION}). \
+             If pwsw was upgraded, restart it: pwsw shutdown && pwsw daemon",
+            crate::version_string()
+        ),
+        Some(kind) => format!("Invalid '{kind}' request: {e}"),
+        None => format!("Invalid request (missing type): {e}"),
+    })
 }
 
 /// Write a response to a client connection
@@ -598,6 +864,137 @@ mod tests {
         assert!(matches!(deserialized, Request::Shutdown));
     }
 
+    #[test]
+    fn test_request_pause_roundtrip() {
+        let request = Request::Pause {
+            duration_secs: Some(600),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        let deserialized: Request = serde_json::from_str(&json).unwrap();
+        if let Request::Pause { duration_secs } = deserialized {
+            assert_eq!(duration_secs, Some(600));
+        } else {
+            panic!("Expected Pause variant");
+        }
+
+        // Duration is optional on the wire
+        let deserialized: Request = serde_json::from_str(r#"{"type":"Pause"}"#).unwrap();
+        assert!(matches!(
+            deserialized,
+            Request::Pause {
+                duration_secs: None
+            }
+        ));
+    }
+
+    #[test]
+    fn test_response_status_from_older_daemon() {
+        // Fields added later default when talking to an older daemon
+        let json = r#"{"type":"Status","version":"v0.6.0","uptime_secs":5,"current_sink":"Speakers","active_window":null,"tracked_windows":0}"#;
+        let deserialized: Response = serde_json::from_str(json).unwrap();
+        if let Response::Status {
+            paused,
+            pause_remaining_secs,
+            active_app_id,
+            ..
+        } = deserialized
+        {
+            assert!(!paused);
+            assert_eq!(pause_remaining_secs, None);
+            assert_eq!(active_app_id, None);
+        } else {
+            panic!("Expected Status variant");
+        }
+    }
+
+    #[test]
+    fn test_request_kind_listed_in_capabilities() {
+        let requests = [
+            Request::Hello {
+                protocol_version: PROTOCOL_VERSION,
+            },
+            Request::Status,
+            Request::ListWindows,
+            Request::Explain,
+            Request::TestRule {
+                pattern: String::new(),
+            },
+            Request::SetSink {
+                sink: String::new(),
+                hold: false,
+                hold_secs: None,
+            },
+            Request::CycleSink {
+                direction: crate::state::Direction::Next,
+            },
+            Request::Release,
+            Request::GetManagerInfo,
+            Request::Shutdown,
+            Request::Reload,
+            Request::Pause {
+                duration_secs: None,
+            },
+            Request::Resume,
+        ];
+        assert_eq!(requests.len(), SUPPORTED_REQUESTS.len());
+        for request in &requests {
+            assert!(SUPPORTED_REQUESTS.contains(&request.kind()));
+            // The wire tag must match kind()
+            let value = serde_json::to_value(request).unwrap();
+            assert_eq!(value["type"], request.kind());
+        }
+    }
+
+    #[test]
+    fn test_parse_request_unknown_type() {
+        let value = serde_json::json!({ "type": "FromTheFuture" });
+        let message = parse_request(value).unwrap_err();
+        assert!(message.contains("Unsupported request type 'FromTheFuture'"));
+        assert!(message.contains("restart"));
+    }
+
+    #[test]
+    fn test_parse_request_malformed_known_type() {
+        // TestRule requires a pattern
+        let value = serde_json::json!({ "type": "TestRule" });
+        let message = parse_request(value).unwrap_err();
+        assert!(message.contains("Invalid 'TestRule' request"));
+    }
+
+    #[tokio::test]
+    async fn test_read_request_reports_unknown_type() {
+        let (mut client, mut server) = UnixStream::pair().unwrap();
+        write_message(&mut client, &serde_json::json!({ "type": "FromTheFuture" }))
+            .await
+            .unwrap();
+
+        let result = read_request(&mut server).await.unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_response_hello_roundtrip() {
+        let response = Response::Hello {
+            protocol_version: PROTOCOL_VERSION,
+            daemon_version: "v0.6.0".to_string(),
+            capabilities: vec!["Status".to_string()],
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        let deserialized: Response = serde_json::from_str(&json).unwrap();
+        if let Response::Hello {
+            protocol_version,
+            daemon_version,
+            capabilities,
+        } = deserialized
+        {
+            assert_eq!(protocol_version, PROTOCOL_VERSION);
+            assert_eq!(daemon_version, "v0.6.0");
+            assert_eq!(capabilities, vec!["Status".to_string()]);
+        } else {
+            panic!("Expected Hello variant");
+        }
+    }
+
     // Response serialization roundtrip tests
     #[test]
     fn test_response_status_roundtrip() {
@@ -607,6 +1004,12 @@ mod tests {
             current_sink: "test_sink".to_string(),
             active_window: Some("firefox".to_string()),
             tracked_windows: 2,
+            active_app_id: Some("firefox".to_string()),
+            active_rule_desc: Some("Browser".to_string()),
+            paused: true,
+            pause_remaining_secs: Some(300),
+            hold_sink: Some("Headset".to_string()),
+            hold_remaining_secs: None,
         };
         let json = serde_json::to_string(&response).unwrap();
         let deserialized: Response = serde_json::from_str(&json).unwrap();
@@ -616,6 +1019,12 @@ mod tests {
             current_sink,
             active_window,
             tracked_windows,
+            active_app_id,
+            active_rule_desc,
+            paused,
+            pause_remaining_secs,
+            hold_sink,
+            hold_remaining_secs,
         } = deserialized
         {
             assert_eq!(version, "0.3.1");
@@ -623,6 +1032,12 @@ mod tests {
             assert_eq!(current_sink, "test_sink");
             assert_eq!(active_window, Some("firefox".to_string()));
             assert_eq!(tracked_windows, 2);
+            assert_eq!(active_app_id, Some("firefox".to_string()));
+            assert_eq!(active_rule_desc, Some("Browser".to_string()));
+            assert!(paused);
+            assert_eq!(pause_remaining_secs, Some(300));
+            assert_eq!(hold_sink, Some("Headset".to_string()));
+            assert_eq!(hold_remaining_secs, None);
         } else {
             panic!("Expected Status variant");
         }
@@ -682,4 +1097,43 @@ mod tests {
             panic!("Expected Windows variant");
         }
     }
+
+    #[test]
+    fn test_response_explanation_roundtrip() {
+        let response = Response::Explanation {
+            current_sink: "Speakers".to_string(),
+            target_sink: "Headphones".to_string(),
+            priority_mode: crate::state::PriorityMode::Index,
+            paused: false,
+            hold_sink: None,
+            windows: vec![ExplainedWindow {
+                id: 7,
+                app_id: "steam".to_string(),
+                title: "Steam".to_string(),
+                rule_index: 0,
+                rule_desc: "Steam".to_string(),
+                sink_name: "headphones".to_string(),
+                sink_desc: "Headphones".to_string(),
+                age_secs: 42,
+                verdict: crate::state::Verdict::Winner,
+            }],
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["priority_mode"], "index");
+        assert_eq!(json["windows"][0]["verdict"], "winner");
+
+        let deserialized: Response = serde_json::from_value(json).unwrap();
+        if let Response::Explanation {
+            target_sink,
+            windows,
+            ..
+        } = deserialized
+        {
+            assert_eq!(target_sink, "Headphones");
+            assert_eq!(windows.len(), 1);
+            assert_eq!(windows[0].age_secs, 42);
+        } else {
+            panic!("Expected Explanation variant");
+        }
+    }
 }
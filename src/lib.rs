@@ -20,14 +20,20 @@ pub mod cli;
 pub mod commands;
 pub mod compositor;
 pub mod config;
+pub mod config_backup;
+pub mod config_edit;
 pub mod daemon;
 pub mod daemon_manager;
+pub mod dbus;
+pub mod hooks;
 pub mod ipc;
 pub mod logging;
 pub mod notification;
 pub mod pipewire;
 pub mod state;
 pub mod style;
+pub mod template;
+pub mod trace;
 
 #[cfg(feature = "tui")]
 pub mod tui;
@@ -88,7 +94,88 @@ pub fn format_uptime(secs: u64) -> String {
     }
 }
 
+/// Parse a human duration such as "90", "90s", "15m", "2h" or "1h30m"
+///
+/// A bare number is interpreted as seconds.
+///
+/// # Errors
+/// Returns an error message if the string is empty, has an unknown unit, or is zero.
+pub fn parse_duration(input: &str) -> Result<std::time::Duration, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("duration is empty".to_string());
+    }
+    if let Ok(secs) = input.parse::<u64>() {
+        return if secs == 0 {
+            Err("duration must be greater than zero".to_string())
+        } else {
+            Ok(std::time::Duration::from_secs(secs))
+        };
+    }
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let multiplier = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            _ => {
+                return Err(format!(
+                    "invalid duration '{input}' (use e.g. 90s, 15m, 1h30m)"
+                ));
+            }
+        };
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| format!("invalid duration '{input}' (use e.g. 90s, 15m, 1h30m)"))?;
+        total = total.saturating_add(value.saturating_mul(multiplier));
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        return Err(format!(
+            "invalid duration '{input}' (missing unit after {digits})"
+        ));
+    }
+    if total == 0 {
+        return Err("duration must be greater than zero".to_string());
+    }
+    Ok(std::time::Duration::from_secs(total))
+}
+
 // Re-export commonly used types for convenience
 pub use cli::Args;
 pub use config::Config;
 pub use state::State;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use test_case::test_case;
+
+    #[test_case("90", 90 ; "bare seconds")]
+    #[test_case("45s", 45 ; "seconds")]
+    #[test_case("15m", 900 ; "minutes")]
+    #[test_case("2h", 7200 ; "hours")]
+    #[test_case("1h30m", 5400 ; "combined")]
+    fn test_parse_duration(input: &str, expected_secs: u64) {
+        assert_eq!(
+            parse_duration(input),
+            Ok(Duration::from_secs(expected_secs))
+        );
+    }
+
+    #[test_case("" ; "empty")]
+    #[test_case("0" ; "zero")]
+    #[test_case("10x" ; "unknown unit")]
+    #[test_case("1h30" ; "trailing digits")]
+    #[test_case("m" ; "unit without number")]
+    fn test_parse_duration_invalid(input: &str) {
+        assert!(parse_duration(input).is_err());
+    }
+}
@@ -1,33 +1,383 @@
 //! Desktop notifications
 //!
-//! Handles sending notifications via notify-rust and icon detection
-//! using `FreeDesktop` standard icon names.
+//! Handles sending notifications via notify-rust (or a user command) and icon
+//! detection using `FreeDesktop` standard icon names.
 
 use color_eyre::eyre::{Context, Result};
-use notify_rust::Notification;
+use notify_rust::{Notification, Urgency};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::config::{NotificationSettings, NotificationUrgency, Rule, SinkConfig};
+use crate::template::Template;
+
+/// Minimum timeout for notifications with action buttons (long enough to reach a button)
+const ACTION_MIN_TIMEOUT_MS: u32 = 8000;
+
+/// Switches closer together than this are coalesced into one notification
+const COALESCE_WINDOW: Duration = Duration::from_secs(3);
+
+/// Most sinks shown in a coalesced switch chain (oldest are dropped)
+const MAX_CHAIN_LEN: usize = 5;
+
+/// A notification ready to show
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationContent {
+    pub summary: String,
+    pub body: String,
+    /// Icon name (falls back to `audio-card`)
+    pub icon: Option<String>,
+    pub urgency: NotificationUrgency,
+    /// 0 = until dismissed
+    pub timeout_ms: u32,
+    /// Sound theme name hint
+    pub sound: Option<String>,
+    /// Command run instead of a desktop notification (`notifications.command`)
+    pub command: Option<Vec<String>>,
+}
+
+impl NotificationContent {
+    /// Content styled by the `[notifications]` settings
+    #[must_use]
+    pub fn new(
+        settings: &NotificationSettings,
+        summary: &str,
+        body: &str,
+        icon: Option<&str>,
+    ) -> Self {
+        Self {
+            summary: summary.to_string(),
+            body: body.to_string(),
+            icon: icon.map(str::to_string),
+            urgency: settings.urgency,
+            timeout_ms: settings.timeout_ms,
+            sound: settings.sound.clone(),
+            command: settings.command.clone(),
+        }
+    }
+}
 
-use crate::config::SinkConfig;
+/// Values for the placeholders in switch notification templates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwitchDetails {
+    pub sink_name: String,
+    pub sink_desc: String,
+    pub prev_sink_desc: String,
+    /// Why the switch happened (`None` for manual switches)
+    pub reason: Option<String>,
+    /// Window that triggered a rule switch
+    pub app_id: Option<String>,
+}
 
-/// Send a desktop notification
+/// Build a switch notification from the `[notifications]` templates
+///
+/// Manual switches (no reason) use `manual_body`. A rule's `notify_summary`,
+/// `notify_body` and `notify_urgency` take precedence over the settings.
+#[must_use]
+pub fn switch_notification(
+    settings: &NotificationSettings,
+    rule: Option<&Rule>,
+    details: &SwitchDetails,
+    icon: Option<&str>,
+) -> NotificationContent {
+    let values = [
+        ("sink_name", details.sink_name.as_str()),
+        ("sink_desc", details.sink_desc.as_str()),
+        ("prev_sink_desc", details.prev_sink_desc.as_str()),
+        ("reason", details.reason.as_deref().unwrap_or_default()),
+        ("app_id", details.app_id.as_deref().unwrap_or_default()),
+    ];
+    // Templates are validated with the config, so a parse error here means a bug;
+    // show the raw template rather than nothing
+    let render = |template: &str| {
+        Template::parse(template, crate::config::NOTIFICATION_PLACEHOLDERS)
+            .map_or_else(|_| template.to_string(), |t| t.render(&values))
+    };
+
+    let default_body = if details.reason.is_some() {
+        &settings.body
+    } else {
+        &settings.manual_body
+    };
+    let summary = rule
+        .and_then(|r| r.notify_summary.as_deref())
+        .unwrap_or(&settings.summary);
+    let body = rule
+        .and_then(|r| r.notify_body.as_deref())
+        .unwrap_or(default_body);
+
+    let mut content = NotificationContent::new(settings, &render(summary), &render(body), icon);
+    if let Some(urgency) = rule.and_then(|r| r.notify_urgency) {
+        content.urgency = urgency;
+    }
+    content
+}
+
+/// Send a desktop notification styled by the `[notifications]` settings
 ///
 /// # Errors
 /// Returns an error if the notification cannot be sent (e.g., no notification daemon running).
-pub fn send_notification(summary: &str, body: &str, icon: Option<&str>) -> Result<()> {
-    // Use provided icon, or fall back to generic audio icon
-    let icon = icon.unwrap_or("audio-card");
+pub fn send_notification(
+    settings: &NotificationSettings,
+    summary: &str,
+    body: &str,
+    icon: Option<&str>,
+) -> Result<()> {
+    let content = NotificationContent::new(settings, summary, body, icon);
+    show_notification(&content, None, &[], |_| {})?;
+    Ok(())
+}
+
+/// Button on a rule switch notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationAction {
+    /// Switch back to the sink that was active before
+    Undo,
+    /// Hold the new sink until released
+    KeepHere,
+}
+
+impl NotificationAction {
+    /// Action identifier sent to the notification server
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Undo => "undo",
+            Self::KeepHere => "keep-here",
+        }
+    }
+
+    /// Button label
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Undo => "Undo",
+            Self::KeepHere => "Keep here",
+        }
+    }
 
-    Notification::new()
-        .summary(summary)
-        .body(body)
+    /// Parse an action identifier (`None` for unknown ones, e.g. `"default"` or `"__closed"`)
+    #[must_use]
+    pub fn from_id(id: &str) -> Option<Self> {
+        [Self::Undo, Self::KeepHere]
+            .into_iter()
+            .find(|action| action.id() == id)
+    }
+}
+
+/// A clicked action on a switch notification, with the switch it belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwitchActionEvent {
+    pub action: NotificationAction,
+    /// Sink that was active before the switch
+    pub from_sink: String,
+    /// Sink the notification announced
+    pub to_sink: String,
+}
+
+/// Show a notification, returning its server ID
+///
+/// With `notifications.command` configured the command runs instead; it has no ID
+/// (`None`) and no action buttons.
+///
+/// # Errors
+/// Returns an error if the notification cannot be sent or the action thread cannot start.
+///
+/// With `replaces_id`, the notification replaces an earlier one instead of stacking.
+/// With `actions`, a background thread waits for the user and calls `on_action` with
+/// the clicked button; nothing is called if the notification is dismissed or expires.
+/// Servers that do not support actions get a plain notification.
+pub fn show_notification(
+    content: &NotificationContent,
+    replaces_id: Option<u32>,
+    actions: &[NotificationAction],
+    on_action: impl FnOnce(NotificationAction) + Send + 'static,
+) -> Result<Option<u32>> {
+    if let Some(command) = &content.command {
+        run_notification_command(command, content)?;
+        return Ok(None);
+    }
+
+    let with_actions = !actions.is_empty()
+        && notify_rust::get_capabilities().is_ok_and(|caps| caps.iter().any(|c| c == "actions"));
+    if !actions.is_empty() && !with_actions {
+        debug!("Notification server does not support actions, sending plain notification");
+    }
+
+    let timeout_ms = if with_actions && content.timeout_ms != 0 {
+        content.timeout_ms.max(ACTION_MIN_TIMEOUT_MS)
+    } else {
+        content.timeout_ms
+    };
+
+    let mut notification = Notification::new();
+    notification
+        .summary(&content.summary)
+        .body(&content.body)
         .appname("PWSW")
-        .icon(icon)
-        .timeout(3000)
-        .show()
-        .context("Failed to show notification")?;
+        // Use provided icon, or fall back to generic audio icon
+        .icon(content.icon.as_deref().unwrap_or("audio-card"))
+        .urgency(match content.urgency {
+            NotificationUrgency::Low => Urgency::Low,
+            NotificationUrgency::Normal => Urgency::Normal,
+            NotificationUrgency::Critical => Urgency::Critical,
+        })
+        // A zero duration never expires
+        .timeout(Duration::from_millis(u64::from(timeout_ms)));
+    if let Some(sound) = &content.sound {
+        notification.sound_name(sound);
+    }
+    if let Some(id) = replaces_id {
+        notification.id(id);
+    }
+    if with_actions {
+        for action in actions {
+            notification.action(action.id(), action.label());
+        }
+    }
+    let handle = notification.show().context("Failed to show notification")?;
+    let id = handle.id();
+
+    if with_actions {
+        std::thread::Builder::new()
+            .name("pwsw-notification".to_string())
+            .spawn(move || {
+                handle.wait_for_action(|action_id| {
+                    if let Some(action) = NotificationAction::from_id(action_id) {
+                        on_action(action);
+                    }
+                });
+            })
+            .context("Failed to spawn notification action thread")?;
+    }
 
+    Ok(Some(id))
+}
+
+/// Run `notifications.command` with the content filled into its arguments
+///
+/// The command is not waited for; a non-zero exit is logged from a background thread.
+fn run_notification_command(command: &[String], content: &NotificationContent) -> Result<()> {
+    let timeout_ms = content.timeout_ms.to_string();
+    let values = [
+        ("summary", content.summary.as_str()),
+        ("body", content.body.as_str()),
+        ("icon", content.icon.as_deref().unwrap_or("audio-card")),
+        ("urgency", content.urgency.name()),
+        ("timeout_ms", timeout_ms.as_str()),
+    ];
+    let args = command
+        .iter()
+        .map(|arg| {
+            Template::parse(arg, crate::config::NOTIFICATION_COMMAND_PLACEHOLDERS)
+                .map(|t| t.render(&values))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let Some((program, args)) = args.split_first() else {
+        color_eyre::eyre::bail!("Notification command is empty");
+    };
+
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run notification command '{program}'"))?;
+
+    let program = program.clone();
+    std::thread::Builder::new()
+        .name("pwsw-notify-command".to_string())
+        .spawn(move || match child.wait_with_output() {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => warn!(
+                "Notification failed: '{program}' exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => warn!("Notification failed: '{program}': {e}"),
+        })
+        .context("Failed to spawn notification command thread")?;
     Ok(())
 }
 
+/// Sends switch notifications as one replaced toast instead of a stack (daemon only)
+///
+/// Switches within a few seconds of each other are coalesced into a chain such as
+/// "Speakers → Headphones → Speakers".
+#[derive(Debug, Default)]
+pub struct SwitchNotifier {
+    last: Option<ShownSwitch>,
+}
+
+/// The switch notification currently on screen
+#[derive(Debug)]
+struct ShownSwitch {
+    id: Option<u32>,
+    at: Instant,
+    /// Sink descriptions, oldest first
+    chain: Vec<String>,
+}
+
+impl SwitchNotifier {
+    /// Notify about a switch from `from_desc` to `to_desc`
+    ///
+    /// `content` is shown as is for a lone switch; coalesced switches show the chain as
+    /// the body instead. `actions` and `on_action` work as for [`show_notification`].
+    ///
+    /// # Errors
+    /// Returns an error if the notification cannot be sent (e.g., no notification daemon running).
+    pub fn notify(
+        &mut self,
+        from_desc: &str,
+        to_desc: &str,
+        mut content: NotificationContent,
+        actions: &[NotificationAction],
+        on_action: impl FnOnce(NotificationAction) + Send + 'static,
+    ) -> Result<()> {
+        let (replaces_id, body) = self.coalesce(from_desc, to_desc, &content.body, Instant::now());
+        content.body = body;
+        let id = show_notification(&content, replaces_id, actions, on_action)?;
+        if let Some(last) = &mut self.last {
+            last.id = id;
+        }
+        Ok(())
+    }
+
+    /// Record a switch and decide which notification to replace and what it says
+    fn coalesce(
+        &mut self,
+        from_desc: &str,
+        to_desc: &str,
+        message: &str,
+        now: Instant,
+    ) -> (Option<u32>, String) {
+        let replaces_id = self.last.as_ref().and_then(|last| last.id);
+        match &mut self.last {
+            Some(last)
+                if now.duration_since(last.at) < COALESCE_WINDOW
+                    && last.chain.last().is_some_and(|desc| desc == from_desc) =>
+            {
+                last.at = now;
+                last.chain.push(to_desc.to_string());
+                if last.chain.len() > MAX_CHAIN_LEN {
+                    last.chain.remove(0);
+                }
+                (replaces_id, last.chain.join(" → "))
+            }
+            _ => {
+                self.last = Some(ShownSwitch {
+                    id: replaces_id,
+                    at: now,
+                    chain: vec![from_desc.to_string(), to_desc.to_string()],
+                });
+                (replaces_id, message.to_string())
+            }
+        }
+    }
+}
+
 /// Determine icon for a sink (custom or auto-detected using `FreeDesktop` standard names)
 #[must_use]
 pub fn get_sink_icon(sink: &SinkConfig) -> String {
@@ -104,7 +454,163 @@ pub fn get_app_icon(app_id: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::fixtures::{make_sink, make_sink_with_icon};
+    use crate::test_utils::fixtures::{make_rule, make_sink, make_sink_with_icon};
+
+    #[test]
+    fn test_notification_action_ids_roundtrip() {
+        for action in [NotificationAction::Undo, NotificationAction::KeepHere] {
+            assert_eq!(NotificationAction::from_id(action.id()), Some(action));
+        }
+        assert_eq!(NotificationAction::from_id("default"), None);
+        assert_eq!(NotificationAction::from_id("__closed"), None);
+    }
+
+    fn switch_details(reason: Option<&str>) -> SwitchDetails {
+        SwitchDetails {
+            sink_name: "hdmi".to_string(),
+            sink_desc: "HDMI".to_string(),
+            prev_sink_desc: "Speakers".to_string(),
+            reason: reason.map(str::to_string),
+            app_id: Some("steam".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_switch_notification_default_templates() {
+        let settings = NotificationSettings::default();
+
+        let content = switch_notification(&settings, None, &switch_details(Some("Gaming")), None);
+        assert_eq!(content.summary, "Audio Output");
+        assert_eq!(content.body, "HDMI → Gaming");
+        assert_eq!(content.timeout_ms, 3000);
+
+        let content = switch_notification(&settings, None, &switch_details(None), None);
+        assert_eq!(content.body, "HDMI");
+    }
+
+    #[test]
+    fn test_switch_notification_rule_overrides() {
+        let settings = NotificationSettings {
+            body: "{prev_sink_desc} → {sink_desc}".to_string(),
+            urgency: NotificationUrgency::Low,
+            ..NotificationSettings::default()
+        };
+        let mut rule = make_rule("steam", None, "hdmi");
+
+        let content = switch_notification(
+            &settings,
+            Some(&rule),
+            &switch_details(Some("Gaming")),
+            None,
+        );
+        assert_eq!(content.body, "Speakers → HDMI");
+        assert_eq!(content.urgency, NotificationUrgency::Low);
+
+        rule.notify_summary = Some("{app_id}".to_string());
+        rule.notify_body = Some("{reason} on {sink_name}".to_string());
+        rule.notify_urgency = Some(NotificationUrgency::Critical);
+        let content = switch_notification(
+            &settings,
+            Some(&rule),
+            &switch_details(Some("Gaming")),
+            None,
+        );
+        assert_eq!(content.summary, "steam");
+        assert_eq!(content.body, "Gaming on hdmi");
+        assert_eq!(content.urgency, NotificationUrgency::Critical);
+    }
+
+    #[test]
+    fn test_notification_command_receives_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out");
+        let settings = NotificationSettings {
+            command: Some(vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                format!(
+                    "printf '%s|%s|%s' \"$1\" \"$2\" \"$3\" > '{}'",
+                    out.display()
+                ),
+                "sh".to_string(),
+                "{summary}".to_string(),
+                "{body}".to_string(),
+                "{urgency}".to_string(),
+            ]),
+            ..NotificationSettings::default()
+        };
+
+        send_notification(&settings, "Audio Output", "HDMI → Gaming", None).unwrap();
+
+        for _ in 0..100 {
+            if std::fs::read_to_string(&out).is_ok_and(|s| !s.is_empty()) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "Audio Output|HDMI → Gaming|normal"
+        );
+    }
+
+    #[test]
+    fn test_notification_command_spawn_failure_is_an_error() {
+        let settings = NotificationSettings {
+            command: Some(vec!["/nonexistent/pwsw-notify".to_string()]),
+            ..NotificationSettings::default()
+        };
+        let err = send_notification(&settings, "Audio Output", "HDMI", None).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/pwsw-notify"));
+    }
+
+    #[test]
+    fn test_switch_notifier_coalesces_rapid_switches() {
+        let mut notifier = SwitchNotifier::default();
+        let start = Instant::now();
+
+        let (id, body) = notifier.coalesce("Speakers", "Headphones", "Headphones → Steam", start);
+        assert_eq!((id, body.as_str()), (None, "Headphones → Steam"));
+        notifier.last.as_mut().unwrap().id = Some(7);
+
+        let (id, body) = notifier.coalesce(
+            "Headphones",
+            "Speakers",
+            "Speakers → Steam closed",
+            start + Duration::from_secs(1),
+        );
+        assert_eq!(id, Some(7));
+        assert_eq!(body, "Speakers → Headphones → Speakers");
+    }
+
+    #[test]
+    fn test_switch_notifier_starts_over_after_window() {
+        let mut notifier = SwitchNotifier::default();
+        let start = Instant::now();
+        notifier.coalesce("Speakers", "Headphones", "Headphones", start);
+        notifier.last.as_mut().unwrap().id = Some(7);
+
+        // Too late to coalesce, but still replaces the old toast
+        let (id, body) = notifier.coalesce(
+            "Headphones",
+            "Speakers",
+            "Speakers",
+            start + COALESCE_WINDOW,
+        );
+        assert_eq!((id, body.as_str()), (Some(7), "Speakers"));
+    }
+
+    #[test]
+    fn test_switch_notifier_caps_chain_length() {
+        let mut notifier = SwitchNotifier::default();
+        let now = Instant::now();
+        let sinks = ["A", "B", "A", "B", "A", "B", "A"];
+        let mut body = String::new();
+        for pair in sinks.windows(2) {
+            body = notifier.coalesce(pair[0], pair[1], "", now).1;
+        }
+        assert_eq!(body, "A → B → A → B → A");
+    }
 
     #[test]
     fn test_get_sink_icon_custom_override() {
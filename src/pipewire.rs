@@ -181,6 +181,51 @@ pub struct ProfileSink {
     pub profile_name: String,
 }
 
+/// Words in node and profile descriptions that name the profile, not the output
+const PROFILE_WORDS: &[&str] = &[
+    "Analog", "Digital", "Stereo", "Mono", "Surround", "Output", "Pro", "4.0", "5.1", "7.1",
+];
+
+/// Short sink description for a new config, from a `PipeWire` node or profile description
+///
+/// Outputs named after a connector become that connector ("... Digital Stereo (HDMI 2)"
+/// → "HDMI 2"); otherwise the profile words every output of a card shares are dropped
+/// ("Built-in Audio Analog Stereo" → "Built-in Audio").
+#[must_use]
+pub fn suggested_sink_desc(description: &str) -> String {
+    let words: Vec<&str> = description
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c| c == '(' || c == ')'))
+        .collect();
+
+    // The last mention wins: "Navi HDMI Audio ... (HDMI 2)" names the port at the end
+    if let Some(i) = words
+        .iter()
+        .rposition(|w| matches!(*w, "HDMI" | "DisplayPort" | "IEC958" | "S/PDIF"))
+    {
+        let connector = if matches!(words[i], "IEC958" | "S/PDIF") {
+            "S/PDIF"
+        } else {
+            words[i]
+        };
+        return match words.get(i + 1) {
+            Some(n) if n.parse::<u32>().is_ok() => format!("{connector} {n}"),
+            _ => connector.to_string(),
+        };
+    }
+
+    let kept: Vec<&str> = words
+        .iter()
+        .copied()
+        .filter(|w| !w.is_empty() && !PROFILE_WORDS.contains(w))
+        .collect();
+    if kept.is_empty() {
+        description.trim_end_matches(" Output").trim().to_string()
+    } else {
+        kept.join(" ")
+    }
+}
+
 // ============================================================================
 // JSON Output Structures (for --list-sinks --json)
 // ============================================================================
@@ -217,6 +262,8 @@ pub struct ConfiguredSinkJson {
     pub desc: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glyph: Option<String>,
     pub is_default_config: bool,
     pub status: String,
 }
@@ -810,6 +857,24 @@ mod tests {
         assert_eq!(props.unwrap().metadata_name.as_deref(), Some("default"));
     }
 
+    #[test]
+    fn test_suggested_sink_desc() {
+        for (description, expected) in [
+            ("Built-in Audio Analog Stereo", "Built-in Audio"),
+            (
+                "Navi 21/23 HDMI/DP Audio Controller Digital Stereo (HDMI 2)",
+                "HDMI 2",
+            ),
+            ("Digital Stereo (HDMI) Output", "HDMI"),
+            ("Digital Stereo (IEC958) Output", "S/PDIF"),
+            ("Razer Kraken 7.1 Analog Surround 7.1", "Razer Kraken"),
+            ("Analog Stereo Output", "Analog Stereo"),
+            ("WH-1000XM4", "WH-1000XM4"),
+        ] {
+            assert_eq!(suggested_sink_desc(description), expected, "{description}");
+        }
+    }
+
     // get_active_sinks() tests
     #[test]
     fn test_get_active_sinks_filters_audio_sink() {
@@ -4,20 +4,148 @@
 //! for the daemon mode event loop.
 
 use color_eyre::eyre::{self, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::time::Instant;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tracing::{debug, info, warn};
 
 use crate::compositor::WindowEvent;
-use crate::config::{Config, Rule};
-use crate::notification::{get_app_icon, get_sink_icon, send_notification};
+use crate::config::{Config, HookEvent, Rule, SinkConfig};
+use crate::hooks::{HookEnv, run_hooks};
+use crate::notification::{
+    NotificationAction, NotificationContent, SwitchActionEvent, SwitchDetails, SwitchNotifier,
+    get_app_icon, get_sink_icon, show_notification, switch_notification,
+};
 use crate::pipewire::PipeWire;
 
 /// Error message for missing default sink (should be caught by config validation)
 const BUG_NO_DEFAULT_SINK: &str =
     "BUG: No default sink found (config validation should prevent this)";
 
+/// Direction for sink cycling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    Next,
+    Prev,
+}
+
+/// Manual sink switch requested by the user (`set-sink`, `next-sink`, `prev-sink`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManualRequest {
+    /// Switch to a sink by description, node name, or 1-indexed position (smart toggle applies)
+    Set(String),
+    /// Cycle through configured sinks
+    Cycle(Direction),
+}
+
+/// Planned outcome of a manual switch
+#[derive(Debug, PartialEq, Eq)]
+pub enum ManualSwitch<'a> {
+    /// Activate this sink
+    Switch(&'a SinkConfig),
+    /// Target is already active, nothing to do
+    AlreadyOn(&'a SinkConfig),
+    /// Fewer than two sinks configured
+    NothingToCycle,
+}
+
+impl ManualSwitch<'_> {
+    /// User-facing description of the outcome
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self {
+            Self::Switch(sink) => format!("Switched to: {}", sink.desc),
+            Self::AlreadyOn(sink) => format!("Already on: {}", sink.desc),
+            Self::NothingToCycle => "Only one sink configured, nothing to cycle".to_string(),
+        }
+    }
+}
+
+/// Decide what a manual switch should do given the currently active sink
+///
+/// Shared by the daemon and the direct (no daemon) fallback so smart toggle and
+/// cycling behave identically either way. `smart_toggle` is the `set_smart_toggle`
+/// setting, turned off for `set-sink --hold` so the requested sink is the one held.
+///
+/// # Errors
+/// Returns an error if the sink reference does not match any configured sink.
+pub fn plan_manual_switch<'a>(
+    config: &'a Config,
+    current: &str,
+    request: &ManualRequest,
+    smart_toggle: bool,
+) -> Result<ManualSwitch<'a>> {
+    match request {
+        ManualRequest::Set(sink_ref) => {
+            let target = config.resolve_sink(sink_ref).ok_or_else(|| {
+                let available: Vec<_> = config
+                    .sinks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, s)| format!("{}. '{}'", i + 1, s.desc))
+                    .collect();
+                eyre::eyre!(
+                    "Unknown sink '{}'. Available: {}",
+                    sink_ref,
+                    available.join(", ")
+                )
+            })?;
+
+            let default = config
+                .get_default_sink()
+                .ok_or_else(|| eyre::eyre!("No default sink configured"))?;
+
+            if smart_toggle && current == target.name {
+                if target.name == default.name {
+                    return Ok(ManualSwitch::AlreadyOn(default));
+                }
+                info!("Toggle → default: {}", default.desc);
+                return Ok(ManualSwitch::Switch(default));
+            }
+
+            Ok(ManualSwitch::Switch(target))
+        }
+        ManualRequest::Cycle(direction) => {
+            // Need at least 2 sinks to cycle
+            if config.sinks.len() < 2 {
+                return Ok(ManualSwitch::NothingToCycle);
+            }
+
+            // Find current sink's index in config, or start from default
+            let current_index = config
+                .sinks
+                .iter()
+                .position(|s| s.name == current)
+                .unwrap_or_else(|| config.sinks.iter().position(|s| s.default).unwrap_or(0));
+
+            // Calculate next index with wrapping
+            let next_index = match direction {
+                Direction::Next => (current_index + 1) % config.sinks.len(),
+                Direction::Prev => {
+                    if current_index == 0 {
+                        config.sinks.len() - 1
+                    } else {
+                        current_index - 1
+                    }
+                }
+            };
+
+            let target = &config.sinks[next_index];
+
+            // Already on target (shouldn't happen with >= 2 sinks, but be safe)
+            if target.name == current {
+                return Ok(ManualSwitch::AlreadyOn(target));
+            }
+
+            info!("Cycling to: {}", target.desc);
+            Ok(ManualSwitch::Switch(target))
+        }
+    }
+}
+
 /// Main application state for daemon mode
 pub struct State {
     pub config: Arc<Config>,
@@ -29,6 +157,80 @@ pub struct State {
     all_windows: HashMap<u64, (String, String)>, // (app_id, title)
     /// Lookup table for fast sink description retrieval (sink name -> description)
     sink_lookup: HashMap<String, String>,
+    /// Automatic switching paused (windows are still tracked)
+    paused: bool,
+    /// When a timed pause expires (`None` = paused until resumed)
+    pause_until: Option<Instant>,
+    /// Manual override set by `set-sink --hold` (suppresses rules until released)
+    manual_hold: Option<ManualHold>,
+    /// Where sink switches are applied
+    audio_backend: AudioBackend,
+    /// Receives Undo/Keep here clicks; rule switch notifications only get buttons when set
+    notification_actions: Option<mpsc::UnboundedSender<SwitchActionEvent>>,
+    /// Replaces and coalesces switch notifications (used from `spawn_blocking`)
+    switch_notifier: Arc<Mutex<SwitchNotifier>>,
+}
+
+/// Where `State` applies sink switches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioBackend {
+    /// Activate sinks through `PipeWire` and send notifications
+    PipeWire,
+    /// Only record the new sink in state (offline `simulate`)
+    NoOp,
+}
+
+/// Manual sink override that takes precedence over window rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManualHold {
+    pub sink_name: String,
+    /// When the hold expires (`None` = held until released)
+    pub until: Option<Instant>,
+}
+
+impl ManualHold {
+    /// Whether the hold is still in effect
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.until.is_none_or(|until| Instant::now() < until)
+    }
+}
+
+/// What led to a sink switch (for notifications and hooks)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct SwitchCause {
+    /// Rule description or window change (`None` = manual switch)
+    context: Option<String>,
+    /// Window that triggered a rule switch
+    app_id: Option<String>,
+    /// Rule whose notification overrides apply
+    rule_index: Option<usize>,
+}
+
+/// How competing tracked windows are ranked (`match_by_index` setting)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityMode {
+    /// Most recently opened window wins
+    Time,
+    /// Lowest rule index wins, most recent window breaks ties
+    Index,
+}
+
+/// Outcome of a tracked window when choosing the target sink (for `explain`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// This window decided the target sink
+    Winner,
+    /// A manual hold overrides every rule
+    Held,
+    /// Another window was opened more recently (time-based priority)
+    OlderWindow,
+    /// The winner matched a rule earlier in the config (index-based priority)
+    LowerPriorityRule,
+    /// The winner matched the same rule but was opened more recently
+    OlderSameRule,
 }
 
 /// Tracked window that matched a rule
@@ -83,9 +285,50 @@ impl State {
             active_windows: HashMap::new(),
             all_windows: HashMap::new(),
             sink_lookup,
+            paused: false,
+            pause_until: None,
+            manual_hold: None,
+            audio_backend: AudioBackend::PipeWire,
+            notification_actions: None,
+            switch_notifier: Arc::default(),
         })
     }
 
+    /// Create state for offline simulation, starting on the configured default sink
+    ///
+    /// Uses [`AudioBackend::NoOp`], so no `PipeWire` queries, switches or notifications happen.
+    ///
+    /// # Panics
+    /// Panics if no default sink is configured (should be prevented by config validation).
+    #[must_use]
+    pub fn new_offline(config: Arc<Config>) -> Self {
+        let current_sink_name = config
+            .get_default_sink()
+            .expect(BUG_NO_DEFAULT_SINK)
+            .name
+            .clone();
+        let sink_lookup = config
+            .sinks
+            .iter()
+            .map(|s| (s.name.clone(), s.desc.clone()))
+            .collect();
+
+        Self {
+            config,
+            current_sink_name,
+            daemon_manager: crate::daemon_manager::DaemonManager::Direct,
+            active_windows: HashMap::new(),
+            all_windows: HashMap::new(),
+            sink_lookup,
+            paused: false,
+            pause_until: None,
+            manual_hold: None,
+            audio_backend: AudioBackend::NoOp,
+            notification_actions: None,
+            switch_notifier: Arc::default(),
+        }
+    }
+
     /// Create a State for testing without `PipeWire` dependency
     #[cfg(test)]
     pub(crate) fn new_for_testing(config: Arc<Config>, current_sink_name: String) -> Self {
@@ -103,6 +346,71 @@ impl State {
             active_windows: HashMap::new(),
             all_windows: HashMap::new(),
             sink_lookup,
+            paused: false,
+            pause_until: None,
+            manual_hold: None,
+            audio_backend: AudioBackend::PipeWire,
+            notification_actions: None,
+            switch_notifier: Arc::default(),
+        }
+    }
+
+    /// Add Undo and Keep here buttons to rule switch notifications, sending clicks to `tx`
+    ///
+    /// Clicks should be passed back to [`State::handle_notification_action`].
+    pub fn enable_notification_actions(&mut self, tx: mpsc::UnboundedSender<SwitchActionEvent>) {
+        self.notification_actions = Some(tx);
+    }
+
+    /// Apply an Undo or Keep here click from a rule switch notification
+    ///
+    /// Ignored if the sink has changed since the notification was shown. Returns a
+    /// description of what happened, for logging.
+    ///
+    /// # Errors
+    /// Returns an error if switching back fails.
+    pub async fn handle_notification_action(&mut self, event: SwitchActionEvent) -> Result<String> {
+        if self.current_sink_name != event.to_sink {
+            return Ok(format!(
+                "Ignoring {} for {}: sink changed since",
+                event.action.label(),
+                event.to_sink
+            ));
+        }
+
+        match event.action {
+            NotificationAction::Undo => {
+                // Go back by node name: the previous sink need not be configured, and
+                // smart toggle must not apply
+                let name = event.from_sink;
+                let sink = self.config.sinks.iter().find(|s| s.name == name);
+                let icon = sink.map(get_sink_icon);
+                let desc = self.sink_lookup.get(&name).unwrap_or(&name).clone();
+                let notify = self.config.settings.notify_manual;
+                self.activate_sink(
+                    name.clone(),
+                    desc.clone(),
+                    icon,
+                    notify,
+                    SwitchCause::default(),
+                )
+                .await?;
+
+                // Like any manual switch, an active hold (and its deadline) stays in
+                // effect on the restored sink
+                if let Some(hold) = self.manual_hold.as_mut().filter(|h| h.is_active()) {
+                    hold.sink_name = name;
+                }
+                Ok(format!("Switched back to: {desc}"))
+            }
+            NotificationAction::KeepHere => {
+                self.hold(event.to_sink, None);
+                let desc = self
+                    .sink_lookup
+                    .get(&self.current_sink_name)
+                    .unwrap_or(&self.current_sink_name);
+                Ok(format!("Holding {desc} until released"))
+            }
         }
     }
 
@@ -111,6 +419,17 @@ impl State {
         info!("Applying new configuration");
         self.config = new_config;
 
+        // Drop a manual hold on a sink that no longer exists
+        if let Some(hold) = &self.manual_hold
+            && !self.config.sinks.iter().any(|s| s.name == hold.sink_name)
+        {
+            warn!(
+                "Held sink {} removed from config, releasing hold",
+                hold.sink_name
+            );
+            self.manual_hold = None;
+        }
+
         // Rebuild sink lookup table
         self.sink_lookup = self
             .config
@@ -159,11 +478,128 @@ impl State {
     }
 
     /// Check if switching to a new sink is needed
+    ///
+    /// Always `false` while automatic switching is paused. While a manual hold is
+    /// active, only the held sink is a valid target.
     #[must_use]
     pub fn should_switch_sink(&self, new_sink_name: &str) -> bool {
+        if self.is_paused() {
+            return false;
+        }
+        if let Some(hold) = self.active_hold()
+            && hold.sink_name != new_sink_name
+        {
+            return false;
+        }
         self.current_sink_name != new_sink_name
     }
 
+    /// Hold a sink against rule-based switching, optionally for a limited time
+    pub fn hold(&mut self, sink_name: String, duration: Option<Duration>) {
+        if let Some(d) = duration {
+            info!(
+                "Manual hold on {} for {}",
+                sink_name,
+                crate::format_uptime(d.as_secs())
+            );
+        } else {
+            info!("Manual hold on {} until released", sink_name);
+        }
+        self.manual_hold = Some(ManualHold {
+            sink_name,
+            until: duration.map(|d| Instant::now() + d),
+        });
+    }
+
+    /// The manual hold, if one is in effect
+    #[must_use]
+    pub fn active_hold(&self) -> Option<&ManualHold> {
+        self.manual_hold.as_ref().filter(|h| h.is_active())
+    }
+
+    /// When the current timed hold expires (`None` if not held or held until released)
+    #[must_use]
+    pub fn hold_deadline(&self) -> Option<Instant> {
+        self.manual_hold.as_ref().and_then(|h| h.until)
+    }
+
+    /// Time left on a timed hold (`None` if not held or held until released)
+    #[must_use]
+    pub fn hold_remaining(&self) -> Option<Duration> {
+        self.active_hold()
+            .and_then(|h| h.until)
+            .map(|until| until.saturating_duration_since(Instant::now()))
+    }
+
+    /// Release the manual hold and switch to whatever the rules select
+    ///
+    /// Returns `false` if no hold was in effect.
+    ///
+    /// # Errors
+    /// Returns an error if sink activation fails.
+    pub async fn release_hold(&mut self) -> Result<bool> {
+        let Some(hold) = self.manual_hold.take() else {
+            return Ok(false);
+        };
+        info!("Manual hold on {} released", hold.sink_name);
+
+        let target = self.determine_target_sink();
+        if self.should_switch_sink(&target) {
+            self.switch_to_target(target, "Manual hold released")
+                .await?;
+        }
+        Ok(true)
+    }
+
+    /// Pause automatic switching, optionally for a limited time
+    pub fn pause(&mut self, duration: Option<Duration>) {
+        self.paused = true;
+        self.pause_until = duration.map(|d| Instant::now() + d);
+        if let Some(d) = duration {
+            info!(
+                "Automatic switching paused for {}",
+                crate::format_uptime(d.as_secs())
+            );
+        } else {
+            info!("Automatic switching paused");
+        }
+    }
+
+    /// Whether automatic switching is currently paused
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused && self.pause_until.is_none_or(|until| Instant::now() < until)
+    }
+
+    /// When the current timed pause expires (`None` if not paused or paused indefinitely)
+    #[must_use]
+    pub fn pause_deadline(&self) -> Option<Instant> {
+        self.pause_until.filter(|_| self.paused)
+    }
+
+    /// Time left on a timed pause (`None` if not paused or paused indefinitely)
+    #[must_use]
+    pub fn pause_remaining(&self) -> Option<Duration> {
+        self.pause_deadline()
+            .map(|until| until.saturating_duration_since(Instant::now()))
+    }
+
+    /// Resume automatic switching and immediately switch to the current target
+    ///
+    /// # Errors
+    /// Returns an error if sink activation fails.
+    pub async fn resume(&mut self) -> Result<()> {
+        self.paused = false;
+        self.pause_until = None;
+        info!("Automatic switching resumed");
+
+        let target = self.determine_target_sink();
+        if self.should_switch_sink(&target) {
+            self.switch_to_target(target, "Switching resumed").await?;
+        }
+        Ok(())
+    }
+
     /// Update the current sink name in state
     pub fn update_sink(&mut self, new_sink_name: String) {
         debug!("State: {} → {}", self.current_sink_name, new_sink_name);
@@ -172,7 +608,7 @@ impl State {
 
     /// Determine target sink based on active windows
     ///
-    /// Priority depends on `match_by_index` setting:
+    /// An active manual hold always wins. Otherwise priority depends on `match_by_index` setting:
     /// - `false` (default): Most recently opened window wins
     /// - `true`: Lowest rule index (highest priority) wins, with most recent as tiebreaker
     ///
@@ -180,18 +616,14 @@ impl State {
     /// Panics if no default sink is configured (should be prevented by config validation).
     #[must_use]
     pub fn determine_target_sink(&self) -> String {
-        let winner = if self.config.settings.match_by_index {
-            // Index-based priority: lower index = higher priority
-            // Tiebreaker: most recent window wins when rule indices are equal
-            self.active_windows.iter().min_by(|(_, a), (_, b)| {
-                a.rule_index
-                    .cmp(&b.rule_index)
-                    .then_with(|| b.opened_at.cmp(&a.opened_at))
-            })
-        } else {
-            // Time-based priority: most recent window wins
-            self.active_windows.iter().max_by_key(|(_, w)| w.opened_at)
-        };
+        if let Some(hold) = self.active_hold() {
+            return hold.sink_name.clone();
+        }
+
+        let winner = self
+            .active_windows
+            .values()
+            .min_by(|a, b| self.compare_priority(a, b));
 
         winner.map_or_else(
             || {
@@ -201,10 +633,67 @@ impl State {
                     .name
                     .clone()
             },
-            |(_, w)| w.sink_name.clone(),
+            |w| w.sink_name.clone(),
         )
     }
 
+    /// Priority mode selected by the `match_by_index` setting
+    #[must_use]
+    pub fn priority_mode(&self) -> PriorityMode {
+        if self.config.settings.match_by_index {
+            PriorityMode::Index
+        } else {
+            PriorityMode::Time
+        }
+    }
+
+    /// Order two tracked windows by priority (`Less` = `a` wins over `b`)
+    fn compare_priority(&self, a: &ActiveWindow, b: &ActiveWindow) -> Ordering {
+        match self.priority_mode() {
+            // Index-based priority: lower index = higher priority
+            // Tiebreaker: most recent window wins when rule indices are equal
+            PriorityMode::Index => a
+                .rule_index
+                .cmp(&b.rule_index)
+                .then_with(|| b.opened_at.cmp(&a.opened_at)),
+            // Time-based priority: most recent window wins
+            PriorityMode::Time => b.opened_at.cmp(&a.opened_at),
+        }
+    }
+
+    /// Rank tracked windows by priority and explain each one's outcome (for `explain`)
+    ///
+    /// The first entry is the window `determine_target_sink` picks, unless a manual
+    /// hold is active, in which case every window is marked [`Verdict::Held`].
+    #[must_use]
+    pub fn explain_windows(&self) -> Vec<(u64, &ActiveWindow, Verdict)> {
+        let mut ranked: Vec<_> = self.active_windows.iter().collect();
+        ranked.sort_by(|(id_a, a), (id_b, b)| self.compare_priority(a, b).then(id_a.cmp(id_b)));
+
+        let held = self.active_hold().is_some();
+        let winner_rule = ranked.first().map(|(_, w)| w.rule_index);
+        ranked
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (id, w))| {
+                let verdict = if held {
+                    Verdict::Held
+                } else if rank == 0 {
+                    Verdict::Winner
+                } else {
+                    match self.priority_mode() {
+                        PriorityMode::Index if winner_rule == Some(w.rule_index) => {
+                            Verdict::OlderSameRule
+                        }
+                        PriorityMode::Index => Verdict::LowerPriorityRule,
+                        PriorityMode::Time => Verdict::OlderWindow,
+                    }
+                };
+                (*id, w, verdict)
+            })
+            .collect()
+    }
+
     /// Check if a window is currently tracked
     #[must_use]
     pub fn is_window_tracked(&self, id: u64) -> bool {
@@ -321,26 +810,14 @@ impl State {
                         let target_sink = self.config.sinks.iter().find(|s| s.name == target);
                         let desc = target_sink.map_or(target.as_str(), |s| s.desc.as_str());
 
-                        // Run blocking activation
-                        let target_clone = target.clone();
-                        let desc_clone = desc.to_string();
-                        let app_icon_clone = app_icon.clone();
-                        let trigger_desc_clone = trigger_desc.clone();
-
-                        let join = tokio::task::spawn_blocking(move || {
-                            crate::state::switch_audio_blocking(
-                                &target_clone,
-                                &desc_clone,
-                                Some(&trigger_desc_clone),
-                                Some(&app_icon_clone),
-                                notify,
-                            )
-                        });
-
-                        let inner = join.await.map_err(|e| eyre::eyre!("Join error: {e:#}"))?;
-                        inner?;
-
-                        self.update_sink(target);
+                        let desc = desc.to_string();
+                        let cause = SwitchCause {
+                            context: Some(trigger_desc),
+                            app_id: Some(app_id.to_string()),
+                            rule_index: Some(rule_index),
+                        };
+                        self.activate_sink(target, desc, Some(app_icon), notify, cause)
+                            .await?;
                     }
                 }
             } else {
@@ -354,33 +831,27 @@ impl State {
                     app_id.to_string(),
                     title.to_string(),
                 );
+                self.run_hooks(
+                    HookEvent::WindowTracked,
+                    &HookEnv {
+                        sink_name: Some(sink_name.clone()),
+                        sink_desc: Some(sink_desc.clone()),
+                        reason: Some(trigger_desc.clone()),
+                        app_id: Some(app_id.to_string()),
+                    },
+                );
 
                 if self.should_switch_sink(&sink_name) {
                     let notify = rule_notify.unwrap_or(self.config.settings.notify_rules);
                     // Use `app_id` as icon (e.g., "steam" shows Steam icon)
                     let app_icon = get_app_icon(app_id);
-                    // Run blocking PipeWire activation in spawn_blocking to avoid blocking tokio runtime
-                    let sink_to_activate = sink_name.clone();
-                    let desc_clone = sink_desc.clone();
-                    let app_icon_clone = app_icon.clone();
-                    let custom_desc = trigger_desc.clone();
-
-                    let app_icon_str = app_icon_clone.clone();
-                    let join = tokio::task::spawn_blocking(move || {
-                        crate::state::switch_audio_blocking(
-                            &sink_to_activate,
-                            &desc_clone,
-                            Some(&custom_desc),
-                            Some(app_icon_str.as_str()),
-                            notify,
-                        )
-                    });
-
-                    let inner = join.await.map_err(|e| eyre::eyre!("Join error: {e:#}"))?;
-                    inner?;
-
-                    // Only update state on success
-                    self.update_sink(sink_name);
+                    let cause = SwitchCause {
+                        context: Some(trigger_desc),
+                        app_id: Some(app_id.to_string()),
+                        rule_index: Some(rule_index),
+                    };
+                    self.activate_sink(sink_name, sink_desc, Some(app_icon), notify, cause)
+                        .await?;
                 }
             }
         } else if was_tracked {
@@ -469,6 +940,60 @@ impl State {
             .collect()
     }
 
+    /// Perform a manual switch (`set-sink`/`next-sink`/`prev-sink` routed through the daemon)
+    ///
+    /// Refreshes the current sink from `PipeWire` first, so smart toggle still works if
+    /// the default was changed outside pwsw. With `hold` (inner `None` = until released),
+    /// smart toggle is skipped and the requested sink is held, even if it was already
+    /// active. Returns a user-facing message.
+    ///
+    /// # Errors
+    /// Returns an error if the sink reference is invalid or activation fails.
+    pub async fn manual_switch(
+        &mut self,
+        request: &ManualRequest,
+        hold: Option<Option<Duration>>,
+    ) -> Result<String> {
+        if self.audio_backend == AudioBackend::PipeWire {
+            match tokio::task::spawn_blocking(PipeWire::get_default_sink_name).await {
+                Ok(Ok(name)) => self.current_sink_name = name,
+                Ok(Err(e)) => warn!("Could not refresh current sink, using cached: {e:#}"),
+                Err(e) => warn!("Join error refreshing current sink: {e:#}"),
+            }
+        }
+
+        let config = Arc::clone(&self.config);
+        let smart_toggle = config.settings.set_smart_toggle && hold.is_none();
+        let plan = plan_manual_switch(&config, &self.current_sink_name, request, smart_toggle)?;
+
+        if let ManualSwitch::Switch(target) = plan {
+            let name = target.name.clone();
+            let desc = target.desc.clone();
+            let icon = get_sink_icon(target);
+            let notify = config.settings.notify_manual;
+
+            self.activate_sink(name, desc, Some(icon), notify, SwitchCause::default())
+                .await?;
+
+            // A manual switch during a hold moves the hold to the new sink
+            if let Some(hold) = self.manual_hold.as_mut().filter(|h| h.is_active()) {
+                hold.sink_name.clone_from(&target.name);
+            }
+        }
+
+        let Some(duration) = hold else {
+            return Ok(plan.message());
+        };
+        if let ManualSwitch::Switch(target) | ManualSwitch::AlreadyOn(target) = plan {
+            self.hold(target.name.clone(), duration);
+        }
+        let held = duration.map_or_else(
+            || "held until released".to_string(),
+            |d| format!("held for {}", crate::format_uptime(d.as_secs())),
+        );
+        Ok(format!("{} ({held})", plan.message()))
+    }
+
     /// Helper to switch to target sink with notification logic for window state changes
     async fn switch_to_target(&mut self, target: String, context: &str) -> Result<()> {
         let target_sink = self.config.sinks.iter().find(|s| s.name == target);
@@ -478,31 +1003,115 @@ impl State {
         let is_default = default_sink.name == target;
         let notify = self.config.settings.notify_rules && is_default;
 
-        // Run blocking activation inside spawn_blocking
-        let target_clone = target.clone();
-        let desc_clone = desc.to_string();
-        let icon_clone = icon.clone();
-        let context_clone = context.to_string();
-
-        let join = tokio::task::spawn_blocking(move || {
-            crate::state::switch_audio_blocking(
-                &target_clone,
-                &desc_clone,
-                Some(&context_clone),
-                icon_clone.as_deref(),
-                notify,
-            )
-        });
+        let desc = desc.to_string();
+        let cause = SwitchCause {
+            context: Some(context.to_string()),
+            ..SwitchCause::default()
+        };
+        self.activate_sink(target, desc, icon, notify, cause).await
+    }
 
-        let inner = join.await.map_err(|e| eyre::eyre!("Join error: {e:#}"))?;
-        inner?;
+    /// Activate a sink through the audio backend, updating state only on success
+    ///
+    /// `PipeWire` activation is blocking, so it runs inside `spawn_blocking`. The
+    /// notification (rendered from the `[notifications]` templates and the triggering
+    /// rule's overrides) replaces the previous switch toast. `switch` hooks start once
+    /// the sink is active.
+    async fn activate_sink(
+        &mut self,
+        name: String,
+        desc: String,
+        icon: Option<String>,
+        notify: bool,
+        cause: SwitchCause,
+    ) -> Result<()> {
+        let SwitchCause {
+            context,
+            app_id,
+            rule_index,
+        } = cause;
+        let hook_env = HookEnv {
+            sink_name: Some(name.clone()),
+            sink_desc: Some(desc.clone()),
+            reason: Some(
+                context
+                    .clone()
+                    .unwrap_or_else(|| "Manual switch".to_string()),
+            ),
+            app_id: app_id.clone(),
+        };
+
+        if self.audio_backend == AudioBackend::PipeWire {
+            // Rule switches get Undo/Keep here buttons when the daemon handles them
+            let actions = self
+                .notification_actions
+                .clone()
+                .filter(|_| app_id.is_some())
+                .map(|tx| (tx, self.current_sink_name.clone()));
+            let from_desc = self
+                .sink_lookup
+                .get(&self.current_sink_name)
+                .unwrap_or(&self.current_sink_name)
+                .clone();
+            let content = (notify && self.config.notifications.switch).then(|| {
+                let details = SwitchDetails {
+                    sink_name: name.clone(),
+                    sink_desc: desc.clone(),
+                    prev_sink_desc: from_desc.clone(),
+                    reason: context.clone(),
+                    app_id,
+                };
+                let rule = rule_index.and_then(|idx| self.config.rules.get(idx));
+                switch_notification(&self.config.notifications, rule, &details, icon.as_deref())
+            });
+            let notifier = Arc::clone(&self.switch_notifier);
+            let target = name.clone();
+            let join = tokio::task::spawn_blocking(move || -> Result<()> {
+                switch_audio_blocking(&target, &desc, context.as_deref(), None)?;
+                let Some(content) = content else {
+                    return Ok(());
+                };
+
+                let buttons: &[NotificationAction] = if actions.is_some() {
+                    &[NotificationAction::Undo, NotificationAction::KeepHere]
+                } else {
+                    &[]
+                };
+                let result = notifier
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .notify(&from_desc, &desc, content, buttons, move |action| {
+                        if let Some((tx, from_sink)) = actions {
+                            let _ = tx.send(SwitchActionEvent {
+                                action,
+                                from_sink,
+                                to_sink: target,
+                            });
+                        }
+                    });
+                if let Err(e) = result {
+                    warn!("Notification failed: {}", e);
+                }
+                Ok(())
+            });
+            let inner = join.await.map_err(|e| eyre::eyre!("Join error: {e:#}"))?;
+            inner?;
+        }
 
-        self.update_sink(target);
+        self.update_sink(name);
+        self.run_hooks(HookEvent::Switch, &hook_env);
         Ok(())
     }
+
+    /// Run the configured hooks for an event (never in offline simulation)
+    pub fn run_hooks(&self, event: HookEvent, env: &HookEnv) {
+        if self.audio_backend == AudioBackend::PipeWire {
+            run_hooks(&self.config.hooks, event, env);
+        }
+    }
 }
 
-/// Switch audio output and optionally notify
+/// Switch audio output and optionally show `notification`
 ///
 /// # Errors
 /// Returns an error if `PipeWire` sink activation fails.
@@ -510,8 +1119,7 @@ pub fn switch_audio_blocking(
     name: &str,
     desc: &str,
     custom_desc: Option<&str>,
-    icon: Option<&str>,
-    notify: bool,
+    notification: Option<&NotificationContent>,
 ) -> Result<()> {
     if let Some(reason) = custom_desc {
         info!("Switching: {} ({}) [Reason: {}]", desc, name, reason);
@@ -520,14 +1128,10 @@ pub fn switch_audio_blocking(
     }
     PipeWire::activate_sink(name)?;
 
-    if notify {
-        let message = match custom_desc {
-            Some(d) => format!("{desc} → {d}"),
-            None => desc.to_string(),
-        };
-        if let Err(e) = send_notification("Audio Output", &message, icon) {
-            warn!("Notification failed: {}", e);
-        }
+    if let Some(content) = notification
+        && let Err(e) = show_notification(content, None, &[], |_| {})
+    {
+        warn!("Notification failed: {}", e);
     }
 
     Ok(())
@@ -598,6 +1202,84 @@ mod tests {
         assert!(!state.should_switch_sink("sink1"));
     }
 
+    #[test]
+    fn test_should_switch_sink_paused_returns_false() {
+        let config = make_config(vec![make_sink("sink1", "Sink 1", true)], vec![]);
+        let mut state = State::new_for_testing(Arc::new(config), "sink1".to_string());
+
+        state.pause(None);
+        assert!(state.is_paused());
+        assert!(!state.should_switch_sink("sink2"));
+    }
+
+    #[test]
+    fn test_manual_hold_overrides_rules() {
+        let config = make_config(
+            vec![
+                make_sink("speakers", "Speakers", true),
+                make_sink("headphones", "Headphones", false),
+                make_sink("headset", "Headset", false),
+            ],
+            vec![],
+        );
+        let mut state = State::new_for_testing(Arc::new(config), "headset".to_string());
+        state.track_window(
+            1,
+            "headphones".to_string(),
+            "Music".to_string(),
+            0,
+            "mpv".to_string(),
+            "Song".to_string(),
+        );
+
+        state.hold("headset".to_string(), None);
+        assert_eq!(state.determine_target_sink(), "headset");
+        // Rules cannot move away from the held sink
+        assert!(!state.should_switch_sink("headphones"));
+        assert_eq!(state.hold_remaining(), None);
+
+        // Expired hold no longer applies
+        state.manual_hold = Some(ManualHold {
+            sink_name: "headset".to_string(),
+            until: Some(Instant::now().checked_sub(Duration::from_secs(1)).unwrap()),
+        });
+        assert!(state.active_hold().is_none());
+        assert_eq!(state.determine_target_sink(), "headphones");
+    }
+
+    #[test]
+    fn test_reload_drops_hold_on_removed_sink() {
+        let config = make_config(
+            vec![
+                make_sink("speakers", "Speakers", true),
+                make_sink("headset", "Headset", false),
+            ],
+            vec![],
+        );
+        let mut state = State::new_for_testing(Arc::new(config), "headset".to_string());
+        state.hold("headset".to_string(), Some(Duration::from_mins(15)));
+        assert!(state.hold_remaining().is_some());
+
+        let new_config = make_config(vec![make_sink("speakers", "Speakers", true)], vec![]);
+        state.reload_config(Arc::new(new_config));
+        assert!(state.active_hold().is_none());
+    }
+
+    #[test]
+    fn test_timed_pause_expires() {
+        let config = make_config(vec![make_sink("sink1", "Sink 1", true)], vec![]);
+        let mut state = State::new_for_testing(Arc::new(config), "sink1".to_string());
+
+        state.pause(Some(Duration::from_mins(10)));
+        assert!(state.is_paused());
+        assert!(state.pause_remaining().is_some());
+
+        // Simulate the deadline passing
+        state.pause_until = Some(Instant::now().checked_sub(Duration::from_secs(1)).unwrap());
+        assert!(!state.is_paused());
+        assert!(state.should_switch_sink("sink2"));
+    }
+
     // determine_target_sink() tests
     #[test]
     fn test_determine_target_sink_empty_returns_default() {
@@ -748,6 +1430,221 @@ mod tests {
         assert_eq!(state.determine_target_sink(), "sink1");
     }
 
+    fn explain_state(match_by_index: bool) -> State {
+        let mut config = make_config(
+            vec![
+                make_sink("default_sink", "Default", true),
+                make_sink("sink1", "S1", false),
+                make_sink("sink2", "S2", false),
+            ],
+            vec![
+                make_rule("app1", None, "sink1"),
+                make_rule("app2", None, "sink2"),
+            ],
+        );
+        config.settings.match_by_index = match_by_index;
+        let mut state = State::new_for_testing(Arc::new(config), "default_sink".to_string());
+        // Opened in order: rule 0, rule 1, rule 0
+        for (id, rule_index) in [(1, 0), (2, 1), (3, 0)] {
+            let app_id = format!("app{}", rule_index + 1);
+            state.track_window(
+                id,
+                format!("sink{}", rule_index + 1),
+                app_id.clone(),
+                rule_index,
+                app_id,
+                format!("T{id}"),
+            );
+        }
+        state
+    }
+
+    fn verdicts(state: &State) -> Vec<(u64, Verdict)> {
+        state
+            .explain_windows()
+            .into_iter()
+            .map(|(id, _, verdict)| (id, verdict))
+            .collect()
+    }
+
+    #[test]
+    fn test_explain_windows_priority_time() {
+        let state = explain_state(false);
+        assert_eq!(state.priority_mode(), PriorityMode::Time);
+        assert_eq!(
+            verdicts(&state),
+            vec![
+                (3, Verdict::Winner),
+                (2, Verdict::OlderWindow),
+                (1, Verdict::OlderWindow),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_windows_priority_index() {
+        let state = explain_state(true);
+        assert_eq!(state.priority_mode(), PriorityMode::Index);
+        assert_eq!(
+            verdicts(&state),
+            vec![
+                (3, Verdict::Winner),
+                (1, Verdict::OlderSameRule),
+                (2, Verdict::LowerPriorityRule),
+            ]
+        );
+        // The explained winner is the sink determine_target_sink picks
+        let (_, winner, _) = state.explain_windows()[0];
+        assert_eq!(winner.sink_name, state.determine_target_sink());
+    }
+
+    #[test]
+    fn test_explain_windows_held() {
+        let mut state = explain_state(false);
+        state.hold("sink2".to_string(), None);
+        assert!(
+            verdicts(&state)
+                .iter()
+                .all(|(_, verdict)| *verdict == Verdict::Held)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_offline_state_replays_events() {
+        let config = make_config(
+            vec![
+                make_sink("default_sink", "Default", true),
+                make_sink("sink1", "S1", false),
+            ],
+            vec![make_rule("steam", Some("Big Picture"), "sink1")],
+        );
+        let mut state = State::new_offline(Arc::new(config));
+        assert_eq!(state.current_sink_name, "default_sink");
+
+        state
+            .process_event(WindowEvent::Opened {
+                id: 1,
+                app_id: "steam".to_string(),
+                title: "Steam".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(state.current_sink_name, "default_sink");
+
+        state
+            .process_event(WindowEvent::Changed {
+                id: 1,
+                app_id: "steam".to_string(),
+                title: "Steam Big Picture Mode".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(state.current_sink_name, "sink1");
+
+        state
+            .process_event(WindowEvent::Closed { id: 1 })
+            .await
+            .unwrap();
+        assert_eq!(state.current_sink_name, "default_sink");
+    }
+
+    #[tokio::test]
+    async fn test_hold_on_already_active_sink_keeps_it() {
+        let mut config = make_config(
+            vec![
+                make_sink("default_sink", "Default", true),
+                make_sink("sink1", "S1", false),
+            ],
+            vec![],
+        );
+        config.settings.set_smart_toggle = true;
+        let mut state = State::new_offline(Arc::new(config));
+        let set = || ManualRequest::Set("S1".to_string());
+
+        state.manual_switch(&set(), None).await.unwrap();
+        assert_eq!(state.current_sink_name, "sink1");
+
+        // Smart toggle would switch back to the default and hold that instead
+        let message = state.manual_switch(&set(), Some(None)).await.unwrap();
+        assert_eq!(message, "Switched to: S1 (held until released)");
+        assert_eq!(state.current_sink_name, "sink1");
+        assert_eq!(state.active_hold().unwrap().sink_name, "sink1");
+
+        // Without a hold, smart toggle still applies
+        state.manual_hold = None;
+        state.manual_switch(&set(), None).await.unwrap();
+        assert_eq!(state.current_sink_name, "default_sink");
+    }
+
+    #[tokio::test]
+    async fn test_notification_actions_undo_and_keep_here() {
+        let config = make_config(
+            vec![
+                make_sink("default_sink", "Default", true),
+                make_sink("sink1", "S1", false),
+            ],
+            vec![make_rule("steam", None, "sink1")],
+        );
+        let mut state = State::new_offline(Arc::new(config));
+        state
+            .process_event(WindowEvent::Opened {
+                id: 1,
+                app_id: "steam".to_string(),
+                title: "Steam".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(state.current_sink_name, "sink1");
+
+        let event = |action| SwitchActionEvent {
+            action,
+            from_sink: "default_sink".to_string(),
+            to_sink: "sink1".to_string(),
+        };
+
+        state
+            .handle_notification_action(event(NotificationAction::KeepHere))
+            .await
+            .unwrap();
+        assert_eq!(state.active_hold().unwrap().sink_name, "sink1");
+
+        state
+            .handle_notification_action(event(NotificationAction::Undo))
+            .await
+            .unwrap();
+        assert_eq!(state.current_sink_name, "default_sink");
+        // A manual switch during a hold moves the hold along
+        assert_eq!(state.active_hold().unwrap().sink_name, "default_sink");
+
+        // Undo restores sinks missing from the config too, keeping a timed hold's deadline
+        state.hold("sink1".to_string(), Some(Duration::from_mins(5)));
+        let deadline = state.hold_deadline();
+        state.current_sink_name = "sink1".to_string();
+        let unconfigured = SwitchActionEvent {
+            action: NotificationAction::Undo,
+            from_sink: "bluez_output.unconfigured".to_string(),
+            to_sink: "sink1".to_string(),
+        };
+        let message = state
+            .handle_notification_action(unconfigured)
+            .await
+            .unwrap();
+        assert_eq!(message, "Switched back to: bluez_output.unconfigured");
+        assert_eq!(state.current_sink_name, "bluez_output.unconfigured");
+        let hold = state.active_hold().unwrap();
+        assert_eq!(hold.sink_name, "bluez_output.unconfigured");
+        assert_eq!(state.hold_deadline(), deadline);
+        state.current_sink_name = "default_sink".to_string();
+
+        // Stale click: the notification's sink is no longer active
+        let message = state
+            .handle_notification_action(event(NotificationAction::Undo))
+            .await
+            .unwrap();
+        assert!(message.contains("Ignoring"));
+        assert_eq!(state.current_sink_name, "default_sink");
+    }
+
     #[tokio::test]
     async fn test_all_windows_tracking() {
         let config = make_config(
@@ -860,4 +1757,122 @@ mod tests {
         assert_eq!(window.rule_index, 0, "Rule index should update in metadata");
         assert_eq!(window.title, "Music");
     }
+
+    #[tokio::test]
+    async fn test_paused_still_tracks_windows() {
+        let config = make_config(
+            vec![
+                make_sink("speakers", "Speakers", true),
+                make_sink("headphones", "Headphones", false),
+            ],
+            vec![make_rule("mpv", None, "headphones")],
+        );
+        let mut state = State::new_for_testing(Arc::new(config), "speakers".to_string());
+        state.pause(None);
+
+        // Paused: no PipeWire call is made, but the window is tracked
+        state
+            .process_event(WindowEvent::Opened {
+                id: 1,
+                app_id: "mpv".to_string(),
+                title: "Video".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert!(state.is_window_tracked(1));
+        assert_eq!(state.current_sink_name, "speakers");
+        assert_eq!(state.determine_target_sink(), "headphones");
+    }
+
+    // plan_manual_switch() tests
+    fn manual_config(smart_toggle: bool) -> Config {
+        let mut config = make_config(
+            vec![
+                make_sink("speakers", "Speakers", true),
+                make_sink("headphones", "Headphones", false),
+                make_sink("hdmi", "HDMI", false),
+            ],
+            vec![],
+        );
+        config.settings.set_smart_toggle = smart_toggle;
+        config
+    }
+
+    #[test_case("speakers", "2", "headphones" ; "switch by position")]
+    #[test_case("headphones", "Headphones", "speakers" ; "smart toggle back to default")]
+    fn test_plan_manual_set(current: &str, sink_ref: &str, expected: &str) {
+        let config = manual_config(true);
+        let plan = plan_manual_switch(
+            &config,
+            current,
+            &ManualRequest::Set(sink_ref.to_string()),
+            config.settings.set_smart_toggle,
+        )
+        .unwrap();
+        assert!(matches!(plan, ManualSwitch::Switch(s) if s.name == expected));
+    }
+
+    #[test]
+    fn test_plan_manual_set_default_already_on() {
+        let config = manual_config(true);
+        let plan = plan_manual_switch(
+            &config,
+            "speakers",
+            &ManualRequest::Set("1".to_string()),
+            true,
+        )
+        .unwrap();
+        assert!(matches!(plan, ManualSwitch::AlreadyOn(s) if s.name == "speakers"));
+    }
+
+    #[test]
+    fn test_plan_manual_set_without_smart_toggle() {
+        let config = manual_config(false);
+        let plan = plan_manual_switch(
+            &config,
+            "headphones",
+            &ManualRequest::Set("Headphones".to_string()),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(plan, ManualSwitch::Switch(s) if s.name == "headphones"));
+    }
+
+    #[test]
+    fn test_plan_manual_set_unknown_sink() {
+        let config = manual_config(true);
+        let err = plan_manual_switch(
+            &config,
+            "speakers",
+            &ManualRequest::Set("nope".to_string()),
+            true,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Unknown sink 'nope'"));
+    }
+
+    #[test_case("speakers", Direction::Next, "headphones" ; "next")]
+    #[test_case("hdmi", Direction::Next, "speakers" ; "next wraps")]
+    #[test_case("speakers", Direction::Prev, "hdmi" ; "prev wraps")]
+    #[test_case("unknown", Direction::Next, "headphones" ; "unconfigured current starts from default")]
+    fn test_plan_manual_cycle(current: &str, direction: Direction, expected: &str) {
+        let config = manual_config(true);
+        let plan =
+            plan_manual_switch(&config, current, &ManualRequest::Cycle(direction), true).unwrap();
+        assert!(matches!(plan, ManualSwitch::Switch(s) if s.name == expected));
+    }
+
+    #[test]
+    fn test_plan_manual_cycle_single_sink() {
+        let config = make_config(vec![make_sink("speakers", "Speakers", true)], vec![]);
+        let plan = plan_manual_switch(
+            &config,
+            "speakers",
+            &ManualRequest::Cycle(Direction::Next),
+            true,
+        )
+        .unwrap();
+        assert_eq!(plan, ManualSwitch::NothingToCycle);
+    }
 }
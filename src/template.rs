@@ -0,0 +1,147 @@
+//! Output templates
+//!
+//! Minimal `{placeholder}` substitution used by `--format` on the status and
+//! list commands, so status bars (i3blocks, yambar, eww) can consume a single
+//! line without parsing `JSON`. Literal braces are written as `{{` and `}}`.
+
+use color_eyre::eyre::{self, Result};
+
+/// Parsed template segment
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder(String),
+}
+
+/// A parsed output template
+///
+/// Placeholders are checked against the allowed set at parse time, so a typo
+/// fails immediately rather than silently rendering as an empty string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Parse a template string, accepting only the given placeholder names
+    ///
+    /// # Errors
+    /// Returns an error on unbalanced braces or unknown placeholders.
+    pub fn parse(source: &str, allowed: &[&str]) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed {
+                        eyre::bail!("Unclosed '{{' in format template: {source}");
+                    }
+                    if !allowed.contains(&name.as_str()) {
+                        eyre::bail!(
+                            "Unknown placeholder '{{{name}}}' in format template\n\
+                             Available: {}",
+                            allowed
+                                .iter()
+                                .map(|p| format!("{{{p}}}"))
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        );
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(name));
+                }
+                '}' => {
+                    eyre::bail!(
+                        "Unmatched '}}' in format template (use '}}}}' for a literal brace)"
+                    );
+                }
+                _ => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Render the template, substituting placeholder values
+    ///
+    /// Placeholders without a value render as an empty string.
+    #[must_use]
+    pub fn render(&self, values: &[(&str, &str)]) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Placeholder(name) => {
+                    if let Some((_, value)) = values.iter().find(|(k, _)| k == name) {
+                        out.push_str(value);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALLOWED: &[&str] = &["sink_desc", "tracked_count"];
+
+    #[test]
+    fn test_render_substitutes_placeholders() {
+        let template = Template::parse("{sink_desc} ({tracked_count})", ALLOWED).unwrap();
+        let out = template.render(&[("sink_desc", "Speakers"), ("tracked_count", "2")]);
+        assert_eq!(out, "Speakers (2)");
+    }
+
+    #[test]
+    fn test_render_missing_value_is_empty() {
+        let template = Template::parse("[{sink_desc}]", ALLOWED).unwrap();
+        assert_eq!(template.render(&[]), "[]");
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        let template = Template::parse("{{\"text\": \"{sink_desc}\"}}", ALLOWED).unwrap();
+        let out = template.render(&[("sink_desc", "HDMI")]);
+        assert_eq!(out, "{\"text\": \"HDMI\"}");
+    }
+
+    #[test]
+    fn test_unknown_placeholder_rejected() {
+        let err = Template::parse("{sink_dsc}", ALLOWED).unwrap_err();
+        assert!(err.to_string().contains("sink_dsc"));
+    }
+
+    #[test]
+    fn test_unbalanced_braces_rejected() {
+        assert!(Template::parse("{sink_desc", ALLOWED).is_err());
+        assert!(Template::parse("sink_desc}", ALLOWED).is_err());
+    }
+}
@@ -64,7 +64,7 @@ impl Drop for XdgTemp {
 /// minimize test boilerplate while allowing customization of relevant fields.
 #[cfg(test)]
 pub(crate) mod fixtures {
-    use crate::config::{Config, Rule, Settings, SinkConfig};
+    use crate::config::{Config, NotificationSettings, Rule, Settings, SinkConfig};
     use regex::Regex;
 
     /// Create a test `Config` with the given sinks and rules.
@@ -80,8 +80,13 @@ pub(crate) mod fixtures {
                 match_by_index: false,
                 log_level: "info".to_string(),
             },
+            notifications: NotificationSettings::default(),
             sinks,
             rules,
+            hooks: vec![],
+            includes: vec![],
+            included_settings: Default::default(),
+            host: None,
         }
     }
 
@@ -93,7 +98,10 @@ pub(crate) mod fixtures {
             name: name.to_string(),
             desc: desc.to_string(),
             icon: None,
+            glyph: None,
             default,
+            source: None,
+            host: None,
         }
     }
 
@@ -105,7 +113,10 @@ pub(crate) mod fixtures {
             name: name.to_string(),
             desc: desc.to_string(),
             icon: Some(icon.to_string()),
+            glyph: None,
             default,
+            source: None,
+            host: None,
         }
     }
 
@@ -123,8 +134,13 @@ pub(crate) mod fixtures {
             sink_ref: sink_ref.to_string(),
             desc: None,
             notify: None,
+            notify_summary: None,
+            notify_body: None,
+            notify_urgency: None,
             app_id_pattern: app_id.to_string(),
             title_pattern: title.map(String::from),
+            source: None,
+            host: None,
         }
     }
 }
@@ -0,0 +1,240 @@
+//! Window event traces
+//!
+//! Records compositor window events to a JSON Lines file so switching bugs can be
+//! reproduced with `pwsw replay`. The first line is a [`TraceHeader`], every following
+//! line a [`TraceEntry`]:
+//!
+//! ```text
+//! {"pwsw_trace":1,"pwsw_version":"v0.6.0","protocol":"ext","titles_redacted":false}
+//! {"t_ms":0,"event":"opened","id":1,"app_id":"steam","title":"Steam"}
+//! {"t_ms":1520,"event":"closed","id":1}
+//! ```
+
+use color_eyre::eyre::{self, Context, ContextCompat, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::compositor::{Protocol, WindowEvent};
+
+/// Trace file format version, bumped on incompatible changes
+pub const TRACE_FORMAT_VERSION: u32 = 1;
+
+/// Placeholder written instead of window titles when redaction is enabled
+pub const REDACTED_TITLE: &str = "[redacted]";
+
+/// First line of a trace file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceHeader {
+    /// Trace format version ([`TRACE_FORMAT_VERSION`])
+    pub pwsw_trace: u32,
+    /// `pwsw` version that recorded the trace
+    pub pwsw_version: String,
+    /// Window management protocol the compositor thread used
+    pub protocol: Protocol,
+    /// Whether window titles were replaced with [`REDACTED_TITLE`]
+    pub titles_redacted: bool,
+}
+
+/// A recorded window event with its time relative to the start of recording
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Milliseconds since recording started
+    pub t_ms: u64,
+    #[serde(flatten)]
+    pub event: WindowEvent,
+}
+
+/// Options for recording a trace (`pwsw record`, `pwsw daemon --record`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordOptions {
+    pub path: PathBuf,
+    pub redact_titles: bool,
+}
+
+/// Writes window events to a trace file
+pub struct TraceRecorder {
+    writer: BufWriter<File>,
+    started: Instant,
+    redact_titles: bool,
+    events: usize,
+}
+
+impl TraceRecorder {
+    /// Create (or truncate) a trace file and write its header
+    ///
+    /// The file is created with user-only permissions (0o600), since window titles
+    /// can contain private information.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be created or written.
+    pub fn create(options: &RecordOptions, protocol: Protocol) -> Result<Self> {
+        let mut open = std::fs::OpenOptions::new();
+        open.create(true).write(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            open.mode(0o600);
+        }
+        let file = open
+            .open(&options.path)
+            .with_context(|| format!("Failed to create trace file: {}", options.path.display()))?;
+
+        let mut recorder = Self {
+            writer: BufWriter::new(file),
+            started: Instant::now(),
+            redact_titles: options.redact_titles,
+            events: 0,
+        };
+        let header = TraceHeader {
+            pwsw_trace: TRACE_FORMAT_VERSION,
+            pwsw_version: crate::version_string(),
+            protocol,
+            titles_redacted: options.redact_titles,
+        };
+        recorder.write_line(&header)?;
+        Ok(recorder)
+    }
+
+    /// Append a window event, flushing so the trace survives a crash
+    ///
+    /// # Errors
+    /// Returns an error if writing to the trace file fails.
+    pub fn record(&mut self, event: &WindowEvent) -> Result<()> {
+        let mut event = event.clone();
+        if self.redact_titles {
+            redact_title(&mut event);
+        }
+        let entry = TraceEntry {
+            t_ms: u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            event,
+        };
+        self.write_line(&entry)?;
+        self.events += 1;
+        Ok(())
+    }
+
+    /// Number of events recorded so far
+    #[must_use]
+    pub const fn events(&self) -> usize {
+        self.events
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush().context("Failed to write trace file")
+    }
+}
+
+/// Replace the title of an opened/changed event with [`REDACTED_TITLE`]
+fn redact_title(event: &mut WindowEvent) {
+    match event {
+        WindowEvent::Opened { title, .. } | WindowEvent::Changed { title, .. } => {
+            REDACTED_TITLE.clone_into(title);
+        }
+        WindowEvent::Closed { .. } => {}
+    }
+}
+
+/// Parse a trace file's contents into its header and entries
+///
+/// # Errors
+/// Returns an error naming the line number if the header or an entry is invalid,
+/// or if the trace uses a newer format version.
+pub fn parse_trace(contents: &str) -> Result<(TraceHeader, Vec<TraceEntry>)> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, first) = lines.next().context("Trace file is empty")?;
+    let header: TraceHeader =
+        serde_json::from_str(first).context("Invalid trace header (is this a pwsw trace?)")?;
+    if header.pwsw_trace > TRACE_FORMAT_VERSION {
+        eyre::bail!(
+            "Trace format v{} is newer than this pwsw supports (v{TRACE_FORMAT_VERSION})",
+            header.pwsw_trace
+        );
+    }
+
+    let entries = lines
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid trace entry on line {}", i + 1))
+        })
+        .collect::<Result<_>>()?;
+    Ok((header, entries))
+}
+
+/// Read and parse a trace file
+///
+/// # Errors
+/// Returns an error if the file cannot be read or is not a valid trace.
+pub fn read_trace(path: &Path) -> Result<(TraceHeader, Vec<TraceEntry>)> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read trace file: {}", path.display()))?;
+    parse_trace(&contents).with_context(|| format!("Invalid trace file: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_roundtrip_with_redaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = RecordOptions {
+            path: dir.path().join("trace.jsonl"),
+            redact_titles: true,
+        };
+        let mut recorder = TraceRecorder::create(&options, Protocol::Wlr).unwrap();
+        recorder
+            .record(&WindowEvent::Opened {
+                id: 1,
+                app_id: "steam".to_string(),
+                title: "Secret Project".to_string(),
+            })
+            .unwrap();
+        recorder.record(&WindowEvent::Closed { id: 1 }).unwrap();
+        assert_eq!(recorder.events(), 2);
+
+        let (header, entries) = read_trace(&options.path).unwrap();
+        assert_eq!(header.protocol, Protocol::Wlr);
+        assert!(header.titles_redacted);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].event,
+            WindowEvent::Opened {
+                id: 1,
+                app_id: "steam".to_string(),
+                title: REDACTED_TITLE.to_string(),
+            }
+        );
+        assert_eq!(entries[1].event, WindowEvent::Closed { id: 1 });
+        assert!(entries[0].t_ms <= entries[1].t_ms);
+    }
+
+    #[test]
+    fn test_parse_trace_rejects_newer_format() {
+        let contents =
+            r#"{"pwsw_trace":99,"pwsw_version":"v9","protocol":"ext","titles_redacted":false}"#;
+        let err = parse_trace(contents).unwrap_err();
+        assert!(err.to_string().contains("newer"));
+    }
+
+    #[test]
+    fn test_parse_trace_reports_bad_line() {
+        let contents = concat!(
+            r#"{"pwsw_trace":1,"pwsw_version":"v0.6.0","protocol":"ext","titles_redacted":false}"#,
+            "\n",
+            r#"{"t_ms":0,"event":"opened","id":1,"app_id":"a","title":"b"}"#,
+            "\n",
+            r#"{"t_ms":5,"event":"exploded"}"#,
+        );
+        let err = parse_trace(contents).unwrap_err();
+        assert!(err.to_string().contains("line 3"));
+    }
+}
@@ -9,6 +9,7 @@ use throbber_widgets_tui::ThrobberState;
 
 use super::screens::{DashboardScreen, RulesScreen, SettingsScreen, SinksScreen};
 use crate::config::Config;
+use crate::config_backup::Backup;
 use crate::style::colors;
 use std::sync::Arc;
 
@@ -110,6 +111,10 @@ pub enum AppUpdate {
         windows: Vec<crate::ipc::WindowInfo>,
         daemon_manager: Option<crate::daemon_manager::DaemonManager>, // None if daemon not running
         service_enabled: Option<bool>, // None for direct mode, Some(bool) for systemd
+        paused: bool,                  // Automatic switching paused
+        pause_remaining_secs: Option<u64>, // None if not paused or paused indefinitely
+        hold_sink: Option<String>,     // Description of the manually held sink
+        hold_remaining_secs: Option<u64>, // None if not held or held until released
     },
     /// Result message from daemon control action (start/stop/restart/enable/disable)
     ///
@@ -151,7 +156,7 @@ pub enum AppUpdate {
 pub enum BgCommand {
     DaemonAction(DaemonAction),
     /// Request an atomic config save
-    SaveConfig(Config),
+    SaveConfig(Box<Config>),
     /// Request a live-preview match for given patterns. Optionally include compiled regex caches.
     PreviewRequest {
         app_pattern: String,
@@ -336,7 +341,10 @@ impl App {
                 }
             }
             Screen::Settings => {
-                if self.settings_screen.editing_log_level {
+                if self.settings_screen.editing_log_level
+                    || self.settings_screen.editing_text.is_some()
+                    || self.settings_screen.backups.is_some()
+                {
                     ScreenMode::Modal
                 } else {
                     ScreenMode::List
@@ -351,14 +359,15 @@ impl App {
         use super::screens::sinks::SinksMode;
 
         match self.current_screen {
-            Screen::Dashboard | Screen::Settings => false,
+            Screen::Dashboard => false,
+            Screen::Settings => self.settings_screen.editing_text.is_some(),
             Screen::Sinks => {
                 self.sinks_screen.mode == SinksMode::AddEdit
                     && self.sinks_screen.editor.focused_field < 3 // name, desc, icon are inputs
             }
             Screen::Rules => {
                 self.rules_screen.mode == RulesMode::AddEdit
-                    && [0, 1, 3].contains(&self.rules_screen.editor.focused_field) // app_id, title, desc are inputs
+                    && [0, 1, 3, 6, 7].contains(&self.rules_screen.editor.focused_field) // app_id, title, desc, templates are inputs
             }
         }
     }
@@ -461,6 +470,34 @@ impl App {
         Ok(())
     }
 
+    /// Replace the config file with `backup` and load it, discarding unsaved changes
+    ///
+    /// # Errors
+    /// Returns an error if the backup is not a valid config or cannot be written.
+    pub(crate) fn restore_backup(&mut self, backup: &Backup) -> Result<()> {
+        let path = Config::get_config_path()?;
+        let config = Config::restore_backup(&path, &path, backup)?;
+
+        self.sinks_screen.update_display_descs(&config.sinks);
+        self.sinks_screen.selected = self
+            .sinks_screen
+            .selected
+            .min(config.sinks.len().saturating_sub(1));
+        self.rules_screen.selected = self
+            .rules_screen
+            .selected
+            .min(config.rules.len().saturating_sub(1));
+        let selected = self.settings_screen.selected;
+        self.settings_screen = SettingsScreen::new(&config.settings);
+        self.settings_screen.selected = selected;
+
+        self.original_config.clone_from(&config);
+        self.config = config;
+        self.config_dirty = false;
+        self.set_status(format!("Restored config backup from {}", backup.created()));
+        Ok(())
+    }
+
     /// Generate the context bar text based on current app state
     pub(crate) fn context_bar_text(&self) -> Line<'static> {
         use super::screens::rules::RulesMode;
@@ -622,7 +659,9 @@ impl App {
                 Span::styled("[PgUp/PgDn]", Style::default().fg(colors::UI_HIGHLIGHT)),
                 Span::raw(" Scroll Info  "),
                 Span::styled("[Enter/Space]", Style::default().fg(colors::UI_HIGHLIGHT)),
-                Span::raw(" Toggle/Edit"),
+                Span::raw(" Toggle/Edit  "),
+                Span::styled("[r]", Style::default().fg(colors::UI_HIGHLIGHT)),
+                Span::raw(" Restore Backup"),
             ]),
             (Screen::Settings, ScreenMode::Modal) => Line::from(vec![
                 Span::raw("↑↓ Navigate  "),
@@ -677,11 +716,19 @@ impl App {
                 windows,
                 daemon_manager,
                 service_enabled,
+                paused,
+                pause_remaining_secs,
+                hold_sink,
+                hold_remaining_secs,
             } => {
                 self.daemon_running = running;
                 self.window_count = windows.len();
                 self.windows = windows;
                 self.dashboard_screen.service_enabled = service_enabled;
+                self.dashboard_screen.paused = paused;
+                self.dashboard_screen.pause_remaining_secs = pause_remaining_secs;
+                self.dashboard_screen.hold_sink = hold_sink;
+                self.dashboard_screen.hold_remaining_secs = hold_remaining_secs;
                 // Update max actions if daemon manager changed (e.g., service installed/removed)
                 if let Some(dm) = daemon_manager {
                     let is_systemd = dm == crate::daemon_manager::DaemonManager::Systemd;
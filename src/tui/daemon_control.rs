@@ -3,6 +3,7 @@
 use color_eyre::eyre::{self, Context, Result};
 use std::process::{Command, Stdio};
 
+use crate::config::Config;
 use crate::daemon_manager::DaemonManager;
 use crate::ipc;
 
@@ -39,7 +40,11 @@ impl DaemonManager {
                     .context("Failed to get current executable path")?;
 
                 tokio::task::spawn_blocking(move || {
-                    Command::new(&pwsw_path)
+                    let mut command = Command::new(&pwsw_path);
+                    if let Some(path) = Config::config_path_override() {
+                        command.arg("--config").arg(path);
+                    }
+                    command
                         .arg("daemon")
                         .stdin(Stdio::null())
                         .stdout(Stdio::null())
@@ -175,9 +180,7 @@ impl DaemonManager {
                     eyre::bail!("systemctl enable failed: {stderr}");
                 }
             }
-            Self::Direct => {
-                eyre::bail!("Enable/disable only supported with systemd service")
-            }
+            Self::Direct => eyre::bail!("{}", direct_enable_error()),
         }
     }
 
@@ -204,9 +207,7 @@ impl DaemonManager {
                     eyre::bail!("systemctl disable failed: {stderr}");
                 }
             }
-            Self::Direct => {
-                eyre::bail!("Enable/disable only supported with systemd service")
-            }
+            Self::Direct => eyre::bail!("{}", direct_enable_error()),
         }
     }
 
@@ -227,3 +228,12 @@ impl DaemonManager {
         }
     }
 }
+
+/// Why enable/disable is refused without systemd supervision
+fn direct_enable_error() -> &'static str {
+    if Config::instance_id().is_some() {
+        "Enable/disable controls pwsw.service, which runs the default config, not --config"
+    } else {
+        "Enable/disable only supported with systemd service"
+    }
+}
@@ -6,7 +6,7 @@ use tui_input::backend::crossterm::EventHandler;
 use super::app::{App, DaemonAction, Screen};
 use super::screens::rules::RulesMode;
 use super::screens::sinks::SinksMode;
-use crate::config::{Rule, SinkConfig};
+use crate::config::{Config, Rule, SinkConfig};
 use regex::Regex;
 
 /// Handle a single input event and update app state
@@ -33,7 +33,11 @@ fn is_modal_active(app: &App) -> bool {
     match app.current_screen {
         Screen::Sinks => app.sinks_screen.mode != SinksMode::List,
         Screen::Rules => app.rules_screen.mode != RulesMode::List,
-        Screen::Settings => app.settings_screen.editing_log_level,
+        Screen::Settings => {
+            app.settings_screen.editing_log_level
+                || app.settings_screen.editing_text.is_some()
+                || app.settings_screen.backups.is_some()
+        }
         Screen::Dashboard => false,
     }
 }
@@ -53,7 +57,9 @@ fn handle_key_event(app: &mut App, key: KeyEvent) {
         (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
             if app.config_dirty {
                 if let Some(tx) = &app.bg_cmd_tx {
-                    let _ = tx.try_send(crate::tui::app::BgCommand::SaveConfig(app.config.clone()));
+                    let _ = tx.try_send(crate::tui::app::BgCommand::SaveConfig(Box::new(
+                        app.config.clone(),
+                    )));
                     // Don't clear config_dirty - wait for ConfigSaved result
                     app.set_status("Saving configuration...".to_string());
                 } else {
@@ -440,6 +446,58 @@ fn handle_settings_input(app: &mut App, key: KeyEvent) {
         return;
     }
 
+    // If editing a text setting
+    if app.settings_screen.editing_text.is_some() {
+        match key.code {
+            KeyCode::Enter => {
+                match app
+                    .settings_screen
+                    .commit_text(&mut app.config.notifications)
+                {
+                    Ok(()) => app.mark_dirty(),
+                    Err(e) => app.set_status(format!("{e:#}")),
+                }
+            }
+            KeyCode::Esc => {
+                app.settings_screen.editing_text = None;
+            }
+            _ => {
+                if let Some(editor) = app.settings_screen.editing_text.as_mut() {
+                    editor.input.handle_event(&Event::Key(key));
+                }
+            }
+        }
+        return;
+    }
+
+    // If picking a backup to restore
+    if let Some(choices) = &app.settings_screen.backups {
+        match key.code {
+            KeyCode::Up => {
+                app.settings_screen.backup_index =
+                    app.settings_screen.backup_index.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                if app.settings_screen.backup_index + 1 < choices.len() {
+                    app.settings_screen.backup_index += 1;
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(backup) = app.settings_screen.selected_backup().cloned() {
+                    app.settings_screen.backups = None;
+                    if let Err(e) = app.restore_backup(&backup) {
+                        app.set_status(format!("Failed to restore backup: {e:#}"));
+                    }
+                }
+            }
+            KeyCode::Esc => {
+                app.settings_screen.backups = None;
+            }
+            _ => {}
+        }
+        return;
+    }
+
     // Normal settings navigation
     match key.code {
         KeyCode::Up => {
@@ -455,10 +513,18 @@ fn handle_settings_input(app: &mut App, key: KeyEvent) {
             app.settings_screen.scroll_desc_down();
         }
         KeyCode::Enter | KeyCode::Char(' ') => {
-            if app.settings_screen.toggle_current(&mut app.config.settings) {
+            if app.settings_screen.toggle_current(&mut app.config) {
                 app.mark_dirty();
             }
         }
+        KeyCode::Char('r') => {
+            match Config::get_config_path().and_then(|path| app.settings_screen.open_backups(&path))
+            {
+                Ok(0) => app.set_status("No config backups yet".to_string()),
+                Ok(_) => {}
+                Err(e) => app.set_status(format!("Failed to list backups: {e:#}")),
+            }
+        }
         _ => {}
     }
 }
@@ -479,20 +545,28 @@ fn handle_sinks_input(app: &mut App, key: KeyEvent) {
                 (KeyCode::Up, KeyModifiers::SHIFT) => {
                     let idx = app.sinks_screen.selected;
                     if idx > 0 && idx < app.config.sinks.len() {
-                        app.config.sinks.swap(idx, idx - 1);
-                        app.sinks_screen.selected = idx - 1;
-                        app.sinks_screen.update_display_descs(&app.config.sinks);
-                        app.mark_dirty();
+                        match app.config.move_sink(idx, idx - 1) {
+                            Ok(()) => {
+                                app.sinks_screen.selected = idx - 1;
+                                app.sinks_screen.update_display_descs(&app.config.sinks);
+                                app.mark_dirty();
+                            }
+                            Err(e) => app.set_status(format!("{e:#}")),
+                        }
                     }
                 }
                 // Shift+Down: Move sink down in list
                 (KeyCode::Down, KeyModifiers::SHIFT) => {
                     let idx = app.sinks_screen.selected;
                     if idx + 1 < app.config.sinks.len() {
-                        app.config.sinks.swap(idx, idx + 1);
-                        app.sinks_screen.selected = idx + 1;
-                        app.sinks_screen.update_display_descs(&app.config.sinks);
-                        app.mark_dirty();
+                        match app.config.move_sink(idx, idx + 1) {
+                            Ok(()) => {
+                                app.sinks_screen.selected = idx + 1;
+                                app.sinks_screen.update_display_descs(&app.config.sinks);
+                                app.mark_dirty();
+                            }
+                            Err(e) => app.set_status(format!("{e:#}")),
+                        }
                     }
                 }
                 (KeyCode::Char('a'), KeyModifiers::NONE) => {
@@ -698,7 +772,24 @@ fn handle_sink_editor_input(app: &mut App, key: KeyEvent) {
                 } else {
                     Some(app.sinks_screen.editor.icon.value().to_string())
                 },
+                // Glyph has no editor field; keep whatever the config file set
+                glyph: app
+                    .sinks_screen
+                    .editing_index
+                    .and_then(|idx| app.config.sinks.get(idx))
+                    .and_then(|s| s.glyph.clone()),
                 default: app.sinks_screen.editor.default,
+                // Edited sinks are saved back to the file (and host section) they came from
+                source: app
+                    .sinks_screen
+                    .editing_index
+                    .and_then(|idx| app.config.sinks.get(idx))
+                    .and_then(|s| s.source.clone()),
+                host: app
+                    .sinks_screen
+                    .editing_index
+                    .and_then(|idx| app.config.sinks.get(idx))
+                    .and_then(|s| s.host.clone()),
             };
 
             // Force first sink to be default (user doesn't need to think about it)
@@ -724,7 +815,7 @@ fn handle_sink_editor_input(app: &mut App, key: KeyEvent) {
                         sink.default = false;
                     }
                 }
-                app.config.sinks.push(new_sink);
+                app.config.push_sink(new_sink);
                 app.set_status("Sink added".to_string());
             }
 
@@ -778,18 +869,26 @@ fn handle_rules_input(app: &mut App, key: KeyEvent) {
                 (KeyCode::Up, KeyModifiers::SHIFT) => {
                     let idx = app.rules_screen.selected;
                     if idx > 0 && idx < app.config.rules.len() {
-                        app.config.rules.swap(idx, idx - 1);
-                        app.rules_screen.selected = idx - 1;
-                        app.mark_dirty();
+                        match app.config.move_rule(idx, idx - 1) {
+                            Ok(()) => {
+                                app.rules_screen.selected = idx - 1;
+                                app.mark_dirty();
+                            }
+                            Err(e) => app.set_status(format!("{e:#}")),
+                        }
                     }
                 }
                 // Shift+Down: Move rule down in list
                 (KeyCode::Down, KeyModifiers::SHIFT) => {
                     let idx = app.rules_screen.selected;
                     if idx + 1 < app.config.rules.len() {
-                        app.config.rules.swap(idx, idx + 1);
-                        app.rules_screen.selected = idx + 1;
-                        app.mark_dirty();
+                        match app.config.move_rule(idx, idx + 1) {
+                            Ok(()) => {
+                                app.rules_screen.selected = idx + 1;
+                                app.mark_dirty();
+                            }
+                            Err(e) => app.set_status(format!("{e:#}")),
+                        }
                     }
                 }
                 (KeyCode::Char('a'), KeyModifiers::NONE) => {
@@ -905,6 +1004,7 @@ fn handle_rule_editor_input(app: &mut App, key: KeyEvent) {
                         Some(false) => None,
                     };
                 }
+                5 => app.rules_screen.editor.cycle_urgency(),
                 _ => {
                     // For text fields (app_id, title, desc), type a space
                     let event = Event::Key(key);
@@ -990,6 +1090,20 @@ fn handle_rule_editor_input(app: &mut App, key: KeyEvent) {
                         3 => {
                             app.rules_screen.editor.desc.input.handle_event(&event);
                         }
+                        6 => {
+                            app.rules_screen
+                                .editor
+                                .notify_summary
+                                .input
+                                .handle_event(&event);
+                        }
+                        7 => {
+                            app.rules_screen
+                                .editor
+                                .notify_body
+                                .input
+                                .handle_event(&event);
+                        }
                         _ => {}
                     }
                 }
@@ -1048,6 +1162,25 @@ fn handle_rule_editor_input(app: &mut App, key: KeyEvent) {
                 }
             };
 
+            let template = |editor: &crate::tui::editor_state::EditorState| {
+                let value = editor.value();
+                (!value.is_empty()).then(|| value.to_string())
+            };
+            let notify_summary = template(&app.rules_screen.editor.notify_summary);
+            let notify_body = template(&app.rules_screen.editor.notify_body);
+            for (name, value) in [("title", &notify_summary), ("body", &notify_body)] {
+                if let Some(value) = value
+                    && let Err(e) = crate::config::validate_notification_template(value)
+                {
+                    app.set_status(format!("Invalid notification {name}: {e}"));
+                    return;
+                }
+            }
+
+            let existing = app
+                .rules_screen
+                .editing_index
+                .and_then(|idx| app.config.rules.get(idx));
             let new_rule = Rule {
                 app_id_regex,
                 title_regex,
@@ -1058,19 +1191,25 @@ fn handle_rule_editor_input(app: &mut App, key: KeyEvent) {
                     Some(app.rules_screen.editor.desc.value().to_string())
                 },
                 notify: app.rules_screen.editor.notify,
+                notify_summary,
+                notify_body,
+                notify_urgency: app.rules_screen.editor.notify_urgency,
                 app_id_pattern: app.rules_screen.editor.app_id_pattern.value().to_string(),
                 title_pattern: if app.rules_screen.editor.title_pattern.value().is_empty() {
                     None
                 } else {
                     Some(app.rules_screen.editor.title_pattern.value().to_string())
                 },
+                // Edited rules are saved back to the file (and host section) they came from
+                source: existing.and_then(|r| r.source.clone()),
+                host: existing.and_then(|r| r.host.clone()),
             };
 
             if let Some(idx) = app.rules_screen.editing_index {
                 app.config.rules[idx] = new_rule;
                 app.set_status("Rule updated".to_string());
             } else {
-                app.config.rules.push(new_rule);
+                app.config.push_rule(new_rule);
                 app.set_status("Rule added".to_string());
             }
 
@@ -1117,7 +1256,21 @@ fn handle_rule_editor_input(app: &mut App, key: KeyEvent) {
                     // desc
                     app.rules_screen.editor.desc.input.handle_event(&event);
                 }
-                // Fields 2 (sink) and 4 (notify) only respond to Space, handled above
+                6 => {
+                    app.rules_screen
+                        .editor
+                        .notify_summary
+                        .input
+                        .handle_event(&event);
+                }
+                7 => {
+                    app.rules_screen
+                        .editor
+                        .notify_body
+                        .input
+                        .handle_event(&event);
+                }
+                // Fields 2 (sink), 4 (notify) and 5 (urgency) only respond to Space, handled above
                 _ => {}
             }
 
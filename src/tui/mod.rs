@@ -184,6 +184,13 @@ pub async fn run() -> Result<()> {
     };
     app.dashboard_screen.set_max_actions(is_systemd);
 
+    // Warn early if the running daemon is from a different build (e.g., after an upgrade)
+    if crate::ipc::is_daemon_running().await
+        && let Err(e) = crate::ipc::handshake().await
+    {
+        app.set_status(e.to_string().replace('\n', " "));
+    }
+
     let _term_guard = TerminalGuard;
 
     // Setup background update channels and spawn worker
@@ -283,6 +290,21 @@ pub async fn run() -> Result<()> {
                 Vec::new()
             };
 
+            let (paused, pause_remaining_secs, hold_sink, hold_remaining_secs) = if running {
+                match crate::ipc::send_request(crate::ipc::Request::Status).await {
+                    Ok(crate::ipc::Response::Status {
+                        paused,
+                        pause_remaining_secs,
+                        hold_sink,
+                        hold_remaining_secs,
+                        ..
+                    }) => (paused, pause_remaining_secs, hold_sink, hold_remaining_secs),
+                    _ => (false, None, None, None),
+                }
+            } else {
+                (false, None, None, None)
+            };
+
             // Compute fingerprint for window snapshot (for preview re-runs)
             let current_fp = windows_fingerprint(&windows);
             let _ = bg_tx.send(AppUpdate::DaemonState {
@@ -290,6 +312,10 @@ pub async fn run() -> Result<()> {
                 windows: windows.clone(),
                 daemon_manager,
                 service_enabled,
+                paused,
+                pause_remaining_secs,
+                hold_sink,
+                hold_remaining_secs,
             });
 
             (windows, current_fp)
@@ -676,6 +702,7 @@ fn render_ui(frame: &mut ratatui::Frame, app: &mut App) {
             // This avoids overlapping borrows when calling `render_rules` which needs both
             // `&mut app.rules_screen` and a mutable throbber state reference.
             let rules_snapshot = app.config.rules.clone();
+            let lints_snapshot = app.config.lint_rules();
             let sinks_snapshot = app.config.sinks.clone();
             let windows_snapshot = app.windows.clone();
             let preview_snapshot = app.preview.clone();
@@ -689,6 +716,7 @@ fn render_ui(frame: &mut ratatui::Frame, app: &mut App) {
                 main_area,
                 &mut RulesRenderContext {
                     rules: &rules_snapshot,
+                    lints: &lints_snapshot,
                     sinks: &sinks_snapshot,
                     screen_state: rules_screen_mut,
                     windows: &windows_snapshot,
@@ -700,7 +728,8 @@ fn render_ui(frame: &mut ratatui::Frame, app: &mut App) {
         Screen::Settings => render_settings(
             frame,
             main_area,
-            &app.config.settings,
+            &app.config,
+            app.config_dirty,
             &mut app.settings_screen,
         ),
     }
@@ -62,6 +62,10 @@ pub struct DashboardScreen {
     pub current_view: DashboardView, // Toggle between Logs and Windows
     pub max_action_index: usize,  // Maximum action index (2 for direct, 4 for systemd)
     pub service_enabled: Option<bool>, // None for direct mode, Some(true/false) for systemd
+    pub paused: bool,             // Automatic switching paused in the daemon
+    pub pause_remaining_secs: Option<u64>, // Time left on a timed pause
+    pub hold_sink: Option<String>, // Manually held sink (rules suppressed)
+    pub hold_remaining_secs: Option<u64>, // Time left on a timed hold
 }
 
 impl DashboardScreen {
@@ -73,6 +77,10 @@ impl DashboardScreen {
             current_view: DashboardView::Logs, // Default to logs
             max_action_index: 2,               // Default to 3 actions (start/stop/restart)
             service_enabled: None,             // Updated by background worker
+            paused: false,
+            pause_remaining_secs: None,
+            hold_sink: None,
+            hold_remaining_secs: None,
         }
     }
 
@@ -302,6 +310,43 @@ fn render_daemon_section(
         status_spans.push(Span::styled(unit_text, Style::default().fg(unit_color)));
     }
 
+    // Append pause state (only meaningful while the daemon runs)
+    if daemon_running && screen_state.paused {
+        status_spans.push(Span::styled(
+            " · ",
+            Style::default().fg(colors::UI_SECONDARY),
+        ));
+        let pause_text = screen_state.pause_remaining_secs.map_or_else(
+            || "⏸ paused".to_string(),
+            |secs| format!("⏸ paused ({} left)", crate::format_uptime(secs)),
+        );
+        status_spans.push(Span::styled(
+            pause_text,
+            Style::default().fg(colors::UI_WARNING),
+        ));
+    }
+
+    // Append manual hold state
+    if daemon_running && let Some(hold_sink) = &screen_state.hold_sink {
+        status_spans.push(Span::styled(
+            " · ",
+            Style::default().fg(colors::UI_SECONDARY),
+        ));
+        let hold_text = screen_state.hold_remaining_secs.map_or_else(
+            || format!("📌 manual hold: {hold_sink}"),
+            |secs| {
+                format!(
+                    "📌 manual hold: {hold_sink} ({} left)",
+                    crate::format_uptime(secs)
+                )
+            },
+        );
+        status_spans.push(Span::styled(
+            hold_text,
+            Style::default().fg(colors::UI_WARNING),
+        ));
+    }
+
     let mut lines = vec![
         Line::from(status_spans),
         Line::from(""), // Spacing
@@ -493,11 +538,15 @@ fn render_statistics_card(
         ]),
     ];
 
+    let title = config.host.as_ref().map_or_else(
+        || " Overview ".to_string(),
+        |host| format!(" Overview - [host.{}] ", host.name),
+    );
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .title(" Overview "),
+            .title(title),
     );
 
     frame.render_widget(paragraph, area);
@@ -230,7 +230,7 @@ pub fn get_section_at_row(
                 }
             }
             Screen::Settings => {
-                for _ in 0..3 {
+                for _ in 0..4 {
                     add_meta_keybind(&mut metadata);
                 }
             }
@@ -461,7 +461,7 @@ fn build_help_rows(
                     &mut rows,
                     &mut metadata,
                     "Space",
-                    "Open sink selector / Cycle notify",
+                    "Open sink selector / Cycle notify or urgency",
                 );
                 add_keybind(&mut rows, &mut metadata, "Enter", "Save rule / Inspect");
                 add_keybind(&mut rows, &mut metadata, "Esc", "Cancel editing");
@@ -487,6 +487,7 @@ fn build_help_rows(
                     "Enter/Space",
                     "Toggle setting / Open dropdown",
                 );
+                add_keybind(&mut rows, &mut metadata, "r", "Restore a config backup");
                 add_keybind(&mut rows, &mut metadata, "Esc", "Cancel dropdown");
             }
         }
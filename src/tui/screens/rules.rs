@@ -12,7 +12,7 @@ use ratatui::{
 };
 use throbber_widgets_tui::{Throbber, ThrobberState};
 
-use crate::config::{Rule, SinkConfig};
+use crate::config::{NotificationUrgency, Rule, RuleLint, SinkConfig};
 use crate::style::colors;
 use crate::tui::editor_state::EditorState;
 use crate::tui::widgets::{
@@ -40,7 +40,12 @@ pub struct RuleEditor {
     pub sink_ref: String,
     pub desc: EditorState,
     pub notify: Option<bool>,
-    pub focused_field: usize, // 0=app_id, 1=title, 2=sink, 3=desc, 4=notify
+    /// Notification title/body template overrides (empty = use `[notifications]`)
+    pub notify_summary: EditorState,
+    pub notify_body: EditorState,
+    pub notify_urgency: Option<NotificationUrgency>,
+    // 0=app_id, 1=title, 2=sink, 3=desc, 4=notify, 5=urgency, 6=summary, 7=body
+    pub focused_field: usize,
     pub sink_dropdown_index: usize,
     /// State for sink selector dropdown
     pub sink_selector_state: ListState,
@@ -60,6 +65,9 @@ impl RuleEditor {
             sink_ref: String::new(),
             desc: EditorState::new(),
             notify: None,
+            notify_summary: EditorState::new(),
+            notify_body: EditorState::new(),
+            notify_urgency: None,
             focused_field: 0,
             sink_dropdown_index: 0,
             sink_selector_state: ListState::default(),
@@ -85,6 +93,11 @@ impl RuleEditor {
             sink_ref: rule.sink_ref.clone(),
             desc: EditorState::from_string(rule.desc.clone().unwrap_or_default()),
             notify: rule.notify,
+            notify_summary: EditorState::from_string(
+                rule.notify_summary.clone().unwrap_or_default(),
+            ),
+            notify_body: EditorState::from_string(rule.notify_body.clone().unwrap_or_default()),
+            notify_urgency: rule.notify_urgency,
             focused_field: 0,
             sink_dropdown_index: 0,
             sink_selector_state: ListState::default(),
@@ -95,8 +108,20 @@ impl RuleEditor {
         }
     }
 
+    /// Index of the last field (the notification body template)
+    const LAST_FIELD: usize = 7;
+
+    /// Cycle the urgency override: default -> low -> normal -> critical -> default
+    pub(crate) const fn cycle_urgency(&mut self) {
+        self.notify_urgency = match self.notify_urgency {
+            None => Some(NotificationUrgency::Low),
+            Some(NotificationUrgency::Critical) => None,
+            Some(urgency) => Some(urgency.next()),
+        };
+    }
+
     pub(crate) const fn next_field(&mut self) {
-        if self.focused_field < 4 {
+        if self.focused_field < Self::LAST_FIELD {
             self.focused_field += 1;
         }
     }
@@ -212,6 +237,8 @@ impl RulesScreen {
 /// Context for rendering the rules screen (bundles related parameters)
 pub struct RulesRenderContext<'a> {
     pub rules: &'a [Rule],
+    /// Lint findings, one entry per rule (see [`crate::config::Config::lint_rules`])
+    pub lints: &'a [Option<RuleLint>],
     pub sinks: &'a [SinkConfig],
     pub screen_state: &'a mut RulesScreen,
     pub windows: &'a [crate::ipc::WindowInfo],
@@ -222,7 +249,14 @@ pub struct RulesRenderContext<'a> {
 /// Render the rules screen
 pub fn render_rules(frame: &mut Frame, area: Rect, ctx: &mut RulesRenderContext) {
     // Always render the list first as background
-    render_list(frame, area, ctx.rules, ctx.sinks, ctx.screen_state);
+    render_list(
+        frame,
+        area,
+        ctx.rules,
+        ctx.lints,
+        ctx.sinks,
+        ctx.screen_state,
+    );
 
     // Overlay modal on top if active
     match ctx.screen_state.mode {
@@ -240,7 +274,9 @@ pub fn render_rules(frame: &mut Frame, area: Rect, ctx: &mut RulesRenderContext)
         RulesMode::SelectSink => {
             render_sink_selector(frame, area, ctx.sinks, &mut ctx.screen_state.editor);
         }
-        RulesMode::Inspect => render_inspect_popup(frame, area, ctx.rules, ctx.screen_state),
+        RulesMode::Inspect => {
+            render_inspect_popup(frame, area, ctx.rules, ctx.lints, ctx.screen_state);
+        }
     }
 }
 
@@ -250,6 +286,7 @@ fn render_list(
     frame: &mut Frame,
     area: Rect,
     rules: &[Rule],
+    lints: &[Option<RuleLint>],
     sinks: &[SinkConfig],
     screen_state: &mut RulesScreen,
 ) {
@@ -318,10 +355,18 @@ fn render_list(
                 Style::default().fg(colors::UI_HIGHLIGHT),
             ));
 
-            let desc_cell = rule
-                .desc
-                .as_ref()
-                .map_or_else(|| Cell::from(""), |s| Cell::from(s.as_str()));
+            // Rules that never match or only re-select the default get a badge before their description
+            let mut desc_spans = Vec::with_capacity(2);
+            if let Some(lint) = lints.get(i).copied().flatten() {
+                desc_spans.push(Span::styled(
+                    format!("[{}] ", lint.badge()),
+                    Style::default().fg(colors::UI_WARNING),
+                ));
+            }
+            if let Some(desc) = &rule.desc {
+                desc_spans.push(Span::raw(desc.as_str()));
+            }
+            let desc_cell = Cell::from(Line::from(desc_spans));
 
             let row_style = if is_selected {
                 Style::default().bg(colors::UI_SELECTED_BG)
@@ -396,7 +441,13 @@ fn render_list(
 }
 
 /// Render inspect modal
-fn render_inspect_popup(frame: &mut Frame, area: Rect, rules: &[Rule], screen_state: &RulesScreen) {
+fn render_inspect_popup(
+    frame: &mut Frame,
+    area: Rect,
+    rules: &[Rule],
+    lints: &[Option<RuleLint>],
+    screen_state: &RulesScreen,
+) {
     if screen_state.selected >= rules.len() {
         return;
     }
@@ -457,6 +508,24 @@ fn render_inspect_popup(frame: &mut Frame, area: Rect, rules: &[Rule], screen_st
         add_field(&mut lines, "Description", desc);
     }
 
+    if let Some(summary) = &rule.notify_summary {
+        add_field(&mut lines, "Notification Title", summary);
+    }
+    if let Some(body) = &rule.notify_body {
+        add_field(&mut lines, "Notification Body", body);
+    }
+    if let Some(urgency) = rule.notify_urgency {
+        add_field(&mut lines, "Notification Urgency", urgency.name());
+    }
+
+    if let Some(lint) = lints.get(screen_state.selected).copied().flatten() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("⚠ {}", lint.message()),
+            Style::default().fg(colors::UI_WARNING),
+        )));
+    }
+
     lines.push(Line::from("")); // Space before notify status
 
     // Notify status with icon
@@ -500,18 +569,26 @@ fn render_editor(
         title_area,
         sink_area,
         desc_area,
-        notify_area,
+        notify_row,
+        template_row,
         preview_area,
     ] = Layout::vertical([
         Constraint::Length(3), // App ID pattern
         Constraint::Length(3), // Title pattern
         Constraint::Length(3), // Sink selector
         Constraint::Length(3), // Description
-        Constraint::Length(3), // Notify toggle
+        Constraint::Length(3), // Notify toggle | Urgency
+        Constraint::Length(3), // Title template | Body template
         Constraint::Min(6),    // Live preview
     ])
     .margin(2)
     .areas(popup_area);
+    let [notify_area, urgency_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .areas(notify_row);
+    let [summary_area, body_area] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .areas(template_row);
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -617,6 +694,41 @@ fn render_editor(
     let notify_widget = Paragraph::new(Line::from(notify_spans)).block(block);
     frame.render_widget(notify_widget, notify_area);
 
+    // Notification overrides (empty = use the [notifications] section)
+    let urgency_spans = match screen_state.editor.notify_urgency {
+        Some(urgency) => vec![
+            Span::styled("! ", Style::default().fg(colors::UI_WARNING)),
+            Span::raw(format!("Urgency ({})", urgency.name())),
+        ],
+        None => vec![
+            Span::styled("○ ", Style::default().fg(colors::UI_SECONDARY)),
+            Span::raw("Urgency (use global setting)"),
+        ],
+    };
+    let urgency_widget = Paragraph::new(Line::from(urgency_spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(crate::tui::widgets::focus_border_style(
+                screen_state.editor.focused_field == 5,
+            )),
+    );
+    frame.render_widget(urgency_widget, urgency_area);
+    render_input(
+        frame,
+        summary_area,
+        "Notification Title (optional):",
+        &screen_state.editor.notify_summary.input,
+        screen_state.editor.focused_field == 6,
+    );
+    render_input(
+        frame,
+        body_area,
+        "Notification Body (optional):",
+        &screen_state.editor.notify_body.input,
+        screen_state.editor.focused_field == 7,
+    );
+
     // Live preview panel
     render_live_preview(
         frame,
@@ -1,16 +1,21 @@
 //! Settings screen - Configure PWSW behavior
 
+use color_eyre::eyre::{Context, Result};
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Padding, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
 };
 
-use crate::config::Settings;
+use std::path::Path;
+
+use crate::config::{Config, NotificationSettings, validate_notification_template};
+use crate::config_backup::{self, Backup};
 use crate::style::colors;
-use crate::tui::widgets::{centered_modal, modal_size};
+use crate::tui::editor_state::EditorState;
+use crate::tui::widgets::{centered_modal, modal_size, render_input};
 
 /// Minimum height of the description panel at the bottom of the settings screen
 const DESCRIPTION_PANEL_MIN_HEIGHT: u16 = 14;
@@ -25,6 +30,16 @@ pub enum SettingItem {
     SetSmartToggle,
     NotifyManual,
     NotifyRules,
+    NotifySwitch,
+    NotifyReload,
+    NotifyStartStop,
+    NotifyErrors,
+    NotifySummary,
+    NotifyBody,
+    NotifyManualBody,
+    NotifyUrgency,
+    NotifyTimeout,
+    NotifySound,
     MatchByIndex,
     LogLevel,
 }
@@ -37,6 +52,16 @@ impl SettingItem {
             Self::SetSmartToggle,
             Self::NotifyManual,
             Self::NotifyRules,
+            Self::NotifySwitch,
+            Self::NotifyReload,
+            Self::NotifyStartStop,
+            Self::NotifyErrors,
+            Self::NotifySummary,
+            Self::NotifyBody,
+            Self::NotifyManualBody,
+            Self::NotifyUrgency,
+            Self::NotifyTimeout,
+            Self::NotifySound,
             Self::MatchByIndex,
             Self::LogLevel,
         ]
@@ -49,11 +74,43 @@ impl SettingItem {
             Self::SetSmartToggle => "Smart Toggle",
             Self::NotifyManual => "Manual Switch Notifications",
             Self::NotifyRules => "Rule-Based Notifications",
+            Self::NotifySwitch => "Notify: Sink Switches",
+            Self::NotifyReload => "Notify: Config Reloads",
+            Self::NotifyStartStop => "Notify: Daemon Start/Stop",
+            Self::NotifyErrors => "Notify: Errors",
+            Self::NotifySummary => "Notification Title",
+            Self::NotifyBody => "Notification Body",
+            Self::NotifyManualBody => "Manual Switch Body",
+            Self::NotifyUrgency => "Notification Urgency",
+            Self::NotifyTimeout => "Notification Timeout",
+            Self::NotifySound => "Notification Sound",
             Self::MatchByIndex => "Match by Rule Index",
             Self::LogLevel => "Log Level",
         }
     }
 
+    /// Section and key of this setting in the config file
+    pub(crate) const fn config_key(self) -> (&'static str, &'static str) {
+        match self {
+            Self::DefaultOnStartup => ("settings", "default_on_startup"),
+            Self::SetSmartToggle => ("settings", "set_smart_toggle"),
+            Self::NotifyManual => ("settings", "notify_manual"),
+            Self::NotifyRules => ("settings", "notify_rules"),
+            Self::NotifySwitch => ("notifications", "switch"),
+            Self::NotifyReload => ("notifications", "reload"),
+            Self::NotifyStartStop => ("notifications", "start_stop"),
+            Self::NotifyErrors => ("notifications", "errors"),
+            Self::NotifySummary => ("notifications", "summary"),
+            Self::NotifyBody => ("notifications", "body"),
+            Self::NotifyManualBody => ("notifications", "manual_body"),
+            Self::NotifyUrgency => ("notifications", "urgency"),
+            Self::NotifyTimeout => ("notifications", "timeout_ms"),
+            Self::NotifySound => ("notifications", "sound"),
+            Self::MatchByIndex => ("settings", "match_by_index"),
+            Self::LogLevel => ("settings", "log_level"),
+        }
+    }
+
     /// Get short description for this setting
     pub(crate) const fn description(self) -> &'static str {
         match self {
@@ -61,92 +118,41 @@ impl SettingItem {
             Self::SetSmartToggle => "Intelligent toggling for manual sink switches",
             Self::NotifyManual => "Show notifications for manual sink switches",
             Self::NotifyRules => "Show notifications for rule-triggered switches",
+            Self::NotifySwitch => "Show notifications when the sink changes",
+            Self::NotifyReload => "Show a notification after a config reload",
+            Self::NotifyStartStop => "Show notifications when the daemon starts or stops",
+            Self::NotifyErrors => "Show notifications for failed reloads",
+            Self::NotifySummary => "Title template for switch notifications",
+            Self::NotifyBody => "Body template for automatic switch notifications",
+            Self::NotifyManualBody => "Body template for manual switch notifications",
+            Self::NotifyUrgency => "Urgency level sent with every notification",
+            Self::NotifyTimeout => "How long notifications stay on screen",
+            Self::NotifySound => "Sound theme name played with notifications",
             Self::MatchByIndex => "Rule priority strategy for window matching",
             Self::LogLevel => "Logging verbosity level",
         }
     }
 
     /// Get detailed description with examples for this setting
-    pub(crate) const fn detailed_description(self) -> &'static str {
+    pub(crate) fn detailed_description(self) -> String {
+        let (section, key) = self.config_key();
+        let description = crate::config::key_description(section, key).unwrap_or_default();
+        match self.edit_hint() {
+            Some(hint) => format!("{hint}\n\n{description}"),
+            None => description.to_string(),
+        }
+    }
+
+    /// How to change a setting that isn't a plain toggle
+    const fn edit_hint(self) -> Option<&'static str> {
         match self {
-            Self::DefaultOnStartup => {
-                "Automatically switches to the configured default sink when the daemon starts.\n\
-                 \n\
-                 When enabled: Daemon activates default sink on startup.\n\
-                 When disabled: Leaves the currently active sink unchanged.\n\
-                 \n\
-                 Useful for ensuring a consistent audio output when the daemon starts.\n\
-                 \n\
-                 Default: disabled"
-            }
-            Self::SetSmartToggle => {
-                "Intelligent toggling behavior for manual sink switches via CLI.\n\
-                 \n\
-                 When enabled: Running 'pwsw set-sink <name>' toggles back to default\n\
-                 if the sink is already active.\n\
-                 When disabled: Always switches to the specified sink, even if already active.\n\
-                 \n\
-                 Example: If headphones are active:\n\
-                 • Enabled: 'pwsw set-sink headphones' → switches to default sink\n\
-                 • Disabled: 'pwsw set-sink headphones' → stays on headphones\n\
-                 \n\
-                 Default: disabled"
-            }
-            Self::NotifyManual => {
-                "Desktop notifications for manual sink switches and daemon lifecycle events.\n\
-                 \n\
-                 When enabled: Shows notifications for:\n\
-                 • Manual sink switches: 'pwsw set-sink <name>'\n\
-                 • Cycling commands: 'pwsw prev-sink' and 'pwsw next-sink'\n\
-                 • Daemon lifecycle: start and stop events\n\
-                 \n\
-                 When disabled: All manual operations happen silently.\n\
-                 \n\
-                 Requires a notification daemon (e.g., dunst, mako) to be running.\n\
-                 \n\
-                 Default: enabled"
-            }
-            Self::NotifyRules => {
-                "Desktop notifications for automatic rule-triggered sink switches.\n\
-                 \n\
-                 When enabled: Shows notification when daemon switches sink due to a\n\
-                 window matching a rule.\n\
-                 When disabled: Rule-based switches happen silently.\n\
-                 \n\
-                 Useful for debugging rules or understanding why switches occur.\n\
-                 \n\
-                 Default: enabled"
-            }
-            Self::MatchByIndex => {
-                "Rule priority strategy when multiple windows match different rules.\n\
-                 \n\
-                 When enabled: Uses rule priority - higher priority rules always win.\n\
-                 Rules at the top of the list have higher priority than those below.\n\
-                 When disabled: Most recently opened matching window determines active sink.\n\
-                 \n\
-                 Example: Firefox (rule 1, higher priority) and Discord (rule 2, lower priority):\n\
-                 • Enabled: Firefox's sink stays active regardless of which window opened last\n\
-                 • Disabled: Whichever window you focused most recently determines the sink\n\
-                 \n\
-                 Tip: Reorder rules in the Rules tab (arrow keys + Shift+Up/Down) to adjust priority.\n\
-                 \n\
-                 Default: disabled (most recent window)"
-            }
-            Self::LogLevel => {
-                "Logging verbosity level for daemon output.\n\
-                 \n\
-                 Levels (from least to most verbose):\n\
-                 • error: Only critical errors\n\
-                 • warn: Warnings and errors\n\
-                 • info: General information (recommended)\n\
-                 • debug: Detailed debugging information\n\
-                 • trace: Very verbose tracing (for development)\n\
-                 \n\
-                 View logs with: journalctl --user -u pwsw -f\n\
-                 Or in TUI: Dashboard → [l] for logs view\n\
-                 \n\
-                 Default: info"
-            }
+            Self::NotifySummary
+            | Self::NotifyBody
+            | Self::NotifyManualBody
+            | Self::NotifyTimeout => Some("Press Enter to edit."),
+            Self::NotifySound => Some("Press Enter to edit; leave empty for none."),
+            Self::NotifyUrgency => Some("Press Enter to cycle."),
+            _ => None,
         }
     }
 
@@ -154,6 +160,54 @@ impl SettingItem {
     pub(crate) const fn requires_restart(self) -> bool {
         matches!(self, Self::MatchByIndex | Self::LogLevel)
     }
+
+    /// Check if this setting is edited as text
+    pub(crate) const fn is_text(self) -> bool {
+        matches!(
+            self,
+            Self::NotifySummary
+                | Self::NotifyBody
+                | Self::NotifyManualBody
+                | Self::NotifyTimeout
+                | Self::NotifySound
+        )
+    }
+
+    /// Boolean value of a toggle setting (`None` for other settings)
+    fn enabled(self, config: &Config) -> Option<bool> {
+        let settings = &config.settings;
+        let notifications = &config.notifications;
+        match self {
+            Self::DefaultOnStartup => Some(settings.default_on_startup),
+            Self::SetSmartToggle => Some(settings.set_smart_toggle),
+            Self::NotifyManual => Some(settings.notify_manual),
+            Self::NotifyRules => Some(settings.notify_rules),
+            Self::NotifySwitch => Some(notifications.switch),
+            Self::NotifyReload => Some(notifications.reload),
+            Self::NotifyStartStop => Some(notifications.start_stop),
+            Self::NotifyErrors => Some(notifications.errors),
+            Self::MatchByIndex => Some(settings.match_by_index),
+            Self::NotifySummary
+            | Self::NotifyBody
+            | Self::NotifyManualBody
+            | Self::NotifyUrgency
+            | Self::NotifyTimeout
+            | Self::NotifySound
+            | Self::LogLevel => None,
+        }
+    }
+
+    /// Current value of a text setting, as shown in the editor
+    fn text_value(self, notifications: &NotificationSettings) -> String {
+        match self {
+            Self::NotifySummary => notifications.summary.clone(),
+            Self::NotifyBody => notifications.body.clone(),
+            Self::NotifyManualBody => notifications.manual_body.clone(),
+            Self::NotifyTimeout => notifications.timeout_ms.to_string(),
+            Self::NotifySound => notifications.sound.clone().unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
 }
 
 /// Settings screen state
@@ -162,6 +216,8 @@ pub struct SettingsScreen {
     pub selected: usize,
     /// Whether we're editing the log level (dropdown open)
     pub editing_log_level: bool,
+    /// Text editor for the selected text setting (`None` when not editing)
+    pub editing_text: Option<EditorState>,
     /// Selected log level index (0-4 for error/warn/info/debug/trace)
     pub log_level_index: usize,
     /// Cached padded display names for settings (left-aligned)
@@ -170,11 +226,24 @@ pub struct SettingsScreen {
     pub state: ListState,
     /// Description panel scroll offset
     pub desc_scroll: u16,
+    /// Config backups offered for restore (`None` when the picker is closed)
+    pub backups: Option<Vec<BackupChoice>>,
+    /// Selected backup in the restore picker
+    pub backup_index: usize,
+}
+
+/// A backup in the restore picker, with its line changes against the current file
+pub struct BackupChoice {
+    pub backup: Backup,
+    /// Lines only in the current file
+    pub removed: usize,
+    /// Lines only in the backup
+    pub added: usize,
 }
 
 impl SettingsScreen {
     /// Create a new settings screen
-    pub(crate) fn new(settings: &Settings) -> Self {
+    pub(crate) fn new(settings: &crate::config::Settings) -> Self {
         let log_level_index = match settings.log_level.as_str() {
             "error" => 0,
             "warn" => 1,
@@ -197,13 +266,53 @@ impl SettingsScreen {
         Self {
             selected: 0,
             editing_log_level: false,
+            editing_text: None,
             log_level_index,
             padded_names,
             state: ListState::default(),
             desc_scroll: 0,
+            backups: None,
+            backup_index: 0,
         }
     }
 
+    /// Open the restore picker with the backups of the config at `config_path`
+    ///
+    /// The picker stays closed when there are no backups. Returns how many were found.
+    ///
+    /// # Errors
+    /// Returns an error if the backups cannot be listed or read.
+    pub(crate) fn open_backups(&mut self, config_path: &Path) -> Result<usize> {
+        let current = std::fs::read_to_string(config_path).unwrap_or_default();
+        let choices = config_backup::list(config_path)?
+            .into_iter()
+            .map(|backup| {
+                let text = std::fs::read_to_string(&backup.path)
+                    .with_context(|| format!("Failed to read backup: {}", backup.path.display()))?;
+                let changes = config_backup::changed_lines(&current, &text);
+                let removed = changes.iter().filter(|(sign, _)| *sign == '-').count();
+                Ok(BackupChoice {
+                    backup,
+                    removed,
+                    added: changes.len() - removed,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let count = choices.len();
+        self.backup_index = 0;
+        self.backups = (count > 0).then_some(choices);
+        Ok(count)
+    }
+
+    /// Backup selected in the restore picker
+    pub(crate) fn selected_backup(&self) -> Option<&Backup> {
+        self.backups
+            .as_ref()
+            .and_then(|choices| choices.get(self.backup_index))
+            .map(|choice| &choice.backup)
+    }
+
     /// Move selection up
     pub(crate) const fn select_previous(&mut self) {
         if self.selected > 0 {
@@ -231,12 +340,23 @@ impl SettingsScreen {
     }
 
     /// Toggle the currently selected boolean setting
-    pub(crate) fn toggle_current(&mut self, settings: &mut Settings) -> bool {
-        if self.editing_log_level {
-            return false; // Don't toggle while editing log level
+    ///
+    /// Cycles the urgency and opens the editor for text settings. Returns whether the
+    /// config changed.
+    pub(crate) fn toggle_current(&mut self, config: &mut Config) -> bool {
+        if self.editing_log_level || self.editing_text.is_some() {
+            return false; // Don't toggle while editing
+        }
+
+        let settings = &mut config.settings;
+        let notifications = &mut config.notifications;
+        let item = SettingItem::all()[self.selected];
+        if item.is_text() {
+            self.editing_text = Some(EditorState::from_string(item.text_value(notifications)));
+            return false;
         }
 
-        match SettingItem::all()[self.selected] {
+        match item {
             SettingItem::DefaultOnStartup => {
                 settings.default_on_startup = !settings.default_on_startup;
                 true
@@ -253,6 +373,26 @@ impl SettingsScreen {
                 settings.notify_rules = !settings.notify_rules;
                 true
             }
+            SettingItem::NotifySwitch => {
+                notifications.switch = !notifications.switch;
+                true
+            }
+            SettingItem::NotifyReload => {
+                notifications.reload = !notifications.reload;
+                true
+            }
+            SettingItem::NotifyStartStop => {
+                notifications.start_stop = !notifications.start_stop;
+                true
+            }
+            SettingItem::NotifyErrors => {
+                notifications.errors = !notifications.errors;
+                true
+            }
+            SettingItem::NotifyUrgency => {
+                notifications.urgency = notifications.urgency.next();
+                true
+            }
             SettingItem::MatchByIndex => {
                 settings.match_by_index = !settings.match_by_index;
                 true
@@ -262,7 +402,56 @@ impl SettingsScreen {
                 self.editing_log_level = true;
                 false
             }
+            // Handled above
+            SettingItem::NotifySummary
+            | SettingItem::NotifyBody
+            | SettingItem::NotifyManualBody
+            | SettingItem::NotifyTimeout
+            | SettingItem::NotifySound => false,
+        }
+    }
+
+    /// Validate the text being edited and store it in the config
+    ///
+    /// The editor stays open when validation fails.
+    ///
+    /// # Errors
+    /// Returns an error if the template has an unknown placeholder or the timeout is not
+    /// a number.
+    pub(crate) fn commit_text(&mut self, notifications: &mut NotificationSettings) -> Result<()> {
+        let Some(editor) = &self.editing_text else {
+            return Ok(());
+        };
+        let value = editor.value().to_string();
+
+        match self.current_item() {
+            SettingItem::NotifySummary => {
+                validate_notification_template(&value)?;
+                notifications.summary = value;
+            }
+            SettingItem::NotifyBody => {
+                validate_notification_template(&value)?;
+                notifications.body = value;
+            }
+            SettingItem::NotifyManualBody => {
+                validate_notification_template(&value)?;
+                notifications.manual_body = value;
+            }
+            SettingItem::NotifyTimeout => {
+                notifications.timeout_ms = value
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Invalid timeout '{value}' (milliseconds)"))?;
+            }
+            SettingItem::NotifySound => {
+                let sound = value.trim();
+                notifications.sound = (!sound.is_empty()).then(|| sound.to_string());
+            }
+            _ => {}
         }
+
+        self.editing_text = None;
+        Ok(())
     }
 
     /// Get the current selected item
@@ -275,7 +464,8 @@ impl SettingsScreen {
 pub fn render_settings(
     frame: &mut Frame,
     area: Rect,
-    settings: &Settings,
+    config: &Config,
+    config_dirty: bool,
     screen_state: &mut SettingsScreen,
 ) {
     // Calculate dynamic height: 40% of screen, but at least 14 lines
@@ -290,7 +480,7 @@ pub fn render_settings(
     .areas(area);
 
     // Render settings list
-    render_settings_list(frame, list_area, settings, screen_state);
+    render_settings_list(frame, list_area, config, config_dirty, screen_state);
 
     // Render description
     render_description(frame, desc_area, screen_state);
@@ -301,14 +491,15 @@ pub fn render_settings(
 fn render_settings_list(
     frame: &mut Frame,
     area: Rect,
-    settings: &Settings,
+    config: &Config,
+    config_dirty: bool,
     screen_state: &mut SettingsScreen,
 ) {
     let items: Vec<ListItem> = SettingItem::all()
         .iter()
         .enumerate()
         .map(|(i, item)| {
-            let value_text = get_setting_value(*item, settings);
+            let value_text = get_setting_value(*item, config);
             let is_selected = i == screen_state.selected;
 
             let style = if is_selected {
@@ -325,23 +516,10 @@ fn render_settings_list(
                 .map_or(item.name(), String::as_str);
 
             // Apply color styling to boolean toggles
-            let value_span = if *item == SettingItem::LogLevel {
-                Span::styled(value_text, style)
-            } else {
-                let enabled = match item {
-                    SettingItem::DefaultOnStartup => settings.default_on_startup,
-                    SettingItem::SetSmartToggle => settings.set_smart_toggle,
-                    SettingItem::NotifyManual => settings.notify_manual,
-                    SettingItem::NotifyRules => settings.notify_rules,
-                    SettingItem::MatchByIndex => settings.match_by_index,
-                    SettingItem::LogLevel => false,
-                };
-
-                if enabled {
-                    Span::styled("✓ enabled", Style::default().fg(colors::UI_SUCCESS))
-                } else {
-                    Span::styled("✗ disabled", Style::default().fg(colors::UI_ERROR))
-                }
+            let value_span = match item.enabled(config) {
+                Some(true) => Span::styled("✓ enabled", Style::default().fg(colors::UI_SUCCESS)),
+                Some(false) => Span::styled("✗ disabled", Style::default().fg(colors::UI_ERROR)),
+                None => Span::styled(value_text, style),
             };
 
             let mut spans = vec![];
@@ -357,6 +535,17 @@ fn render_settings_list(
             spans.push(Span::raw("     ")); // Separation
             spans.push(value_span);
 
+            // Values from the applied [host.<name>] section are saved back there
+            if let Some(host) = &config.host
+                && let ("settings", key) = item.config_key()
+                && host.settings.iter().any(|k| k == key)
+            {
+                spans.push(Span::styled(
+                    format!("  [host.{}]", host.name),
+                    Style::default().fg(colors::UI_SECONDARY),
+                ));
+            }
+
             let line = Line::from(spans);
 
             let mut item_style = Style::default();
@@ -368,12 +557,16 @@ fn render_settings_list(
         })
         .collect();
 
+    let title = config.host.as_ref().map_or_else(
+        || " Settings ".to_string(),
+        |host| format!(" Settings - [host.{}] applied ", host.name),
+    );
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .title(" Settings "),
+                .title(title),
         )
         .scroll_padding(1);
 
@@ -400,17 +593,39 @@ fn render_settings_list(
     if screen_state.editing_log_level && screen_state.current_item() == SettingItem::LogLevel {
         render_log_level_dropdown(frame, area, screen_state);
     }
+
+    // Render text editor if editing
+    if let Some(editor) = &screen_state.editing_text {
+        render_text_editor(frame, area, screen_state.current_item(), editor);
+    }
+
+    // Render backup picker if open
+    if let Some(choices) = &screen_state.backups {
+        render_backup_picker(
+            frame,
+            area,
+            choices,
+            screen_state.backup_index,
+            config_dirty,
+        );
+    }
 }
 
 /// Get the display value for a setting
-fn get_setting_value(item: SettingItem, settings: &Settings) -> String {
+fn get_setting_value(item: SettingItem, config: &Config) -> String {
+    let notifications = &config.notifications;
+    if let Some(enabled) = item.enabled(config) {
+        return format_bool(enabled);
+    }
     match item {
-        SettingItem::DefaultOnStartup => format_bool(settings.default_on_startup),
-        SettingItem::SetSmartToggle => format_bool(settings.set_smart_toggle),
-        SettingItem::NotifyManual => format_bool(settings.notify_manual),
-        SettingItem::NotifyRules => format_bool(settings.notify_rules),
-        SettingItem::MatchByIndex => format_bool(settings.match_by_index),
-        SettingItem::LogLevel => settings.log_level.clone(),
+        SettingItem::NotifyUrgency => notifications.urgency.name().to_string(),
+        SettingItem::NotifyTimeout if notifications.timeout_ms == 0 => {
+            "until dismissed".to_string()
+        }
+        SettingItem::NotifyTimeout => format!("{} ms", notifications.timeout_ms),
+        SettingItem::NotifySound if notifications.sound.is_none() => "none".to_string(),
+        SettingItem::LogLevel => config.settings.log_level.clone(),
+        _ => item.text_value(notifications),
     }
 }
 
@@ -423,6 +638,25 @@ fn format_bool(value: bool) -> String {
     }
 }
 
+/// Render the text editor for a text setting
+fn render_text_editor(frame: &mut Frame, area: Rect, item: SettingItem, editor: &EditorState) {
+    let popup_area = centered_modal(modal_size::SMALL, area);
+    let [input_area, hint_area] =
+        Layout::vertical([Constraint::Length(3), Constraint::Length(1)]).areas(popup_area);
+
+    frame.render_widget(Clear, input_area);
+    frame.render_widget(Clear, hint_area);
+    render_input(frame, input_area, item.name(), &editor.input, true);
+    frame.render_widget(
+        Paragraph::new("Enter: save  Esc: cancel").style(
+            Style::default()
+                .fg(colors::UI_SECONDARY)
+                .bg(colors::UI_MODAL_BG),
+        ),
+        hint_area,
+    );
+}
+
 /// Render the log level dropdown
 fn render_log_level_dropdown(frame: &mut Frame, area: Rect, screen_state: &SettingsScreen) {
     let log_levels = ["error", "warn", "info", "debug", "trace"];
@@ -477,6 +711,75 @@ fn render_log_level_dropdown(frame: &mut Frame, area: Rect, screen_state: &Setti
     frame.render_widget(list, popup_area);
 }
 
+/// Render the backup restore picker
+fn render_backup_picker(
+    frame: &mut Frame,
+    area: Rect,
+    choices: &[BackupChoice],
+    selected: usize,
+    config_dirty: bool,
+) {
+    let popup_area = centered_modal(modal_size::MEDIUM, area);
+
+    let mut items: Vec<ListItem> = choices
+        .iter()
+        .enumerate()
+        .map(|(i, choice)| {
+            let is_selected = i == selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(colors::UI_SELECTED)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(colors::UI_TEXT)
+            };
+
+            let changes = if choice.added + choice.removed == 0 {
+                Span::styled(
+                    "same as current file",
+                    Style::default().fg(colors::UI_SECONDARY),
+                )
+            } else {
+                Span::styled(
+                    format!("-{} +{} lines", choice.removed, choice.added),
+                    Style::default().fg(colors::UI_WARNING),
+                )
+            };
+
+            ListItem::new(Line::from(vec![
+                if is_selected {
+                    Span::styled(" → ", Style::default().fg(colors::UI_HIGHLIGHT))
+                } else {
+                    Span::raw("   ")
+                },
+                Span::styled(choice.backup.created(), style),
+                Span::raw("  "),
+                changes,
+            ]))
+        })
+        .collect();
+
+    if config_dirty {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Span::styled(
+            "⚠  Unsaved changes will be discarded",
+            Style::default().fg(colors::UI_WARNING),
+        )));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1))
+            .title(" Restore Config Backup ")
+            .style(Style::default().bg(colors::UI_MODAL_BG)),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(list, popup_area);
+}
+
 /// Render the description panel
 fn render_description(frame: &mut Frame, area: Rect, screen_state: &mut SettingsScreen) {
     let current_item = screen_state.current_item();
@@ -59,3 +59,120 @@ fn rules_editor_input_wiring() {
     simulate_key_event(&mut app, ke);
     assert_eq!(app.rules_screen.editor.app_id_pattern.value(), "food");
 }
+
+#[test]
+fn settings_notification_template_editing() {
+    use crate::tui::screens::settings::SettingItem;
+
+    let mut app = make_app_sinks();
+    app.current_screen = crate::tui::app::Screen::Settings;
+    app.settings_screen.selected = SettingItem::all()
+        .iter()
+        .position(|i| *i == SettingItem::NotifyManualBody)
+        .unwrap();
+
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    simulate_key_event(&mut app, key(KeyCode::Enter));
+    assert_eq!(
+        app.settings_screen.editing_text.as_ref().map(|e| e.value()),
+        Some("{sink_desc}")
+    );
+
+    // Unknown placeholder keeps the editor open and the config unchanged
+    simulate_key_event(&mut app, key(KeyCode::Char('{')));
+    simulate_key_event(&mut app, key(KeyCode::Char('x')));
+    simulate_key_event(&mut app, key(KeyCode::Char('}')));
+    simulate_key_event(&mut app, key(KeyCode::Enter));
+    assert!(app.settings_screen.editing_text.is_some());
+    assert_eq!(app.config.notifications.manual_body, "{sink_desc}");
+
+    for _ in 0..3 {
+        simulate_key_event(&mut app, key(KeyCode::Backspace));
+    }
+    simulate_key_event(&mut app, key(KeyCode::Char('!')));
+    simulate_key_event(&mut app, key(KeyCode::Enter));
+    assert!(app.settings_screen.editing_text.is_none());
+    assert_eq!(app.config.notifications.manual_body, "{sink_desc}!");
+    assert!(app.config_dirty);
+}
+
+#[test]
+fn settings_backup_picker_navigation() {
+    let mut app = make_app_sinks();
+    app.current_screen = crate::tui::app::Screen::Settings;
+
+    let temp_dir = tempfile::tempdir().expect("failed to create tempdir");
+    let config_path = temp_dir.path().join("config.toml");
+    assert_eq!(app.settings_screen.open_backups(&config_path).unwrap(), 0);
+    assert!(app.settings_screen.backups.is_none());
+
+    for version in ["a = 1\n", "a = 2\n"] {
+        std::fs::write(&config_path, version).unwrap();
+        crate::config_backup::create(&config_path).unwrap();
+    }
+    std::fs::write(&config_path, "a = 3\n").unwrap();
+    assert_eq!(app.settings_screen.open_backups(&config_path).unwrap(), 2);
+    let choices = app.settings_screen.backups.as_ref().unwrap();
+    assert_eq!((choices[0].removed, choices[0].added), (1, 1));
+
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    simulate_key_event(&mut app, key(KeyCode::Down));
+    simulate_key_event(&mut app, key(KeyCode::Down));
+    assert_eq!(app.settings_screen.backup_index, 1);
+    assert_eq!(
+        app.get_screen_mode(),
+        crate::tui::app::ScreenMode::Modal,
+        "the picker is a modal"
+    );
+
+    simulate_key_event(&mut app, key(KeyCode::Esc));
+    assert!(app.settings_screen.backups.is_none());
+    assert_eq!(
+        app.settings_screen.selected, 0,
+        "Down moved the picker only"
+    );
+}
+
+#[test]
+fn rules_editor_notification_overrides() {
+    let mut app = make_app_sinks();
+    app.current_screen = crate::tui::app::Screen::Rules;
+    app.rules_screen.start_add();
+    app.rules_screen.editor.app_id_pattern =
+        crate::tui::editor_state::EditorState::from_string("mpv".to_string());
+    app.rules_screen.editor.sink_ref = "d1".to_string();
+
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    let type_str = |app: &mut crate::tui::app::App, text: &str| {
+        for c in text.chars() {
+            simulate_key_event(app, key(KeyCode::Char(c)));
+        }
+    };
+
+    app.rules_screen.editor.focused_field = 5;
+    simulate_key_event(&mut app, key(KeyCode::Char(' ')));
+    simulate_key_event(&mut app, key(KeyCode::Tab));
+    type_str(&mut app, "Movie {nope}");
+
+    // Unknown placeholder keeps the editor open
+    simulate_key_event(&mut app, key(KeyCode::Enter));
+    assert!(app.config.rules.is_empty());
+    assert!(
+        app.status_message()
+            .is_some_and(|m| m.contains("Invalid notification title"))
+    );
+
+    for _ in 0.."{nope}".len() {
+        simulate_key_event(&mut app, key(KeyCode::Backspace));
+    }
+    type_str(&mut app, "on {sink_desc}");
+    simulate_key_event(&mut app, key(KeyCode::Enter));
+
+    let rule = &app.config.rules[0];
+    assert_eq!(rule.notify_summary.as_deref(), Some("Movie on {sink_desc}"));
+    assert_eq!(rule.notify_body, None);
+    assert_eq!(
+        rule.notify_urgency,
+        Some(crate::config::NotificationUrgency::Low)
+    );
+}
@@ -44,6 +44,11 @@ mod daemon_log_tests {
         let config = Config {
             sinks: vec![],
             rules: vec![],
+            hooks: vec![],
+            includes: vec![],
+            included_settings: Default::default(),
+            host: None,
+            notifications: crate::config::NotificationSettings::default(),
             settings: crate::config::Settings {
                 default_on_startup: true,
                 set_smart_toggle: true,
@@ -89,6 +94,11 @@ mod daemon_log_tests {
         let config = Config {
             sinks: vec![],
             rules: vec![],
+            hooks: vec![],
+            includes: vec![],
+            included_settings: Default::default(),
+            host: None,
+            notifications: crate::config::NotificationSettings::default(),
             settings: crate::config::Settings {
                 default_on_startup: true,
                 set_smart_toggle: true,
@@ -124,3 +124,352 @@ fn cli_status_shows_helpful_error_when_daemon_not_running() {
     }
     // If daemon is actually running (unlikely in test), that's fine too
 }
+
+#[test]
+fn cli_global_config_flag_is_honored() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("scratch.toml");
+    std::fs::write(
+        &path,
+        "[[sinks]]\nname = \"scratch_sink\"\ndesc = \"Scratch Sink\"\ndefault = true\n",
+    )
+    .unwrap();
+
+    // The flag is global, so it works after the subcommand too
+    let output = pwsw_bin()
+        .args(["validate", "--config"])
+        .arg(&path)
+        .output()
+        .expect("Failed to run pwsw validate --config");
+    assert!(output.status.success(), "validate should accept --config");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Scratch Sink"),
+        "validate should read --config"
+    );
+    assert!(
+        stdout.contains("scratch.toml"),
+        "summary should show the path"
+    );
+
+    let output = pwsw_bin()
+        .arg("--config")
+        .arg(dir.path().join("missing.toml"))
+        .arg("validate")
+        .output()
+        .expect("Failed to run pwsw --config validate");
+    assert!(
+        !output.status.success(),
+        "a missing --config file is an error"
+    );
+    assert!(!dir.path().join("missing.toml").exists());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Config file not found"), "{stderr}");
+}
+
+#[test]
+fn cli_validate_live_without_pipewire_has_its_own_exit_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(
+        &path,
+        "[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+    )
+    .unwrap();
+
+    // An empty PATH hides pw-dump, like a system without PipeWire tools
+    let output = pwsw_bin()
+        .env("PATH", dir.path())
+        .arg("--config")
+        .arg(&path)
+        .args(["validate", "--live"])
+        .output()
+        .expect("Failed to run pwsw validate --live");
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Failed to query PipeWire"), "{stderr}");
+}
+
+#[test]
+fn cli_config_flag_uses_its_own_daemon_socket() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("scratch.toml");
+    std::fs::write(
+        &path,
+        "[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+    )
+    .unwrap();
+
+    // Stands in for the usual daemon, which a --config command must not talk to
+    let listener = std::os::unix::net::UnixListener::bind(dir.path().join("pwsw.sock")).unwrap();
+    listener.set_nonblocking(true).unwrap();
+
+    let output = pwsw_bin()
+        .env("XDG_RUNTIME_DIR", dir.path())
+        .arg("--config")
+        .arg(&path)
+        .arg("shutdown")
+        .output()
+        .expect("Failed to run pwsw --config shutdown");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Daemon is not running"), "{stderr}");
+    assert!(listener.accept().is_err(), "connected to the usual socket");
+}
+
+#[test]
+fn cli_config_migrate_upgrades_and_backs_up() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("old.toml");
+    let original =
+        "# Kept across migrations\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n";
+    std::fs::write(&path, original).unwrap();
+
+    let output = pwsw_bin()
+        .arg("--config")
+        .arg(&path)
+        .args(["config", "migrate"])
+        .output()
+        .expect("Failed to run pwsw config migrate");
+    assert!(output.status.success(), "migrate should succeed");
+    let migrated = std::fs::read_to_string(&path).unwrap();
+    assert!(migrated.starts_with("version = "), "{migrated}");
+    assert!(migrated.contains("# Kept across migrations"));
+    let backups: Vec<_> = std::fs::read_dir(dir.path().join("backups"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(backups.len(), 1);
+    assert!(
+        backups[0]
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("old-")
+    );
+    assert_eq!(std::fs::read_to_string(&backups[0]).unwrap(), original);
+
+    let output = pwsw_bin()
+        .arg("--config")
+        .arg(&path)
+        .args(["config", "migrate"])
+        .output()
+        .expect("Failed to run pwsw config migrate");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("already at version"), "{stdout}");
+}
+
+#[test]
+fn cli_config_backups_list_and_restore() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "[settings]\nlog_level = \"info\"\n").unwrap();
+
+    let run = |args: &[&str]| {
+        pwsw_bin()
+            .arg("--config")
+            .arg(&path)
+            .arg("config")
+            .args(args)
+            .output()
+            .expect("Failed to run pwsw config")
+    };
+
+    let output = run(&["backups"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No backups"));
+
+    assert!(run(&["set", "log_level", "debug"]).status.success());
+    let output = run(&["backups"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("- log_level = \"debug\""), "{stdout}");
+    assert!(stdout.contains("+ log_level = \"info\""), "{stdout}");
+
+    let output = run(&["restore", "9"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("No backup '9'"));
+
+    let output = run(&["restore", "1"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = pwsw::config::Config::load_from_path(&path).unwrap();
+    assert_eq!(config.settings.log_level, "info");
+
+    // The restored-over version is kept too
+    let stdout = String::from_utf8_lossy(&run(&["backups"]).stdout).into_owned();
+    assert!(stdout.contains("2."), "{stdout}");
+
+    // Included files are backed up when a save changes them, and restored with --file
+    std::fs::write(
+        &path,
+        "include = [\"rules.toml\"]\n\n[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n",
+    )
+    .unwrap();
+    let rules_path = dir.path().join("rules.toml");
+    std::fs::write(&rules_path, "[[rules]]\napp_id = \"steam\"\nsink = \"S\"\n").unwrap();
+    assert!(run(&["remove-rule", "1"]).status.success());
+    assert!(
+        !std::fs::read_to_string(&rules_path)
+            .unwrap()
+            .contains("steam")
+    );
+
+    let stdout = String::from_utf8_lossy(&run(&["backups"]).stdout).into_owned();
+    assert!(stdout.contains("--file"), "{stdout}");
+    let output = run(&["backups", "--file", "rules.toml"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+ app_id = \"steam\""), "{stdout}");
+    let output = run(&["restore", "1", "--file", "rules.toml"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        std::fs::read_to_string(&rules_path)
+            .unwrap()
+            .contains("steam")
+    );
+}
+
+#[test]
+fn cli_config_schema_is_valid_json() {
+    let output = pwsw_bin()
+        .args(["config", "schema"])
+        .output()
+        .expect("Failed to run pwsw config schema");
+    assert!(output.status.success(), "schema should succeed");
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("schema should be valid JSON");
+    assert!(schema["properties"]["settings"]["properties"]["match_by_index"].is_object());
+}
+
+#[test]
+fn cli_config_edits_are_validated_and_saved() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "# Provisioned\n[settings]\nlog_level = \"info\"\n").unwrap();
+
+    let edit = |args: &[&str]| {
+        pwsw_bin()
+            .arg("--config")
+            .arg(&path)
+            .arg("config")
+            .args(args)
+            .output()
+            .expect("Failed to run pwsw config")
+    };
+
+    for args in [
+        &[
+            "add-sink",
+            "--name",
+            "alsa_output.speakers",
+            "--desc",
+            "Speakers",
+        ][..],
+        &[
+            "add-sink",
+            "--name",
+            "bluez_output.headset",
+            "--desc",
+            "Headset",
+        ],
+        &["add-rule", "--app-id", "^steam$", "--sink", "Headset"],
+        &["add-rule", "--app-id", "mpv", "--sink", "Speakers"],
+        &["move-rule", "2", "1"],
+        &["set", "log_level", "debug"],
+    ] {
+        let output = edit(args);
+        assert!(
+            output.status.success(),
+            "{args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    let config = pwsw::config::Config::load_from_path(&path).unwrap();
+    assert_eq!(config.sinks.len(), 2);
+    assert!(config.sinks[0].default, "first sink becomes the default");
+    assert_eq!(config.rules[0].app_id_pattern, "mpv");
+    assert_eq!(config.rules[1].app_id_pattern, "^steam$");
+    assert_eq!(config.settings.log_level, "debug");
+    assert!(
+        std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# Provisioned")
+    );
+
+    // Rejected edits exit non-zero with the validation message and leave the file alone
+    let before = std::fs::read_to_string(&path).unwrap();
+    let output = edit(&["add-rule", "--app-id", "firefox", "--sink", "Missing"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown sink 'Missing'"));
+    let output = edit(&["remove-rule", "3"]);
+    assert!(!output.status.success());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+
+    let output = edit(&["remove-rule", "1"]);
+    assert!(output.status.success());
+    let config = pwsw::config::Config::load_from_path(&path).unwrap();
+    assert_eq!(config.rules.len(), 1);
+}
+
+#[test]
+fn cli_config_set_edits_the_include_that_overrides_the_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    let extra_path = dir.path().join("extra.toml");
+    std::fs::write(
+        &path,
+        "include = [\"extra.toml\"]\n\n[settings]\nlog_level = \"info\"\n",
+    )
+    .unwrap();
+    std::fs::write(&extra_path, "[settings]\nlog_level = \"debug\"\n").unwrap();
+
+    let output = pwsw_bin()
+        .arg("--config")
+        .arg(&path)
+        .args(["config", "set", "log_level", "warn"])
+        .output()
+        .expect("Failed to run pwsw config set");
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("extra.toml"), "{stdout}");
+
+    assert!(
+        std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("log_level = \"info\"")
+    );
+    assert!(
+        std::fs::read_to_string(&extra_path)
+            .unwrap()
+            .contains("log_level = \"warn\"")
+    );
+    let config = pwsw::config::Config::load_from_path(&path).unwrap();
+    assert_eq!(config.settings.log_level, "warn");
+}
+
+#[test]
+fn cli_init_keeps_existing_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    let original = "[[sinks]]\nname = \"s\"\ndesc = \"S\"\ndefault = true\n";
+    std::fs::write(&path, original).unwrap();
+
+    let output = pwsw_bin()
+        .arg("--config")
+        .arg(&path)
+        .args(["init", "--yes"])
+        .output()
+        .expect("Failed to run pwsw init");
+    assert!(!output.status.success(), "init should not replace a config");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--force"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), original);
+}
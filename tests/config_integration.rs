@@ -234,3 +234,33 @@ default = false
         "Duplicate sink names should fail validation"
     );
 }
+
+#[test]
+fn test_config_sink_glyph_roundtrip() {
+    let (_temp, config_path) = setup_temp_config();
+
+    let toml_content = r#"
+[[sinks]]
+name = "speakers"
+desc = "Speakers"
+default = true
+glyph = "S"
+
+[[sinks]]
+name = "headphones"
+desc = "Headphones"
+icon = "audio-headphones"
+"#;
+
+    fs::write(&config_path, toml_content).expect("Failed to write TOML");
+
+    let config = pwsw::config::Config::load_from_path(&config_path).expect("Failed to load");
+    assert_eq!(config.sinks[0].status_glyph(), Some("S"));
+    // Without a glyph, the custom icon is used
+    assert_eq!(config.sinks[1].status_glyph(), Some("audio-headphones"));
+
+    config.save_to(&config_path).expect("Failed to save");
+    let reloaded = pwsw::config::Config::load_from_path(&config_path).expect("Failed to reload");
+    assert_eq!(reloaded.sinks[0].glyph, Some("S".to_string()));
+    assert_eq!(reloaded.sinks[1].glyph, None);
+}
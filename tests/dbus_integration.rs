@@ -0,0 +1,209 @@
+//! D-Bus service integration tests
+//!
+//! These tests run the `io.github.pwsw` service against a private
+//! `dbus-daemon --session` instance and answer its requests with canned
+//! responses in place of the daemon main loop. They are skipped when
+//! `dbus-daemon` is not installed.
+
+use futures_util::StreamExt;
+use pwsw::dbus::{BUS_NAME, DbusRequest, DbusService, OBJECT_PATH};
+use pwsw::ipc::{Request, Response, TrackedInfo, WindowInfo};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use tokio::sync::mpsc;
+use zbus::zvariant::OwnedValue;
+
+const INTERFACE: &str = "io.github.pwsw";
+
+/// Private session bus, killed on drop
+struct PrivateBus {
+    child: Child,
+    address: String,
+}
+
+impl PrivateBus {
+    /// Start `dbus-daemon --session`, or `None` if it is not installed
+    fn start() -> Option<Self> {
+        let mut child = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .ok()?;
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let mut address = String::new();
+        BufReader::new(stdout)
+            .read_line(&mut address)
+            .expect("Failed to read bus address");
+        Some(Self {
+            child,
+            address: address.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for PrivateBus {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Answer forwarded requests the way the daemon main loop would
+fn spawn_fake_daemon(mut requests: mpsc::Receiver<DbusRequest>) {
+    tokio::spawn(async move {
+        while let Some((request, reply)) = requests.recv().await {
+            let response = match request {
+                Request::SetSink { sink, .. } if sink == "Nowhere" => Response::Error {
+                    message: "Unknown sink 'Nowhere'".to_string(),
+                },
+                Request::SetSink { sink, hold, .. } => {
+                    assert!(!hold, "D-Bus SetSink never holds");
+                    Response::Ok {
+                        message: format!("Switched to {sink}"),
+                    }
+                }
+                Request::CycleSink { direction } => Response::Ok {
+                    message: format!("Cycled {direction:?}"),
+                },
+                Request::Status => Response::Status {
+                    version: "v0.6.0".to_string(),
+                    uptime_secs: 42,
+                    current_sink: "Speakers".to_string(),
+                    active_window: None,
+                    tracked_windows: 1,
+                    active_app_id: Some("steam".to_string()),
+                    active_rule_desc: None,
+                    paused: false,
+                    pause_remaining_secs: None,
+                    hold_sink: None,
+                    hold_remaining_secs: None,
+                },
+                Request::ListWindows => Response::Windows {
+                    windows: vec![
+                        WindowInfo {
+                            id: Some(1),
+                            app_id: "steam".to_string(),
+                            title: "Steam".to_string(),
+                            matched_on: None,
+                            tracked: Some(TrackedInfo {
+                                sink_name: "headphones".to_string(),
+                                sink_desc: "Headphones".to_string(),
+                            }),
+                        },
+                        WindowInfo {
+                            id: Some(2),
+                            app_id: "firefox".to_string(),
+                            title: "Docs".to_string(),
+                            matched_on: None,
+                            tracked: None,
+                        },
+                    ],
+                },
+                other => panic!("Unexpected request over D-Bus: {other:?}"),
+            };
+            let _ = reply.send(response);
+        }
+    });
+}
+
+/// Start the service on a private bus and return a client connection
+async fn setup() -> Option<(PrivateBus, DbusService, zbus::Connection)> {
+    let Some(bus) = PrivateBus::start() else {
+        eprintln!("dbus-daemon not installed, skipping D-Bus test");
+        return None;
+    };
+    let (tx, rx) = mpsc::channel(16);
+    spawn_fake_daemon(rx);
+    let service = DbusService::start(Some(&bus.address), tx)
+        .await
+        .expect("Failed to start D-Bus service");
+    let client = zbus::connection::Builder::address(bus.address.as_str())
+        .unwrap()
+        .build()
+        .await
+        .expect("Failed to connect client");
+    Some((bus, service, client))
+}
+
+async fn call<B>(client: &zbus::Connection, method: &str, body: &B) -> zbus::Result<zbus::Message>
+where
+    B: serde::Serialize + zbus::zvariant::DynamicType,
+{
+    client
+        .call_method(Some(BUS_NAME), OBJECT_PATH, Some(INTERFACE), method, body)
+        .await
+}
+
+#[tokio::test]
+async fn test_dbus_set_and_cycle_sink() {
+    let Some((_bus, _service, client)) = setup().await else {
+        return;
+    };
+
+    let reply = call(&client, "SetSink", &("Headphones",)).await.unwrap();
+    assert_eq!(
+        reply.body().deserialize::<String>().unwrap(),
+        "Switched to Headphones"
+    );
+
+    let reply = call(&client, "NextSink", &()).await.unwrap();
+    assert_eq!(reply.body().deserialize::<String>().unwrap(), "Cycled Next");
+
+    let err = call(&client, "SetSink", &("Nowhere",)).await.unwrap_err();
+    assert!(err.to_string().contains("Unknown sink 'Nowhere'"));
+}
+
+#[tokio::test]
+async fn test_dbus_status_and_list_windows() {
+    let Some((_bus, _service, client)) = setup().await else {
+        return;
+    };
+
+    let reply = call(&client, "Status", &()).await.unwrap();
+    let status: HashMap<String, OwnedValue> = reply.body().deserialize().unwrap();
+    assert_eq!(
+        String::try_from(status["current_sink"].clone()).unwrap(),
+        "Speakers"
+    );
+    assert_eq!(u64::try_from(status["tracked_windows"].clone()).unwrap(), 1);
+    assert!(!bool::try_from(status["paused"].clone()).unwrap());
+    assert!(status.contains_key("active_app_id"));
+    assert!(!status.contains_key("hold_sink"));
+
+    let reply = call(&client, "ListWindows", &()).await.unwrap();
+    let windows: Vec<HashMap<String, OwnedValue>> = reply.body().deserialize().unwrap();
+    assert_eq!(windows.len(), 2);
+    assert!(bool::try_from(windows[0]["tracked"].clone()).unwrap());
+    assert_eq!(
+        String::try_from(windows[0]["sink_desc"].clone()).unwrap(),
+        "Headphones"
+    );
+    assert!(!windows[1].contains_key("sink_name"));
+}
+
+#[tokio::test]
+async fn test_dbus_sink_changed_signal() {
+    let Some((_bus, service, client)) = setup().await else {
+        return;
+    };
+
+    let proxy = zbus::Proxy::new(&client, BUS_NAME, OBJECT_PATH, INTERFACE)
+        .await
+        .unwrap();
+    let mut signals = proxy.receive_signal("SinkChanged").await.unwrap();
+
+    service
+        .sink_changed("headphones", "Headphones")
+        .await
+        .unwrap();
+
+    let signal = tokio::time::timeout(std::time::Duration::from_secs(5), signals.next())
+        .await
+        .expect("Timed out waiting for SinkChanged")
+        .expect("Signal stream ended");
+    let (name, desc): (String, String) = signal.body().deserialize().unwrap();
+    assert_eq!(name, "headphones");
+    assert_eq!(desc, "Headphones");
+}